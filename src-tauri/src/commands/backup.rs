@@ -0,0 +1,553 @@
+use super::binaries::{
+    FAKE_FILES, FILTERS, calculate_sha256_bytes, get_fake_dir, get_lists_dir, sanitize_filename,
+};
+use super::config::{
+    AppConfig, AppState, ensure_config_exists_and_loaded, ensure_runtime_data_dir_ready,
+    get_config_path, get_filters_dir, get_runtime_data_dir, parse_config_snapshot,
+};
+use super::history::snapshot_config_history;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const BACKUP_FORMAT: &str = "zapret-interactive-backup";
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const BACKUPS_DIR_NAME: &str = "backups";
+const BACKUP_FILE_PREFIX: &str = "zapret-interactive-backup-";
+const MANIFEST_ENTRY: &str = "manifest.json";
+const CONFIG_ENTRY: &str = "config.json";
+const USER_LIST_FILES: &[&str] = &["zapret-hosts-user-exclude.txt", "zapret-ip-user.txt"];
+const MAX_ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupEntryKind {
+    Config,
+    List,
+    Filter,
+    Fake,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupEntry {
+    pub path: String,
+    pub kind: BackupEntryKind,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub format: String,
+    pub format_version: u32,
+    pub app_version: String,
+    pub created_at: u64,
+    pub entries: Vec<BackupEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupFileInfo {
+    pub path: String,
+    pub file_name: String,
+    pub size: u64,
+    pub modified_at: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RestoreAction {
+    Create,
+    Replace,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestorePreviewEntry {
+    pub path: String,
+    pub kind: BackupEntryKind,
+    pub action: RestoreAction,
+    pub target_path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupRestorePreview {
+    pub path: String,
+    pub manifest: BackupManifest,
+    pub entries: Vec<RestorePreviewEntry>,
+}
+
+struct BackupFile {
+    entry: BackupEntry,
+    target: PathBuf,
+    bytes: Vec<u8>,
+}
+
+fn unix_millis_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn get_backups_dir() -> PathBuf {
+    get_runtime_data_dir().join(BACKUPS_DIR_NAME)
+}
+
+fn entry_target(path: &str, kind: BackupEntryKind) -> Result<PathBuf, String> {
+    let (dir, name) = path.split_once('/').unwrap_or(("", path));
+    let invalid = || format!("Неожиданный файл в резервной копии: {path}");
+
+    match (kind, dir) {
+        (BackupEntryKind::Config, "") if name == CONFIG_ENTRY => Ok(get_config_path()),
+        (BackupEntryKind::List, "lists") if USER_LIST_FILES.contains(&name) => {
+            Ok(get_lists_dir().join(name))
+        }
+        (BackupEntryKind::Filter, "filters") => {
+            let name = sanitize_filename(name).map_err(|_| invalid())?;
+            if FILTERS.contains(&name.as_str()) {
+                return Err(invalid());
+            }
+            Ok(get_filters_dir().join(name))
+        }
+        (BackupEntryKind::Fake, "fake") => {
+            let name = sanitize_filename(name).map_err(|_| invalid())?;
+            if FAKE_FILES.contains(&name.as_str()) {
+                return Err(invalid());
+            }
+            Ok(get_fake_dir().join(name))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+fn user_files_in(dir: &Path, builtin: &[&str]) -> Result<Vec<String>, String> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut names = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        if !entry.file_type().map_err(|e| e.to_string())?.is_file() {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if builtin.contains(&name.as_str())
+            || name.ends_with(".tmp")
+            || sanitize_filename(&name).is_err()
+        {
+            continue;
+        }
+        names.push(name);
+    }
+
+    names.sort();
+    Ok(names)
+}
+
+fn collect_backup_files() -> Result<Vec<BackupFile>, String> {
+    let mut sources = vec![(CONFIG_ENTRY.to_string(), BackupEntryKind::Config)];
+    sources.extend(
+        USER_LIST_FILES
+            .iter()
+            .map(|name| (format!("lists/{name}"), BackupEntryKind::List)),
+    );
+    sources.extend(
+        user_files_in(&get_filters_dir(), FILTERS)?
+            .into_iter()
+            .map(|name| (format!("filters/{name}"), BackupEntryKind::Filter)),
+    );
+    sources.extend(
+        user_files_in(&get_fake_dir(), FAKE_FILES)?
+            .into_iter()
+            .map(|name| (format!("fake/{name}"), BackupEntryKind::Fake)),
+    );
+
+    let mut files = Vec::new();
+    for (path, kind) in sources {
+        let target = entry_target(&path, kind)?;
+        if kind != BackupEntryKind::Config && !target.is_file() {
+            continue;
+        }
+        let bytes = fs::read(&target).map_err(|e| format!("Не удалось прочитать {path}: {e}"))?;
+        files.push(BackupFile {
+            entry: BackupEntry {
+                path,
+                kind,
+                size: bytes.len() as u64,
+                sha256: calculate_sha256_bytes(&bytes),
+            },
+            target,
+            bytes,
+        });
+    }
+
+    Ok(files)
+}
+
+fn write_backup_archive(
+    path: &Path,
+    manifest: &BackupManifest,
+    files: &[BackupFile],
+) -> Result<(), String> {
+    let temp_path = path.with_extension("zip.tmp");
+    let file = fs::File::create(&temp_path).map_err(|e| e.to_string())?;
+    let mut writer = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let manifest_bytes = serde_json::to_vec_pretty(manifest).map_err(|e| e.to_string())?;
+    for (name, bytes) in std::iter::once((MANIFEST_ENTRY, manifest_bytes.as_slice())).chain(
+        files
+            .iter()
+            .map(|file| (file.entry.path.as_str(), file.bytes.as_slice())),
+    ) {
+        writer
+            .start_file(name, options)
+            .map_err(|e| e.to_string())?;
+        writer.write_all(bytes).map_err(|e| e.to_string())?;
+    }
+
+    let file = writer.finish().map_err(|e| e.to_string())?;
+    file.sync_all().map_err(|e| e.to_string())?;
+    drop(file);
+    fs::rename(&temp_path, path).map_err(|e| e.to_string())
+}
+
+fn read_archive_entry<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<Vec<u8>, String> {
+    let entry = archive
+        .by_name(name)
+        .map_err(|_| format!("В резервной копии нет {name}"))?;
+    if entry.size() > MAX_ENTRY_SIZE {
+        return Err(format!("Файл {name} в резервной копии слишком большой"));
+    }
+
+    let mut bytes = Vec::new();
+    entry
+        .take(MAX_ENTRY_SIZE + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Не удалось прочитать {name} из резервной копии: {e}"))?;
+    Ok(bytes)
+}
+
+fn read_backup_archive(path: &Path) -> Result<(BackupManifest, Vec<BackupFile>), String> {
+    let file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| format!("Не удалось открыть архив резервной копии: {e}"))?;
+    let manifest: BackupManifest =
+        serde_json::from_slice(&read_archive_entry(&mut archive, MANIFEST_ENTRY)?)
+            .map_err(|e| format!("Некорректный манифест резервной копии: {e}"))?;
+
+    if manifest.format != BACKUP_FORMAT {
+        return Err("Файл не является резервной копией Zapret Interactive".to_string());
+    }
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(format!(
+            "Формат резервной копии {} новее поддерживаемого {BACKUP_FORMAT_VERSION}",
+            manifest.format_version
+        ));
+    }
+    if !manifest
+        .entries
+        .iter()
+        .any(|entry| entry.kind == BackupEntryKind::Config)
+    {
+        return Err(format!("В резервной копии нет {CONFIG_ENTRY}"));
+    }
+
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for entry in &manifest.entries {
+        if !seen.insert(entry.path.as_str()) {
+            return Err(format!(
+                "Повторяющийся файл в резервной копии: {}",
+                entry.path
+            ));
+        }
+        let target = entry_target(&entry.path, entry.kind)?;
+        let bytes = read_archive_entry(&mut archive, &entry.path)?;
+        if bytes.len() as u64 != entry.size || calculate_sha256_bytes(&bytes) != entry.sha256 {
+            return Err(format!("Контрольная сумма {} не совпадает", entry.path));
+        }
+        if entry.kind == BackupEntryKind::Config {
+            let content = std::str::from_utf8(&bytes).map_err(|e| e.to_string())?;
+            parse_config_snapshot(content)?;
+        }
+        files.push(BackupFile {
+            entry: entry.clone(),
+            target,
+            bytes,
+        });
+    }
+
+    Ok((manifest, files))
+}
+
+fn restore_action(file: &BackupFile) -> RestoreAction {
+    match fs::read(&file.target) {
+        Ok(current) if calculate_sha256_bytes(&current) == file.entry.sha256 => {
+            RestoreAction::Unchanged
+        }
+        Ok(_) => RestoreAction::Replace,
+        Err(_) => RestoreAction::Create,
+    }
+}
+
+fn rollback_restore(applied: &[(PathBuf, Option<PathBuf>)]) {
+    for (target, previous) in applied.iter().rev() {
+        let _ = fs::remove_file(target);
+        if let Some(previous) = previous {
+            let _ = fs::rename(previous, target);
+        }
+    }
+}
+
+fn apply_backup_files(files: &[BackupFile], staging_dir: &Path) -> Result<(), String> {
+    let new_dir = staging_dir.join("new");
+    let previous_dir = staging_dir.join("previous");
+    fs::create_dir_all(&new_dir).map_err(|e| e.to_string())?;
+    fs::create_dir_all(&previous_dir).map_err(|e| e.to_string())?;
+
+    let mut staged = Vec::new();
+    for (index, file) in files.iter().enumerate() {
+        let staged_path = new_dir.join(index.to_string());
+        let mut staged_file = fs::File::create(&staged_path).map_err(|e| e.to_string())?;
+        staged_file
+            .write_all(&file.bytes)
+            .map_err(|e| e.to_string())?;
+        staged_file.sync_all().map_err(|e| e.to_string())?;
+        staged.push(staged_path);
+    }
+
+    let mut applied: Vec<(PathBuf, Option<PathBuf>)> = Vec::new();
+    for (index, (file, staged_path)) in files.iter().zip(&staged).enumerate() {
+        let result = (|| {
+            if let Some(parent) = file.target.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let previous = if file.target.exists() {
+                let previous_path = previous_dir.join(index.to_string());
+                fs::rename(&file.target, &previous_path).map_err(|e| e.to_string())?;
+                Some(previous_path)
+            } else {
+                None
+            };
+            if let Err(error) = fs::rename(staged_path, &file.target) {
+                if let Some(previous_path) = &previous {
+                    let _ = fs::rename(previous_path, &file.target);
+                }
+                return Err(error.to_string());
+            }
+            Ok(previous)
+        })();
+
+        match result {
+            Ok(previous) => applied.push((file.target.clone(), previous)),
+            Err(error) => {
+                rollback_restore(&applied);
+                return Err(format!(
+                    "Не удалось восстановить {}: {error}",
+                    file.entry.path
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn create_backup() -> Result<BackupFileInfo, String> {
+    ensure_runtime_data_dir_ready()?;
+    let backups_dir = get_backups_dir();
+    fs::create_dir_all(&backups_dir).map_err(|e| e.to_string())?;
+
+    let files = collect_backup_files()?;
+    let manifest = BackupManifest {
+        format: BACKUP_FORMAT.to_string(),
+        format_version: BACKUP_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: unix_millis_now(),
+        entries: files.iter().map(|file| file.entry.clone()).collect(),
+    };
+    let file_name = format!("{BACKUP_FILE_PREFIX}{}.zip", manifest.created_at);
+    let path = backups_dir.join(&file_name);
+    write_backup_archive(&path, &manifest, &files)?;
+
+    let size = fs::metadata(&path).map_err(|e| e.to_string())?.len();
+    Ok(BackupFileInfo {
+        path: path.to_string_lossy().to_string(),
+        file_name,
+        size,
+        modified_at: manifest.created_at,
+    })
+}
+
+#[tauri::command]
+pub fn list_backups() -> Result<Vec<BackupFileInfo>, String> {
+    let backups_dir = get_backups_dir();
+    if !backups_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(&backups_dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("zip") {
+            continue;
+        }
+        let metadata = entry.metadata().map_err(|e| e.to_string())?;
+        let modified_at = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+        backups.push(BackupFileInfo {
+            path: path.to_string_lossy().to_string(),
+            file_name: entry.file_name().to_string_lossy().to_string(),
+            size: metadata.len(),
+            modified_at,
+        });
+    }
+
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.modified_at));
+    Ok(backups)
+}
+
+#[tauri::command]
+pub fn preview_backup_restore(path: String) -> Result<BackupRestorePreview, String> {
+    let (manifest, files) = read_backup_archive(Path::new(&path))?;
+    let entries = files
+        .iter()
+        .map(|file| RestorePreviewEntry {
+            path: file.entry.path.clone(),
+            kind: file.entry.kind,
+            action: restore_action(file),
+            target_path: file.target.to_string_lossy().to_string(),
+        })
+        .collect();
+
+    Ok(BackupRestorePreview {
+        path,
+        manifest,
+        entries,
+    })
+}
+
+#[tauri::command]
+pub fn restore_backup(
+    app: AppHandle,
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let (_, files) = read_backup_archive(Path::new(&path))?;
+    let files = files
+        .into_iter()
+        .filter(|file| restore_action(file) != RestoreAction::Unchanged)
+        .collect::<Vec<_>>();
+
+    snapshot_config_history()?;
+    let staging_dir = get_runtime_data_dir().join(format!(".restore-{}", Uuid::new_v4()));
+    let result = apply_backup_files(&files, &staging_dir);
+    let _ = fs::remove_dir_all(&staging_dir);
+    result?;
+
+    let config = ensure_config_exists_and_loaded(&state)?.config;
+    crate::sync_list_mode_ui(&app, config.list_mode)?;
+    crate::sync_profiles_ui(&app, &config)?;
+    Ok(config)
+}
+
+#[tauri::command]
+pub fn open_backups_directory(app: AppHandle) -> Result<(), String> {
+    let dir = get_backups_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    app.opener()
+        .open_path(dir.to_string_lossy().to_string(), None::<&str>)
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backup_file(path: &str, kind: BackupEntryKind, bytes: &[u8]) -> BackupFile {
+        BackupFile {
+            entry: BackupEntry {
+                path: path.to_string(),
+                kind,
+                size: bytes.len() as u64,
+                sha256: calculate_sha256_bytes(bytes),
+            },
+            target: entry_target(path, kind).unwrap(),
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    fn manifest(files: &[BackupFile]) -> BackupManifest {
+        BackupManifest {
+            format: BACKUP_FORMAT.to_string(),
+            format_version: BACKUP_FORMAT_VERSION,
+            app_version: "test".to_string(),
+            created_at: 0,
+            entries: files.iter().map(|file| file.entry.clone()).collect(),
+        }
+    }
+
+    #[test]
+    fn entry_targets_reject_builtin_and_unexpected_paths() {
+        assert!(entry_target("config.json", BackupEntryKind::Config).is_ok());
+        assert!(entry_target("lists/zapret-ip-user.txt", BackupEntryKind::List).is_ok());
+        assert!(entry_target("filters/custom.txt", BackupEntryKind::Filter).is_ok());
+        assert!(entry_target("fake/custom.bin", BackupEntryKind::Fake).is_ok());
+
+        assert!(entry_target("lists/zapret-hosts-google.txt", BackupEntryKind::List).is_err());
+        assert!(entry_target("filters/windivert_part.stun.txt", BackupEntryKind::Filter).is_err());
+        assert!(entry_target("fake/stun.bin", BackupEntryKind::Fake).is_err());
+        assert!(entry_target("fake/../config.json", BackupEntryKind::Fake).is_err());
+        assert!(entry_target("config.json", BackupEntryKind::Filter).is_err());
+    }
+
+    #[test]
+    fn archive_round_trip_verifies_hashes() {
+        let config = serde_json::to_vec(&AppConfig::default()).unwrap();
+        let mut files = vec![
+            backup_file("config.json", BackupEntryKind::Config, &config),
+            backup_file("filters/custom.txt", BackupEntryKind::Filter, b"udp"),
+        ];
+        let path = std::env::temp_dir().join(format!("zapret-backup-{}.zip", Uuid::new_v4()));
+
+        write_backup_archive(&path, &manifest(&files), &files).unwrap();
+        let (read_manifest, read_files) = read_backup_archive(&path).unwrap();
+        assert_eq!(read_manifest.entries.len(), 2);
+        assert_eq!(read_files[1].bytes, b"udp");
+
+        files[1].entry.sha256 = calculate_sha256_bytes(b"tcp");
+        write_backup_archive(&path, &manifest(&files), &files).unwrap();
+        let result = read_backup_archive(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            result.err(),
+            Some("Контрольная сумма filters/custom.txt не совпадает".to_string())
+        );
+    }
+}
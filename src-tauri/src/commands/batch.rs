@@ -0,0 +1,636 @@
+use super::config::{
+    AppConfig, AppState, Category, PROFILE_SEPARATOR_ARG, Placeholder, Strategy, StrategyMetadata,
+    build_filter_args, build_strategy_args, check_placeholder_resolution, current_config,
+    get_filters_dir, get_managed_resources_dir, get_runtime_data_dir, global_port_args,
+    instance_config, instance_groups, replace_current_config,
+};
+use super::strategy::{is_global_only_option, option_takes_file};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{MAIN_SEPARATOR, Path};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+use uuid::Uuid;
+
+const WINWS_EXECUTABLE: &str = "winws.exe";
+const EXPORTS_DIR_NAME: &str = "exports";
+const EXPORT_FILE_PREFIX: &str = "zapret-interactive-";
+const SCRIPT_RESOURCES_VAR: &str = "%RES%";
+
+/// A file argument that did not match any placeholder and was imported as is.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchFileReference {
+    pub profile: usize,
+    pub option: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchImportReport {
+    pub wf_tcp: Option<String>,
+    pub wf_udp: Option<String>,
+    pub skipped_options: Vec<String>,
+    pub unmapped_files: Vec<BatchFileReference>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct BatchPreset {
+    profiles: Vec<Vec<String>>,
+    report: BatchImportReport,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchImportResult {
+    pub config: AppConfig,
+    pub category_id: String,
+    pub strategy_ids: Vec<String>,
+    #[serde(flatten)]
+    pub report: BatchImportReport,
+}
+
+/// Joins lines ending with the `^` continuation character.
+fn join_continuations(script: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut pending = String::new();
+    for line in script.lines() {
+        let line = line.trim_end();
+        match line.strip_suffix('^') {
+            Some(head) => {
+                pending.push_str(head);
+                pending.push(' ');
+            }
+            None => {
+                pending.push_str(line);
+                lines.push(std::mem::take(&mut pending));
+            }
+        }
+    }
+    if !pending.is_empty() {
+        lines.push(pending);
+    }
+    lines
+}
+
+fn is_comment(line: &str) -> bool {
+    let lower = line.trim_start().to_ascii_lowercase();
+    lower.starts_with("::") || lower == "rem" || lower.starts_with("rem ")
+}
+
+/// `set "NAME=value"` and `set NAME=value`; `/a` and `/p` forms are ignored.
+fn parse_set_command(line: &str) -> Option<(String, String)> {
+    let line = line.trim_start().trim_start_matches('@');
+    let rest = line
+        .get(..4)
+        .filter(|head| head.eq_ignore_ascii_case("set "))
+        .map(|_| line[4..].trim())?;
+    if rest.starts_with('/') {
+        return None;
+    }
+    let rest = rest.strip_prefix('"').map_or(rest, |quoted| {
+        quoted.rsplit_once('"').map_or(quoted, |(inner, _)| inner)
+    });
+    let (name, value) = rest.split_once('=')?;
+    Some((name.trim().to_ascii_uppercase(), value.to_string()))
+}
+
+/// Expands `%NAME%` like cmd does at parse time. `%~dp0` becomes empty, so
+/// paths end up relative to the script folder; unknown variables expand to
+/// nothing as well.
+fn expand_variables(line: &str, variables: &HashMap<String, String>) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('%') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(modifier) = after.strip_prefix('~') {
+            let end = modifier
+                .find(|char: char| char.is_ascii_digit())
+                .map_or(0, |index| index + 1);
+            rest = &modifier[end..];
+            continue;
+        }
+        let Some(end) = after.find('%') else {
+            result.push_str(&rest[start..]);
+            return result;
+        };
+        if let Some(value) = variables.get(&after[..end].to_ascii_uppercase()) {
+            result.push_str(value);
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Splits a command line on unquoted whitespace. Quotes are dropped and `^`
+/// escapes the next character outside of quotes.
+fn tokenize_command(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut in_quotes = false;
+    let mut chars = line.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '"' => {
+                in_quotes = !in_quotes;
+                in_token = true;
+            }
+            '^' if !in_quotes => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                    in_token = true;
+                }
+            }
+            char if char.is_whitespace() && !in_quotes => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            char => {
+                current.push(char);
+                in_token = true;
+            }
+        }
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn file_name_of(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+fn is_winws_executable(token: &str) -> bool {
+    file_name_of(token).eq_ignore_ascii_case(WINWS_EXECUTABLE)
+}
+
+fn clean_port_list(value: &str) -> String {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn is_file_value(value: &str) -> bool {
+    !value.is_empty()
+        && !value.starts_with("0x")
+        && !value.starts_with('!')
+        && !value.starts_with("{{")
+}
+
+fn map_file_placeholder<'a>(value: &str, placeholders: &'a [Placeholder]) -> Option<&'a str> {
+    let file_name = file_name_of(value);
+    placeholders
+        .iter()
+        .find(|placeholder| file_name_of(&placeholder.value).eq_ignore_ascii_case(file_name))
+        .map(|placeholder| placeholder.name.as_str())
+}
+
+fn parse_batch_preset(script: &str, placeholders: &[Placeholder]) -> Result<BatchPreset, String> {
+    let mut variables = HashMap::new();
+    let mut preset = BatchPreset::default();
+    let mut found_winws = false;
+
+    for line in join_continuations(script) {
+        if is_comment(&line) {
+            continue;
+        }
+        if let Some((name, value)) = parse_set_command(&line) {
+            let value = expand_variables(&value, &variables);
+            variables.insert(name, value);
+            continue;
+        }
+
+        let tokens = tokenize_command(&expand_variables(&line, &variables));
+        let Some(executable) = tokens.iter().position(|token| is_winws_executable(token)) else {
+            continue;
+        };
+        found_winws = true;
+
+        let mut profile = Vec::new();
+        for token in &tokens[executable + 1..] {
+            let (name, value) = token
+                .split_once('=')
+                .map_or((token.as_str(), None), |(name, value)| (name, Some(value)));
+            if name == PROFILE_SEPARATOR_ARG {
+                preset.profiles.push(std::mem::take(&mut profile));
+                continue;
+            }
+            if is_global_only_option(name) {
+                match name {
+                    "--wf-tcp" => preset.report.wf_tcp = value.map(clean_port_list),
+                    "--wf-udp" => preset.report.wf_udp = value.map(clean_port_list),
+                    _ => preset.report.skipped_options.push(token.clone()),
+                }
+                continue;
+            }
+
+            match value {
+                Some(value) if option_takes_file(name) && is_file_value(value) => {
+                    match map_file_placeholder(value, placeholders) {
+                        Some(placeholder) => profile.push(format!("{name}={{{{{placeholder}}}}}")),
+                        None => {
+                            preset.report.unmapped_files.push(BatchFileReference {
+                                profile: preset.profiles.len() + 1,
+                                option: name.to_string(),
+                                value: value.to_string(),
+                            });
+                            profile.push(token.clone());
+                        }
+                    }
+                }
+                Some(value) if name.starts_with("--filter-") && name != "--filter-l7" => {
+                    profile.push(format!("{name}={}", clean_port_list(value)));
+                }
+                _ => profile.push(token.clone()),
+            }
+        }
+        preset.profiles.push(profile);
+    }
+
+    if !found_winws {
+        return Err(format!("Командная строка {WINWS_EXECUTABLE} не найдена"));
+    }
+    preset.profiles.retain(|profile| !profile.is_empty());
+    if preset.profiles.is_empty() {
+        return Err(format!(
+            "В командной строке {WINWS_EXECUTABLE} нет профилей"
+        ));
+    }
+    Ok(preset)
+}
+
+fn preset_name(file_name: &str) -> String {
+    let file_name = file_name_of(file_name);
+    let stem = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem)
+        .trim();
+    if stem.is_empty() {
+        "Импортированная стратегия".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+/// Imports a batch file as a new category with one strategy per `--new` profile.
+/// Global `--wf-*` options are reported instead of overwriting the current ones.
+#[tauri::command]
+pub fn import_batch_preset(
+    file_name: String,
+    content: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<BatchImportResult, String> {
+    let mut next = current_config(&state)?;
+    let preset = parse_batch_preset(&content, &next.placeholders)
+        .map_err(|e| format!("{}: {e}", file_name_of(&file_name)))?;
+    let name = preset_name(&file_name);
+
+    let strategies = preset
+        .profiles
+        .iter()
+        .enumerate()
+        .map(|(index, profile)| Strategy {
+            id: Uuid::new_v4().to_string(),
+            name: format!("{name} #{}", index + 1),
+            content: profile.join("\n"),
+            active: false,
+            metadata: StrategyMetadata::default(),
+            system: false,
+            system_base_name: None,
+            system_base_content: None,
+            system_base_metadata: None,
+        })
+        .collect::<Vec<_>>();
+    let strategy_ids = strategies
+        .iter()
+        .map(|strategy| strategy.id.clone())
+        .collect();
+    let category_id = Uuid::new_v4().to_string();
+    next.categories.push(Category {
+        id: category_id.clone(),
+        name,
+        strategies,
+        system: false,
+        system_base_name: None,
+        source_id: None,
+        instance_group: None,
+    });
+
+    Ok(BatchImportResult {
+        config: replace_current_config(next, &state)?,
+        category_id,
+        strategy_ids,
+        report: preset.report,
+    })
+}
+
+/// The arguments `start_winws` passes to the winws.exe of each instance
+/// group, except that filters are inlined so the script does not depend on
+/// the app's filters folder.
+fn build_script_args(config: &AppConfig, filters_dir: &Path) -> Vec<(String, Vec<String>)> {
+    instance_groups(config)
+        .into_iter()
+        .map(|group| {
+            let args = build_instance_script_args(&instance_config(config, &group), filters_dir);
+            (group, args)
+        })
+        .collect()
+}
+
+fn build_instance_script_args(config: &AppConfig, filters_dir: &Path) -> Vec<String> {
+    let mut args = global_port_args(config);
+    for filter in config.filters.iter().filter(|filter| filter.active) {
+        let expression = filter
+            .content
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if expression.is_empty() {
+            args.extend(build_filter_args(std::slice::from_ref(filter), filters_dir));
+        } else {
+            args.push(format!("--wf-raw-part={expression}"));
+        }
+    }
+    args.extend(build_strategy_args(config));
+    args
+}
+
+/// Escapes `%` for cmd, rewrites resource paths through `%RES%` and quotes
+/// anything cmd would otherwise split or interpret.
+fn script_arg(arg: &str, resources_prefix: &str) -> String {
+    let escaped = arg.replace('%', "%%");
+    let escaped = if resources_prefix.is_empty() {
+        escaped
+    } else {
+        escaped.replace(&resources_prefix.replace('%', "%%"), SCRIPT_RESOURCES_VAR)
+    };
+    if escaped.contains(SCRIPT_RESOURCES_VAR)
+        || escaped
+            .chars()
+            .any(|char| char.is_whitespace() || "&|<>^()".contains(char))
+    {
+        format!("\"{escaped}\"")
+    } else {
+        escaped
+    }
+}
+
+fn render_winws_script(config: &AppConfig, resources_dir: &Path, filters_dir: &Path) -> String {
+    let resources_prefix = format!(
+        "{}{MAIN_SEPARATOR}",
+        resources_dir
+            .to_string_lossy()
+            .trim_end_matches(MAIN_SEPARATOR)
+    );
+    let active_strategies = config
+        .categories
+        .iter()
+        .flat_map(|category| {
+            category
+                .strategies
+                .iter()
+                .filter(|strategy| strategy.active)
+                .map(move |strategy| format!("rem   {} / {}", category.name, strategy.name))
+        })
+        .collect::<Vec<_>>();
+    let active_filters = config
+        .filters
+        .iter()
+        .filter(|filter| filter.active)
+        .map(|filter| format!("rem   {}", filter.name))
+        .collect::<Vec<_>>();
+
+    let mut lines = vec![
+        "@echo off".to_string(),
+        format!("rem Zapret Interactive {}", env!("CARGO_PKG_VERSION")),
+        "rem Положите файл в папку с winws.exe и запустите от имени администратора.".to_string(),
+        "rem Стратегии:".to_string(),
+    ];
+    lines.extend(active_strategies);
+    if !active_filters.is_empty() {
+        lines.push("rem Фильтры:".to_string());
+        lines.extend(active_filters);
+    }
+    lines.extend([
+        "chcp 65001 >nul".to_string(),
+        "set \"RES=%~dp0\"".to_string(),
+        "cd /d \"%RES%\"".to_string(),
+    ]);
+
+    // Several instance groups run side by side, so each gets its own window
+    // through `start`; a single winws.exe keeps running in the script's one.
+    let instances = build_script_args(config, filters_dir);
+    let detached = instances.len() > 1;
+    for (group, args) in instances {
+        let executable = format!("\"{SCRIPT_RESOURCES_VAR}{WINWS_EXECUTABLE}\"");
+        lines.push(if detached {
+            format!("start \"winws {group}\" {executable} ^")
+        } else {
+            format!("{executable} ^")
+        });
+        let last = args.len().saturating_sub(1);
+        for (index, arg) in args.iter().enumerate() {
+            let continuation = if index == last { "" } else { " ^" };
+            lines.push(format!(
+                "  {}{continuation}",
+                script_arg(arg, &resources_prefix)
+            ));
+        }
+    }
+
+    let mut script = lines.join("\r\n");
+    script.push_str("\r\n");
+    script
+}
+
+/// Writes the current setup as a standalone `.cmd` file and reveals it.
+#[tauri::command]
+pub fn export_winws_script(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let config = current_config(&state)?;
+    let placeholder_report = check_placeholder_resolution(&config);
+    if !placeholder_report.is_clean() {
+        return Err(placeholder_report.describe());
+    }
+
+    let exports_dir = get_runtime_data_dir().join(EXPORTS_DIR_NAME);
+    fs::create_dir_all(&exports_dir).map_err(|e| e.to_string())?;
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    let path = exports_dir.join(format!("{EXPORT_FILE_PREFIX}{created_at}.cmd"));
+    fs::write(
+        &path,
+        render_winws_script(&config, &get_managed_resources_dir(), &get_filters_dir()),
+    )
+    .map_err(|e| e.to_string())?;
+
+    if let Err(error) = app.opener().reveal_item_in_dir(&path) {
+        eprintln!("Failed to reveal exported script: {error}");
+    }
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizes_continuations_quotes_and_variables() {
+        let script = "@echo off\r\nset \"BIN=%~dp0bin\\\"\r\nset LISTS=%~dp0lists\\\r\n\
+            start \"zapret: %~n0\" /min \"%BIN%winws.exe\" --wf-tcp=80,443,%GameFilter% ^\r\n\
+            --hostlist=\"%LISTS%list general.txt\" --dpi-desync=fake^,split2\r\n";
+        let lines = join_continuations(script);
+        assert_eq!(lines.len(), 4);
+
+        let mut variables = HashMap::new();
+        for line in &lines[1..3] {
+            let (name, value) = parse_set_command(line).unwrap();
+            variables.insert(name, expand_variables(&value, &variables));
+        }
+        assert_eq!(variables["BIN"], "bin\\");
+
+        assert_eq!(
+            tokenize_command(&expand_variables(&lines[3], &variables)),
+            vec![
+                "start",
+                "zapret: ",
+                "/min",
+                "bin\\winws.exe",
+                "--wf-tcp=80,443,",
+                "--hostlist=lists\\list general.txt",
+                "--dpi-desync=fake,split2",
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_profiles_and_maps_files_to_placeholders() {
+        let placeholders = AppConfig::default().placeholders;
+        let script = r#"
+set "BIN=%~dp0bin\"
+set "LISTS=%~dp0lists\"
+rem "%BIN%winws.exe" --ignored
+"%BIN%winws.exe" --wf-tcp=80,443 --wf-udp=443,50000-50100, --wf-raw-part=@"%BIN%stun.txt" ^
+--filter-udp=443 --hostlist="%LISTS%zapret-hosts-google.txt" --dpi-desync=fake --dpi-desync-fake-quic="%BIN%quic_initial_www_google_com.bin" --new ^
+--filter-tcp=443 --hostlist="%LISTS%list-general.txt" --dpi-desync-fake-tls=0x00000000 --new
+"#;
+        let preset = parse_batch_preset(script, &placeholders).unwrap();
+        assert_eq!(
+            preset.profiles,
+            vec![
+                vec![
+                    "--filter-udp=443",
+                    "--hostlist={{HOSTS_GOOGLE}}",
+                    "--dpi-desync=fake",
+                    "--dpi-desync-fake-quic={{QUIC_INITIAL_GOOGLE}}",
+                ],
+                vec![
+                    "--filter-tcp=443",
+                    "--hostlist=lists\\list-general.txt",
+                    "--dpi-desync-fake-tls=0x00000000",
+                ],
+            ]
+        );
+        assert_eq!(preset.report.wf_tcp.as_deref(), Some("80,443"));
+        assert_eq!(preset.report.wf_udp.as_deref(), Some("443,50000-50100"));
+        assert_eq!(
+            preset.report.skipped_options,
+            vec!["--wf-raw-part=@bin\\stun.txt"]
+        );
+        assert_eq!(
+            preset.report.unmapped_files,
+            vec![BatchFileReference {
+                profile: 2,
+                option: "--hostlist".to_string(),
+                value: "lists\\list-general.txt".to_string(),
+            }]
+        );
+        assert!(parse_batch_preset("echo hello", &placeholders).is_err());
+    }
+
+    #[test]
+    fn export_script_uses_resource_relative_paths() {
+        let resources_dir = Path::new("C:\\Program Files\\Zapret\\resources");
+        let mut config = AppConfig::default();
+        for category in &mut config.categories {
+            for strategy in &mut category.strategies {
+                strategy.active = false;
+            }
+        }
+        let strategy = &mut config.categories[0].strategies[0];
+        strategy.active = true;
+        strategy.content = "--filter-udp=443\n--dpi-desync=fake\n--dpi-desync-fake-quic={{QUIC_INITIAL_GOOGLE}}\n--dpi-desync-fooling=md5sig&ts".to_string();
+        let quic = config
+            .placeholders
+            .iter_mut()
+            .find(|placeholder| placeholder.name == "QUIC_INITIAL_GOOGLE")
+            .unwrap();
+        quic.value = resources_dir
+            .join("fake")
+            .join("quic_initial_www_google_com.bin")
+            .to_string_lossy()
+            .to_string();
+        for filter in &mut config.filters {
+            filter.active = false;
+        }
+        config.filters[0].active = true;
+        config.filters[0].content = "outbound and\n  udp.DstPort=3478".to_string();
+
+        let script = render_winws_script(&config, resources_dir, Path::new("filters"));
+        let lines = script.lines().collect::<Vec<_>>();
+        let start = lines
+            .iter()
+            .position(|line| line.starts_with("\"%RES%winws.exe\""))
+            .unwrap();
+        assert_eq!(
+            &lines[start + 1..],
+            [
+                format!("  --wf-tcp={} ^", config.global_ports.tcp),
+                format!("  --wf-udp={} ^", config.global_ports.udp),
+                "  \"--wf-raw-part=outbound and udp.DstPort=3478\" ^".to_string(),
+                "  --filter-udp=443 ^".to_string(),
+                "  --dpi-desync=fake ^".to_string(),
+                format!(
+                    "  \"--dpi-desync-fake-quic=%RES%fake{MAIN_SEPARATOR}quic_initial_www_google_com.bin\" ^"
+                ),
+                "  \"--dpi-desync-fooling=md5sig&ts\"".to_string(),
+            ]
+        );
+        assert_eq!(script_arg("--name=50%", ""), "--name=50%%");
+
+        config.categories[0].instance_group = Some("udp".to_string());
+        let strategy = &mut config.categories[1].strategies[0];
+        strategy.active = true;
+        strategy.content = "--filter-tcp=443\n--dpi-desync=split".to_string();
+        let script = render_winws_script(&config, resources_dir, Path::new("filters"));
+        let launches = script
+            .lines()
+            .filter(|line| line.contains("%RES%winws.exe"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            launches,
+            [
+                "start \"winws main\" \"%RES%winws.exe\" ^",
+                "start \"winws udp\" \"%RES%winws.exe\" ^",
+            ]
+        );
+        assert_eq!(script.matches("--wf-raw-part=").count(), 1);
+        assert!(script.contains("  --dpi-desync=split\r\nstart \"winws udp\""));
+    }
+}
@@ -1,9 +1,9 @@
-use crate::commands::process::kill_windivert_service;
+use crate::commands::process::{kill_windivert_service, reload_winws_with_config};
 use crate::config::{
     AppConfig, AppState, current_config, ensure_config_exists_and_loaded,
     ensure_managed_resources_dir_ready, ensure_runtime_data_dir_ready, get_config_path,
-    get_managed_resources_dir, get_runtime_data_dir, uses_dev_managed_resources_source,
-    validate_filter_filename,
+    get_filters_dir, get_managed_resources_dir, get_runtime_data_dir,
+    uses_dev_managed_resources_source, validate_filter_filename,
 };
 use futures::stream::{self, StreamExt};
 use notify::{Config as NotifyConfig, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
@@ -37,6 +37,7 @@ pub struct FileHealthChangedPayload {
     pub config_missing: bool,
     pub config_restored: bool,
     pub config_reloaded: bool,
+    pub config_validation_error: Option<String>,
     pub restored_files: Vec<String>,
     pub unrecoverable_filters: Vec<String>,
 }
@@ -55,6 +56,7 @@ pub struct EnsureManagedFilesResult {
     pub restored_files: Vec<String>,
     pub config_restored: bool,
     pub config_reloaded: bool,
+    pub config_validation_error: Option<String>,
     pub unrecoverable_filters: Vec<String>,
 }
 
@@ -94,7 +96,7 @@ const MODULE_FILES: &[&str] = &[
 ];
 const FAKE_FILES_BASE_URL: &str =
     "https://raw.githubusercontent.com/Noktomezo/ZapretInteractive/main/thirdparty/fake";
-const FAKE_FILES: &[&str] = &[
+pub(crate) const FAKE_FILES: &[&str] = &[
     "4pda.bin",
     "dht_find_node.bin",
     "dht_get_peers.bin",
@@ -141,7 +143,7 @@ const LISTS: &[&str] = &[
     "zapret-ip-user.txt",
 ];
 
-const FILTERS: &[&str] = &[
+pub(crate) const FILTERS: &[&str] = &[
     "windivert_part.dht.txt",
     "windivert_part.discord_media.txt",
     "windivert_part.quic_initial_ietf.txt",
@@ -171,23 +173,20 @@ enum DownloadMode {
     ApplyCoreUpdates,
 }
 
-fn get_fake_dir() -> PathBuf {
+pub(crate) fn get_fake_dir() -> PathBuf {
     get_managed_resources_dir().join("fake")
 }
-fn get_lists_dir() -> PathBuf {
+pub(crate) fn get_lists_dir() -> PathBuf {
     get_managed_resources_dir().join("lists")
 }
 fn get_modules_dir() -> PathBuf {
     get_managed_resources_dir().join("modules")
 }
-fn get_filters_dir() -> PathBuf {
-    get_runtime_data_dir().join("filters")
-}
 fn get_hashes_path() -> PathBuf {
     get_managed_resources_dir().join("hashes.json")
 }
 
-fn sanitize_filename(filename: &str) -> Result<String, String> {
+pub(crate) fn sanitize_filename(filename: &str) -> Result<String, String> {
     let name = validate_filter_filename(filename)?;
 
     let file_stem = Path::new(&name)
@@ -200,7 +199,7 @@ fn sanitize_filename(filename: &str) -> Result<String, String> {
             "com8", "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
         ];
         if RESERVED_NAMES.contains(&stem.as_str()) {
-            return Err("Filename uses reserved name".to_string());
+            return Err("Имя файла зарезервировано системой".to_string());
         }
     }
 
@@ -214,7 +213,7 @@ fn calculate_sha256(file_path: &PathBuf) -> Result<String, String> {
     Ok(format!("{:x}", hasher.finalize()))
 }
 
-fn calculate_sha256_bytes(bytes: &[u8]) -> String {
+pub(crate) fn calculate_sha256_bytes(bytes: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
     format!("{:x}", hasher.finalize())
@@ -225,9 +224,9 @@ fn load_stored_hashes() -> Result<HashMap<String, String>, String> {
     if !hashes_path.exists() {
         return Ok(HashMap::new());
     }
-    let content =
-        fs::read_to_string(&hashes_path).map_err(|e| format!("Failed to read hashes.json: {e}"))?;
-    serde_json::from_str(&content).map_err(|e| format!("Failed to parse hashes.json: {e}"))
+    let content = fs::read_to_string(&hashes_path)
+        .map_err(|e| format!("Не удалось прочитать hashes.json: {e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("Не удалось разобрать hashes.json: {e}"))
 }
 
 fn save_stored_hashes(hashes: &HashMap<String, String>) -> Result<(), String> {
@@ -250,7 +249,7 @@ where
 {
     let _guard = HASHES_LOCK
         .lock()
-        .map_err(|e| format!("Failed to lock hashes.json: {e}"))?;
+        .map_err(|e| format!("Не удалось заблокировать hashes.json: {e}"))?;
     let mut hashes = load_stored_hashes()?;
     mutate(&mut hashes)?;
     save_stored_hashes(&hashes)
@@ -259,7 +258,7 @@ where
 fn rebuild_hashes_from_disk() -> Result<(), String> {
     let _guard = HASHES_LOCK
         .lock()
-        .map_err(|e| format!("Failed to lock hashes.json: {e}"))?;
+        .map_err(|e| format!("Не удалось заблокировать hashes.json: {e}"))?;
 
     let files = tracked_files();
     let hashes = files
@@ -274,7 +273,7 @@ fn rebuild_hashes_from_disk() -> Result<(), String> {
     save_stored_hashes(&hashes)
 }
 
-fn create_http_client() -> Result<reqwest::Client, String> {
+pub(crate) fn create_http_client() -> Result<reqwest::Client, String> {
     reqwest::Client::builder()
         .user_agent("Mozilla/5.0")
         .timeout(Duration::from_secs(120))
@@ -284,7 +283,8 @@ fn create_http_client() -> Result<reqwest::Client, String> {
 
 async fn fetch_remote_hashes(client: &reqwest::Client) -> Result<HashMap<String, String>, String> {
     let bytes = download_bytes(client, REMOTE_HASHES_URL, "hashes.json").await?;
-    serde_json::from_slice(&bytes).map_err(|e| format!("Failed to parse remote hashes.json: {e}"))
+    serde_json::from_slice(&bytes)
+        .map_err(|e| format!("Не удалось разобрать удалённый hashes.json: {e}"))
 }
 
 fn remote_hash_for<'a>(
@@ -294,7 +294,7 @@ fn remote_hash_for<'a>(
     remote_hashes
         .get(&tracked_key(file))
         .map(String::as_str)
-        .ok_or_else(|| format!("Missing remote hash for {} ({})", file.name, file.group))
+        .ok_or_else(|| format!("Нет удалённого хеша для {} ({})", file.name, file.group))
 }
 
 fn hash_key(group: &str, name: &str) -> String {
@@ -435,7 +435,7 @@ async fn inspect_local_files_async(
     let owned_files = files.to_vec();
     tauri::async_runtime::spawn_blocking(move || inspect_local_files(&owned_files))
         .await
-        .map_err(|e| format!("Failed to join local file inspection task: {e}"))?
+        .map_err(|e| format!("Задача проверки локальных файлов завершилась с ошибкой: {e}"))?
 }
 
 fn tracked_file_is_healthy(
@@ -796,6 +796,19 @@ pub fn start_files_watcher(app: AppHandle) -> Result<(), String> {
                     }
                 };
 
+            // A config that failed to load or was reset to the default must
+            // never replace the strategies winws is running with.
+            if ensured.config_reloaded && ensured.config_validation_error.is_none() {
+                let reloaded = current_config(&state)
+                    .and_then(|config| reload_winws_with_config(&app, &config));
+                if let Err(error) = reloaded {
+                    let _ = app.emit(
+                        "files-health-watch-error",
+                        format!("Не удалось применить изменённый config.json к winws: {error}"),
+                    );
+                }
+            }
+
             let payload = match build_local_health_snapshot(&state) {
                 Ok(snapshot) => FileHealthChangedPayload {
                     binaries_ok: snapshot.binaries_ok,
@@ -803,6 +816,7 @@ pub fn start_files_watcher(app: AppHandle) -> Result<(), String> {
                     config_missing: snapshot.config_missing,
                     config_restored: ensured.config_restored,
                     config_reloaded: ensured.config_reloaded,
+                    config_validation_error: ensured.config_validation_error.clone(),
                     restored_files: ensured.restored_files.clone(),
                     unrecoverable_filters: ensured.unrecoverable_filters.clone(),
                 },
@@ -825,10 +839,10 @@ pub fn start_files_watcher(app: AppHandle) -> Result<(), String> {
 async fn calculate_sha256_async(file_path: PathBuf) -> Result<String, String> {
     tauri::async_runtime::spawn_blocking(move || calculate_sha256(&file_path))
         .await
-        .map_err(|e| format!("Failed to join SHA-256 task: {e}"))?
+        .map_err(|e| format!("Задача подсчёта SHA-256 завершилась с ошибкой: {e}"))?
 }
 
-async fn download_bytes(
+pub(crate) async fn download_bytes(
     client: &reqwest::Client,
     url: &str,
     name: &str,
@@ -837,10 +851,10 @@ async fn download_bytes(
         .get(url)
         .send()
         .await
-        .map_err(|e| format!("Failed to fetch {name}: {e}"))?;
+        .map_err(|e| format!("Не удалось загрузить {name}: {e}"))?;
     if !response.status().is_success() {
         return Err(format!(
-            "Failed to download {name}: HTTP {}",
+            "Не удалось скачать {name}: HTTP {}",
             response.status()
         ));
     }
@@ -848,7 +862,7 @@ async fn download_bytes(
         .bytes()
         .await
         .map(|bytes| bytes.to_vec())
-        .map_err(|e| format!("Failed to read {name} body: {e}"))
+        .map_err(|e| format!("Не удалось прочитать ответ для {name}: {e}"))
 }
 
 async fn write_bytes_atomic(dest_path: &Path, bytes: &[u8], name: &str) -> Result<(), String> {
@@ -857,25 +871,25 @@ async fn write_bytes_atomic(dest_path: &Path, bytes: &[u8], name: &str) -> Resul
     {
         tokio::fs::create_dir_all(parent)
             .await
-            .map_err(|e| format!("Failed to create parent directory for {name}: {e}"))?;
+            .map_err(|e| format!("Не удалось создать папку для {name}: {e}"))?;
     }
 
     let temp_path = dest_path.with_extension("tmp");
     use tokio::io::AsyncWriteExt;
     let mut temp_file = tokio::fs::File::create(&temp_path)
         .await
-        .map_err(|e| format!("Failed to create temp file for {name}: {e}"))?;
+        .map_err(|e| format!("Не удалось создать временный файл для {name}: {e}"))?;
     temp_file
         .write_all(bytes)
         .await
-        .map_err(|e| format!("Failed to write temp file for {name}: {e}"))?;
+        .map_err(|e| format!("Не удалось записать временный файл для {name}: {e}"))?;
     temp_file
         .sync_all()
         .await
-        .map_err(|e| format!("Failed to sync temp file for {name}: {e}"))?;
+        .map_err(|e| format!("Не удалось сохранить временный файл для {name}: {e}"))?;
     tokio::fs::rename(&temp_path, dest_path)
         .await
-        .map_err(|e| format!("Failed to rename temp file for {name}: {e}"))?;
+        .map_err(|e| format!("Не удалось переименовать временный файл для {name}: {e}"))?;
     Ok(())
 }
 
@@ -967,7 +981,7 @@ async fn build_download_plan(
                         } else if tracked_file.include_in_remote_updates {
                             let remote_hashes = remote_hashes
                                 .as_ref()
-                                .ok_or_else(|| "Remote hashes not loaded".to_string())?;
+                                .ok_or_else(|| "Удалённые хеши не загружены".to_string())?;
                             let remote_hash = remote_hash_for(remote_hashes, &tracked_file)?;
                             (local_hash != Some(remote_hash), None)
                         } else {
@@ -980,7 +994,7 @@ async fn build_download_plan(
                         } else {
                             let remote_hashes = remote_hashes
                                 .as_ref()
-                                .ok_or_else(|| "Remote hashes not loaded".to_string())?;
+                                .ok_or_else(|| "Удалённые хеши не загружены".to_string())?;
                             let remote_hash = remote_hash_for(remote_hashes, &tracked_file)?;
                             (local_hash != Some(remote_hash), None)
                         }
@@ -1039,12 +1053,12 @@ async fn execute_download_plan(
                 if let Some(app) = app {
                     app.emit(
                         "download-error",
-                        format!("Failed to stop WinDivert service before download: {e}"),
+                        format!("Не удалось остановить службу WinDivert перед загрузкой: {e}"),
                     )
                     .ok();
                 }
                 return Err(format!(
-                    "Failed to stop WinDivert service before download: {e}"
+                    "Не удалось остановить службу WinDivert перед загрузкой: {e}"
                 ));
             }
         }
@@ -1115,6 +1129,7 @@ async fn ensure_managed_files_internal(
         restored_files,
         config_restored: ensured_config.restored_default,
         config_reloaded,
+        config_validation_error: ensured_config.validation_error,
         unrecoverable_filters: ensured_config.unrecoverable_filters,
     })
 }
@@ -1152,7 +1167,7 @@ async fn refresh_lists_internal() -> Result<Vec<String>, String> {
             let downloaded_hash = calculate_sha256_bytes(&bytes);
             if downloaded_hash != remote_hash {
                 return Err(format!(
-                    "Downloaded list hash mismatch for {name}: expected {remote_hash}, got {downloaded_hash}"
+                    "Хеш загруженного списка {name} не совпадает: ожидался {remote_hash}, получен {downloaded_hash}"
                 ));
             }
 
@@ -1160,18 +1175,18 @@ async fn refresh_lists_internal() -> Result<Vec<String>, String> {
             use tokio::io::AsyncWriteExt;
             let mut temp_file = tokio::fs::File::create(&temp_path)
                 .await
-                .map_err(|e| format!("Failed to create temp file: {e}"))?;
+                .map_err(|e| format!("Не удалось создать временный файл: {e}"))?;
             temp_file
                 .write_all(&bytes)
                 .await
-                .map_err(|e| format!("Failed to write temp file: {e}"))?;
+                .map_err(|e| format!("Не удалось записать временный файл: {e}"))?;
             temp_file
                 .sync_all()
                 .await
-                .map_err(|e| format!("Failed to sync temp file: {e}"))?;
+                .map_err(|e| format!("Не удалось сохранить временный файл: {e}"))?;
             tokio::fs::rename(&temp_path, &file_path)
                 .await
-                .map_err(|e| format!("Failed to rename temp file: {e}"))?;
+                .map_err(|e| format!("Не удалось переименовать временный файл: {e}"))?;
             update_hashes(|hashes| {
                 hashes.insert(hash_key("lists", name), remote_hash.clone());
                 Ok(())
@@ -1406,16 +1421,17 @@ pub fn save_filter_file(filename: String, content: String) -> Result<(), String>
     let file_path = filters_dir.join(&filename);
     let temp_path = file_path.with_extension("tmp");
     use std::io::Write;
-    let mut temp_file =
-        fs::File::create(&temp_path).map_err(|e| format!("Failed to create temp file: {e}"))?;
+    let mut temp_file = fs::File::create(&temp_path)
+        .map_err(|e| format!("Не удалось создать временный файл: {e}"))?;
     temp_file
         .write_all(content.as_bytes())
-        .map_err(|e| format!("Failed to write temp file: {e}"))?;
+        .map_err(|e| format!("Не удалось записать временный файл: {e}"))?;
     temp_file
         .sync_all()
-        .map_err(|e| format!("Failed to sync temp file: {e}"))?;
+        .map_err(|e| format!("Не удалось сохранить временный файл: {e}"))?;
     drop(temp_file);
-    fs::rename(&temp_path, &file_path).map_err(|e| format!("Failed to rename temp file: {e}"))?;
+    fs::rename(&temp_path, &file_path)
+        .map_err(|e| format!("Не удалось переименовать временный файл: {e}"))?;
 
     Ok(())
 }
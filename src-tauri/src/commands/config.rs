@@ -1,10 +1,21 @@
+use super::binaries::{get_fake_dir, get_lists_dir};
+use super::history::{record_config_history, snapshot_config_history};
+use super::ports::{format_ranges, normalize_global_port_list, parse_port_list, subtract_ranges};
+use super::profile::normalize_profiles;
+use super::strategy::{
+    MergeConflict, derive_global_ports, effective_global_ports, merge_strategy_content,
+};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashSet;
 use std::fs;
 use std::io::{BufReader, Read};
 use std::net::IpAddr;
+use std::ops::RangeInclusive;
 use std::path::{Component, Path, PathBuf};
 use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 const DEFAULT_CONFIG: &str = include_str!("../../default-config.json");
@@ -21,11 +32,30 @@ const INSTALLED_RESOURCES_DIR_NAME: &str = "resources";
 const MANAGED_PATH_ALIAS: &str = "@resources";
 const LEGACY_MANAGED_PATH_ALIAS: &str = "@thirdparty";
 const LEGACY_INSTALLED_RESOURCES_MARKER: &str = ".legacy-thirdparty-migrated";
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+const FILTERS_DIR_NAME: &str = "filters";
+pub(crate) const LIST_MODE_TOKEN: &str = "<LIST_MODE>";
+pub(crate) const PROFILE_SEPARATOR_ARG: &str = "--new";
+/// Instance group of categories without an explicit `instanceGroup`. Its
+/// winws.exe also gets the raw WinDivert filters.
+pub(crate) const DEFAULT_INSTANCE_GROUP: &str = "main";
+pub const CONFIG_SCHEMA_VERSION: u32 = CONFIG_MIGRATIONS.len() as u32;
+
+type ConfigMigration = fn(&mut Value);
+
+/// `CONFIG_MIGRATIONS[n]` upgrades a stored config from schema `n` to `n + 1`.
+const CONFIG_MIGRATIONS: &[ConfigMigration] = &[
+    migrate_legacy_managed_path_alias,
+    migrate_legacy_system_strategy_names,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct GlobalPorts {
     pub tcp: String,
     pub udp: String,
+    /// Derive the port sets from the active strategies; `tcp` and `udp` are
+    /// kept as the manual override for when auto mode is switched off.
+    #[serde(default)]
+    pub auto: bool,
 }
 
 impl Default for GlobalPorts {
@@ -33,16 +63,86 @@ impl Default for GlobalPorts {
         Self {
             tcp: "1-65535".to_string(),
             udp: "1-65535".to_string(),
+            auto: false,
+        }
+    }
+}
+
+fn validate_global_ports(ports: &GlobalPorts) -> Result<(), String> {
+    normalize_global_port_list(&ports.tcp).map_err(|e| format!("некорректные TCP-порты: {e}"))?;
+    normalize_global_port_list(&ports.udp).map_err(|e| format!("некорректные UDP-порты: {e}"))?;
+    Ok(())
+}
+
+/// Rewrites both port lists in canonical form. A list that does not parse is
+/// left as is; stored configs with one are refused by `parse_stored_config`.
+fn normalize_global_ports(ports: &mut GlobalPorts) -> bool {
+    let mut changed = false;
+    for value in [&mut ports.tcp, &mut ports.udp] {
+        let Ok(normalized) = normalize_global_port_list(value) else {
+            continue;
+        };
+        if *value != normalized {
+            *value = normalized;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Instance group names end up in PID and log file names, so they are kept
+/// to lowercase ASCII letters, digits, `-` and `_`.
+fn is_valid_instance_group(group: &str) -> bool {
+    !group.is_empty()
+        && group.len() <= 32
+        && group.chars().all(|char| {
+            char.is_ascii_lowercase() || char.is_ascii_digit() || matches!(char, '-' | '_')
+        })
+}
+
+/// Lowercases instance group names; empty and invalid names as well as the
+/// default group's own name fall back to `None`.
+fn normalize_instance_groups(categories: &mut [Category]) -> bool {
+    let mut changed = false;
+    for category in categories {
+        let normalized = category
+            .instance_group
+            .as_deref()
+            .map(|group| group.trim().to_ascii_lowercase())
+            .filter(|group| is_valid_instance_group(group) && group != DEFAULT_INSTANCE_GROUP);
+        if category.instance_group != normalized {
+            category.instance_group = normalized;
+            changed = true;
         }
     }
+    changed
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Optional descriptive fields of a strategy. Serialized flat next to `name`
+/// and `content`; `last_verified` is an ISO 8601 date such as `2025-06-01`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_verified: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Strategy {
     pub id: String,
     pub name: String,
     pub content: String,
     pub active: bool,
+    #[serde(flatten)]
+    pub metadata: StrategyMetadata,
     #[serde(default)]
     pub system: bool,
     #[serde(
@@ -57,9 +157,15 @@ pub struct Strategy {
         skip_serializing_if = "Option::is_none"
     )]
     pub system_base_content: Option<String>,
+    #[serde(
+        default,
+        rename = "systemBaseMetadata",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub system_base_metadata: Option<StrategyMetadata>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Category {
     pub id: String,
     pub name: String,
@@ -72,12 +178,55 @@ pub struct Category {
         skip_serializing_if = "Option::is_none"
     )]
     pub system_base_name: Option<String>,
+    /// Id of the pack subscription the category came from.
+    #[serde(default, rename = "sourceId", skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    /// The winws.exe instance the category's strategies run in; `None` is the
+    /// default instance.
+    #[serde(
+        default,
+        rename = "instanceGroup",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub instance_group: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Category {
+    pub fn instance_group(&self) -> &str {
+        self.instance_group
+            .as_deref()
+            .unwrap_or(DEFAULT_INSTANCE_GROUP)
+    }
+}
+
+/// What a placeholder stands for. Only `Path` values are resolved against
+/// `@resources` and `~`; the other kinds are substituted as validated text.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PlaceholderKind {
+    #[default]
+    Path,
+    String,
+    Integer,
+    Hex,
+    DomainList,
+}
+
+impl PlaceholderKind {
+    pub fn is_path(&self) -> bool {
+        *self == Self::Path
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Placeholder {
     pub name: String,
-    pub path: String,
+    /// The placeholder value; a file path only for `PlaceholderKind::Path`.
+    /// Stored as `path`, the key from when every placeholder was a file.
+    #[serde(rename = "path")]
+    pub value: String,
+    #[serde(default, skip_serializing_if = "PlaceholderKind::is_path")]
+    pub kind: PlaceholderKind,
     #[serde(default)]
     pub system: bool,
     #[serde(
@@ -92,9 +241,11 @@ pub struct Placeholder {
         skip_serializing_if = "Option::is_none"
     )]
     pub system_base_path: Option<String>,
+    #[serde(default, rename = "sourceId", skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Filter {
     pub id: String,
     pub name: String,
@@ -130,7 +281,48 @@ pub struct Filter {
     pub system_base_active: Option<bool>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileStrategyRef {
+    pub category_id: String,
+    pub strategy_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyProfile {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub active_strategies: Vec<ProfileStrategyRef>,
+    #[serde(default)]
+    pub active_filter_ids: Vec<String>,
+    #[serde(default)]
+    pub list_mode: ListMode,
+    #[serde(default)]
+    pub global_ports: GlobalPorts,
+}
+
+/// An external strategy pack. The pack is verified with the minisign
+/// `public_key` when one is set, otherwise with a `.sha256` file next to it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackSubscription {
+    pub id: String,
+    pub url: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ListMode {
     #[default]
@@ -138,6 +330,15 @@ pub enum ListMode {
     Exclude,
 }
 
+impl ListMode {
+    pub(crate) fn strategy_arg(self) -> &'static str {
+        match self {
+            ListMode::Ipset => "--ipset={{IP_USER}}",
+            ListMode::Exclude => "--hostlist-exclude={{HOSTS_USER_EXCLUDE}}",
+        }
+    }
+}
+
 impl std::fmt::Display for ListMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -147,7 +348,7 @@ impl std::fmt::Display for ListMode {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum DiscordPresenceActivityType {
     #[default]
@@ -157,8 +358,10 @@ pub enum DiscordPresenceActivityType {
     Competing,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AppConfig {
+    #[serde(default, rename = "schemaVersion")]
+    pub schema_version: u32,
     pub global_ports: GlobalPorts,
     pub categories: Vec<Category>,
     pub placeholders: Vec<Placeholder>,
@@ -209,6 +412,16 @@ pub struct AppConfig {
     pub connect_on_autostart: bool,
     #[serde(default, rename = "listMode")]
     pub list_mode: ListMode,
+    #[serde(default)]
+    pub profiles: Vec<StrategyProfile>,
+    #[serde(
+        default,
+        rename = "activeProfileId",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub active_profile_id: Option<String>,
+    #[serde(default, rename = "packSubscriptions")]
+    pub pack_subscriptions: Vec<PackSubscription>,
     #[serde(
         default = "default_core_file_update_prompts_enabled",
         rename = "coreFileUpdatePromptsEnabled"
@@ -219,6 +432,14 @@ pub struct AppConfig {
         rename = "appAutoUpdatesEnabled"
     )]
     pub app_auto_updates_enabled: bool,
+    /// How many times in a row winws.exe is restarted after it crashes before
+    /// the connection is reported as lost; 0 disables automatic restarts.
+    #[serde(default = "default_winws_restart_limit", rename = "winwsRestartLimit")]
+    pub winws_restart_limit: u32,
+    /// Runs winws.exe with `--debug=1` so its output can be broken down into
+    /// per-profile and per-host counters.
+    #[serde(default, rename = "winwsDebug")]
+    pub winws_debug: bool,
 
     #[serde(default, rename = "systemRemovedCategoryIds")]
     pub system_removed_category_ids: Vec<String>,
@@ -237,6 +458,9 @@ pub struct ConfigEnsureResult {
     pub restored_default: bool,
     pub normalized_and_persisted: bool,
     pub unrecoverable_filters: Vec<String>,
+    /// Why config.json failed to load. The file is left as is and `config`
+    /// is the last one that loaded.
+    pub validation_error: Option<String>,
 }
 
 struct NormalizedConfigResult {
@@ -247,33 +471,33 @@ struct NormalizedConfigResult {
 
 pub(crate) fn validate_filter_filename(filename: &str) -> Result<String, String> {
     if filename.is_empty() {
-        return Err("Filename cannot be empty".to_string());
+        return Err("Имя файла не может быть пустым".to_string());
     }
     if filename.contains('/') || filename.contains('\\') {
-        return Err("Path separators not allowed in filename".to_string());
+        return Err("Имя файла не может содержать разделители пути".to_string());
     }
 
     let path = Path::new(filename);
     let mut components = path.components();
     let first_component = components
         .next()
-        .ok_or_else(|| "Filename cannot be empty".to_string())?;
+        .ok_or_else(|| "Имя файла не может быть пустым".to_string())?;
 
     if components.next().is_some() {
-        return Err("Path separators not allowed in filename".to_string());
+        return Err("Имя файла не может содержать разделители пути".to_string());
     }
 
     match first_component {
         Component::Normal(name) => {
             let name = name
                 .to_str()
-                .ok_or_else(|| "Invalid filename".to_string())?;
+                .ok_or_else(|| "Некорректное имя файла".to_string())?;
             if name.is_empty() || name == "." || name == ".." {
-                return Err("Invalid filename".to_string());
+                return Err("Некорректное имя файла".to_string());
             }
             Ok(name.to_string())
         }
-        _ => Err("Invalid filename".to_string()),
+        _ => Err("Некорректное имя файла".to_string()),
     }
 }
 
@@ -297,6 +521,10 @@ fn default_app_auto_updates_enabled() -> bool {
     true
 }
 
+fn default_winws_restart_limit() -> u32 {
+    3
+}
+
 fn default_dns_preset_id() -> String {
     "comss-one".to_string()
 }
@@ -362,7 +590,7 @@ fn system_strategy_key(category_id: &str, strategy_id: &str) -> String {
     format!("{category_id}::{strategy_id}")
 }
 
-fn annotate_builtin_category(category: &mut Category) {
+pub(crate) fn annotate_builtin_category(category: &mut Category) {
     category.system = true;
     category.system_base_name = Some(category.name.clone());
 
@@ -370,13 +598,14 @@ fn annotate_builtin_category(category: &mut Category) {
         strategy.system = true;
         strategy.system_base_name = Some(strategy.name.clone());
         strategy.system_base_content = Some(strategy.content.clone());
+        strategy.system_base_metadata = Some(strategy.metadata.clone());
     }
 }
 
-fn annotate_builtin_placeholder(placeholder: &mut Placeholder) {
+pub(crate) fn annotate_builtin_placeholder(placeholder: &mut Placeholder) {
     placeholder.system = true;
     placeholder.system_base_name = Some(placeholder.name.clone());
-    placeholder.system_base_path = Some(placeholder.path.clone());
+    placeholder.system_base_path = Some(placeholder.value.clone());
 }
 
 fn annotate_builtin_filter(filter: &mut Filter) {
@@ -394,7 +623,7 @@ fn strategy_base_name(strategy: &Strategy) -> &str {
         .unwrap_or(strategy.name.as_str())
 }
 
-fn strategy_base_content(strategy: &Strategy) -> &str {
+pub(crate) fn strategy_base_content(strategy: &Strategy) -> &str {
     strategy
         .system_base_content
         .as_deref()
@@ -425,9 +654,44 @@ fn is_legacy_system_strategy_name(
         .any(|candidate| current_name == candidate)
 }
 
+fn strategy_base_metadata(strategy: &Strategy) -> &StrategyMetadata {
+    strategy
+        .system_base_metadata
+        .as_ref()
+        .unwrap_or(&strategy.metadata)
+}
+
 fn is_system_strategy_modified(strategy: &Strategy) -> bool {
     strategy.name != strategy_base_name(strategy)
         || strategy.content != strategy_base_content(strategy)
+        || strategy.metadata != *strategy_base_metadata(strategy)
+}
+
+/// Takes each builtin metadata field the user has not edited since `base`.
+fn merge_strategy_metadata(
+    metadata: &mut StrategyMetadata,
+    base: &StrategyMetadata,
+    builtin: &StrategyMetadata,
+) {
+    fn merge_field<T: PartialEq + Clone>(current: &mut T, base: &T, builtin: &T) {
+        if current == base {
+            current.clone_from(builtin);
+        }
+    }
+
+    merge_field(
+        &mut metadata.description,
+        &base.description,
+        &builtin.description,
+    );
+    merge_field(&mut metadata.tags, &base.tags, &builtin.tags);
+    merge_field(&mut metadata.targets, &base.targets, &builtin.targets);
+    merge_field(&mut metadata.author, &base.author, &builtin.author);
+    merge_field(
+        &mut metadata.last_verified,
+        &base.last_verified,
+        &builtin.last_verified,
+    );
 }
 
 fn category_base_name(category: &Category) -> &str {
@@ -441,7 +705,7 @@ fn is_system_category_name_modified(category: &Category) -> bool {
     category.name != category_base_name(category)
 }
 
-fn is_system_category_modified(category: &Category) -> bool {
+pub(crate) fn is_system_category_modified(category: &Category) -> bool {
     if is_system_category_name_modified(category) {
         return true;
     }
@@ -463,12 +727,12 @@ fn placeholder_base_path(placeholder: &Placeholder) -> &str {
     placeholder
         .system_base_path
         .as_deref()
-        .unwrap_or(placeholder.path.as_str())
+        .unwrap_or(placeholder.value.as_str())
 }
 
-fn is_system_placeholder_modified(placeholder: &Placeholder) -> bool {
+pub(crate) fn is_system_placeholder_modified(placeholder: &Placeholder) -> bool {
     placeholder.name != placeholder_base_name(placeholder)
-        || placeholder.path != placeholder_base_path(placeholder)
+        || placeholder.value != placeholder_base_path(placeholder)
 }
 
 fn filter_base_name(filter: &Filter) -> &str {
@@ -647,6 +911,10 @@ pub(crate) fn get_runtime_data_dir() -> PathBuf {
     executable_dir().unwrap_or_else(|| PathBuf::from("."))
 }
 
+pub(crate) fn get_filters_dir() -> PathBuf {
+    get_runtime_data_dir().join(FILTERS_DIR_NAME)
+}
+
 fn managed_relative_path(path: &str) -> Option<String> {
     let normalized = path.replace('\\', "/");
     if normalized == MANAGED_PATH_ALIAS {
@@ -668,21 +936,13 @@ fn join_relative_path(base: &Path, relative: &str) -> PathBuf {
     path
 }
 
-fn normalize_placeholder_path(path: &str) -> Option<String> {
+pub(crate) fn normalize_placeholder_path(path: &str) -> Option<String> {
     let normalized = path.replace('\\', "/");
     if normalized == MANAGED_PATH_ALIAS || normalized.starts_with(&format!("{MANAGED_PATH_ALIAS}/"))
     {
         return Some(normalized);
     }
 
-    if normalized == LEGACY_MANAGED_PATH_ALIAS {
-        return Some(MANAGED_PATH_ALIAS.to_string());
-    }
-
-    if let Some(suffix) = normalized.strip_prefix(&format!("{LEGACY_MANAGED_PATH_ALIAS}/")) {
-        return Some(format!("{MANAGED_PATH_ALIAS}/{suffix}"));
-    }
-
     None
 }
 
@@ -690,10 +950,10 @@ fn normalize_placeholder_paths(placeholders: &mut [Placeholder]) -> bool {
     let mut changed = false;
 
     for placeholder in placeholders.iter_mut() {
-        if let Some(normalized) = normalize_placeholder_path(&placeholder.path)
-            && placeholder.path != normalized
+        if let Some(normalized) = normalize_placeholder_path(&placeholder.value)
+            && placeholder.value != normalized
         {
-            placeholder.path = normalized;
+            placeholder.value = normalized;
             changed = true;
         }
     }
@@ -722,6 +982,7 @@ impl Default for AppConfig {
         config.system_removed_strategy_keys.clear();
         config.system_removed_placeholder_names.clear();
         config.system_removed_filter_ids.clear();
+        config.schema_version = CONFIG_SCHEMA_VERSION;
         config.system_sync_initialized = true;
         if config.filters.is_empty() {
             config.filters = default_filters_metadata();
@@ -894,14 +1155,19 @@ pub struct AppState {
 
 impl AppState {
     pub fn new() -> Result<Self, String> {
-        let ensured = ensure_config_exists_and_normalized()?;
+        let ensured = ensure_config_exists_and_normalized(None)?;
         Ok(Self {
             config: Mutex::new(ensured.config),
         })
     }
 }
 
-fn read_config_from_disk() -> Result<Option<AppConfig>, String> {
+struct StoredConfig {
+    config: AppConfig,
+    schema_version: u32,
+}
+
+fn read_config_from_disk() -> Result<Option<String>, String> {
     ensure_runtime_data_dir_ready()?;
     let config_path = get_config_path();
 
@@ -913,9 +1179,248 @@ fn read_config_from_disk() -> Result<Option<AppConfig>, String> {
         return Ok(None);
     }
 
-    let content = fs::read_to_string(&config_path).map_err(|e| e.to_string())?;
-    let config: AppConfig = serde_json::from_str(&content).map_err(|e| e.to_string())?;
-    Ok(Some(config))
+    fs::read_to_string(&config_path)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+fn parse_stored_config(content: &str) -> Result<StoredConfig, String> {
+    let mut value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let schema_version = migrate_config_value(&mut value)?;
+    // A newer schema may add keys this version does not know; such configs
+    // are refused or kept read-only further up instead.
+    if schema_version <= CONFIG_SCHEMA_VERSION {
+        remove_retired_config_keys(&mut value);
+        validate_config_value(&value)?;
+    }
+    let config = deserialize_config(value)?;
+    // A hand-edited port list winws would reject is reported instead of
+    // being replaced with the defaults behind the user's back.
+    validate_global_ports(&config.global_ports)
+        .map_err(|error| format!("global_ports: {error}"))?;
+    Ok(StoredConfig {
+        config,
+        schema_version,
+    })
+}
+
+/// Deserializes a config value against the same types the published schema is
+/// generated from, naming the offending field by its path (for example
+/// `categories[3].strategies[1].content`) instead of serde's bare message.
+fn deserialize_config(value: Value) -> Result<AppConfig, String> {
+    serde_path_to_error::deserialize(value)
+        .map_err(|error| format!("{}: {}", error.path(), error.inner()))
+}
+
+/// Keys older versions wrote that no longer mean anything; they are dropped
+/// rather than reported as unknown.
+const RETIRED_CONFIG_KEYS: &[&str] = &["windowMaterial"];
+
+fn remove_retired_config_keys(value: &mut Value) {
+    if let Some(object) = value.as_object_mut() {
+        for key in RETIRED_CONFIG_KEYS {
+            object.remove(*key);
+        }
+    }
+}
+
+/// Checks a config value against the published schema, so that unknown or
+/// misspelled keys and wrongly typed values that `#[serde(default)]` would
+/// quietly replace are refused. Every violation is reported with its path.
+fn validate_config_value(value: &Value) -> Result<(), String> {
+    let validator = jsonschema::validator_for(&config_json_schema())
+        .map_err(|error| format!("некорректная JSON Schema конфига: {error}"))?;
+    let errors: Vec<String> = validator
+        .iter_errors(value)
+        .map(|error| {
+            format!(
+                "{}: {}",
+                instance_path(error.instance_path()),
+                error.masked()
+            )
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Formats a JSON pointer the way `serde_path_to_error` does.
+fn instance_path(location: &jsonschema::paths::Location) -> String {
+    let mut path = String::new();
+    for segment in location {
+        match segment {
+            jsonschema::paths::LocationSegment::Index(index) => {
+                path.push_str(&format!("[{index}]"));
+            }
+            jsonschema::paths::LocationSegment::Property(name) => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(&name);
+            }
+        }
+    }
+    if path.is_empty() {
+        ".".to_string()
+    } else {
+        path
+    }
+}
+
+/// The schema of `config.json`. Objects refuse properties they do not
+/// declare, so a misspelled key is an error rather than silently dropped.
+pub(crate) fn config_json_schema() -> Value {
+    let mut schema = serde_json::to_value(schemars::schema_for!(AppConfig)).unwrap_or(Value::Null);
+    deny_unknown_properties(&mut schema);
+    schema
+}
+
+fn deny_unknown_properties(schema: &mut Value) {
+    match schema {
+        Value::Object(object) => {
+            if object.contains_key("properties") && !object.contains_key("additionalProperties") {
+                object.insert("additionalProperties".to_string(), Value::Bool(false));
+            }
+            object.values_mut().for_each(deny_unknown_properties);
+        }
+        Value::Array(items) => items.iter_mut().for_each(deny_unknown_properties),
+        _ => {}
+    }
+}
+
+/// Parses a stored config snapshot, refusing ones written by a newer schema.
+pub(crate) fn parse_config_snapshot(content: &str) -> Result<AppConfig, String> {
+    let stored = parse_stored_config(content)?;
+    if stored.schema_version > CONFIG_SCHEMA_VERSION {
+        return Err(format!(
+            "config.json записан более новой версией приложения (схема {}, поддерживается {CONFIG_SCHEMA_VERSION})",
+            stored.schema_version
+        ));
+    }
+    Ok(stored.config)
+}
+
+fn stored_schema_version(value: &Value) -> u32 {
+    value
+        .get("schemaVersion")
+        .and_then(Value::as_u64)
+        .map(|version| u32::try_from(version).unwrap_or(u32::MAX))
+        .unwrap_or(0)
+}
+
+fn migrate_config_value(value: &mut Value) -> Result<u32, String> {
+    let schema_version = stored_schema_version(value);
+    if schema_version >= CONFIG_SCHEMA_VERSION {
+        return Ok(schema_version);
+    }
+
+    for migration in &CONFIG_MIGRATIONS[schema_version as usize..] {
+        migration(value);
+    }
+    value
+        .as_object_mut()
+        .ok_or_else(|| "config.json должен содержать JSON-объект".to_string())?
+        .insert("schemaVersion".to_string(), CONFIG_SCHEMA_VERSION.into());
+    Ok(schema_version)
+}
+
+fn migrate_legacy_managed_path(path: &str) -> Option<String> {
+    let normalized = path.replace('\\', "/");
+    if normalized == LEGACY_MANAGED_PATH_ALIAS {
+        return Some(MANAGED_PATH_ALIAS.to_string());
+    }
+
+    normalized
+        .strip_prefix(&format!("{LEGACY_MANAGED_PATH_ALIAS}/"))
+        .map(|suffix| format!("{MANAGED_PATH_ALIAS}/{suffix}"))
+}
+
+fn migrate_legacy_managed_path_alias(config: &mut Value) {
+    let Some(placeholders) = config.get_mut("placeholders").and_then(Value::as_array_mut) else {
+        return;
+    };
+
+    for placeholder in placeholders {
+        for key in ["path", "systemBasePath"] {
+            if let Some(Value::String(path)) = placeholder.get_mut(key)
+                && let Some(migrated) = migrate_legacy_managed_path(path)
+            {
+                *path = migrated;
+            }
+        }
+    }
+}
+
+fn migrate_legacy_system_strategy_names(config: &mut Value) {
+    let builtin_config = AppConfig::default();
+    let Some(categories) = config.get_mut("categories").and_then(Value::as_array_mut) else {
+        return;
+    };
+
+    for category in categories {
+        let Some(builtin_category) = category
+            .get("id")
+            .and_then(Value::as_str)
+            .and_then(|id| builtin_config.categories.iter().find(|item| item.id == id))
+        else {
+            continue;
+        };
+        let Some(strategies) = category.get_mut("strategies").and_then(Value::as_array_mut) else {
+            continue;
+        };
+
+        for strategy in strategies {
+            let text = |key: &str| strategy.get(key).and_then(Value::as_str);
+            let Some(id) = text("id") else {
+                continue;
+            };
+            let Some(builtin_strategy) = builtin_category
+                .strategies
+                .iter()
+                .find(|item| item.id == id)
+            else {
+                continue;
+            };
+            let name = text("name").unwrap_or_default();
+            let content = text("content").unwrap_or_default();
+            let base_name = text("systemBaseName").unwrap_or(&builtin_strategy.name);
+            let base_content = text("systemBaseContent").unwrap_or(&builtin_strategy.content);
+
+            if content == base_content
+                && base_name == builtin_strategy.name
+                && base_content == builtin_strategy.content
+                && is_legacy_system_strategy_name(
+                    name,
+                    &builtin_category.name,
+                    id,
+                    &builtin_strategy.name,
+                )
+            {
+                strategy["name"] = Value::String(builtin_strategy.name.clone());
+            }
+        }
+    }
+}
+
+fn ensure_config_not_newer(config_path: &Path) -> Result<(), String> {
+    let Ok(content) = fs::read_to_string(config_path) else {
+        return Ok(());
+    };
+    let Ok(value) = serde_json::from_str::<Value>(&content) else {
+        return Ok(());
+    };
+
+    let schema_version = stored_schema_version(&value);
+    if schema_version > CONFIG_SCHEMA_VERSION {
+        return Err(format!(
+            "config.json записан более новой версией приложения (схема {schema_version}, поддерживается {CONFIG_SCHEMA_VERSION}), перезапись отменена"
+        ));
+    }
+
+    Ok(())
 }
 
 fn normalize_config(mut config: AppConfig) -> NormalizedConfigResult {
@@ -924,6 +1429,11 @@ fn normalize_config(mut config: AppConfig) -> NormalizedConfigResult {
     let expected_binaries_path = get_managed_resources_dir().to_string_lossy().to_string();
     let builtin_config = AppConfig::default();
 
+    if config.schema_version != CONFIG_SCHEMA_VERSION {
+        config.schema_version = CONFIG_SCHEMA_VERSION;
+        changed = true;
+    }
+
     if config.binaries_path != expected_binaries_path {
         config.binaries_path = expected_binaries_path;
         changed = true;
@@ -978,6 +1488,14 @@ fn normalize_config(mut config: AppConfig) -> NormalizedConfigResult {
         changed = true;
     }
 
+    if normalize_global_ports(&mut config.global_ports) {
+        changed = true;
+    }
+
+    if normalize_instance_groups(&mut config.categories) {
+        changed = true;
+    }
+
     if populate_builtin_filter_content(&mut config.filters) {
         changed = true;
     }
@@ -986,6 +1504,14 @@ fn normalize_config(mut config: AppConfig) -> NormalizedConfigResult {
         changed = true;
     }
 
+    let before_placeholder_count = config.placeholders.len();
+    config
+        .placeholders
+        .retain(|placeholder| !is_dynamic_placeholder(&placeholder.name));
+    if config.placeholders.len() != before_placeholder_count {
+        changed = true;
+    }
+
     if sync_builtin_placeholders(&mut config, &builtin_config) {
         changed = true;
     }
@@ -998,6 +1524,10 @@ fn normalize_config(mut config: AppConfig) -> NormalizedConfigResult {
         changed = true;
     }
 
+    if normalize_profiles(&mut config) {
+        changed = true;
+    }
+
     for filter in config.filters.iter_mut() {
         let validated_filename = match validate_filter_filename(&filter.filename) {
             Ok(filename) => filename,
@@ -1011,9 +1541,7 @@ fn normalize_config(mut config: AppConfig) -> NormalizedConfigResult {
             }
         };
         if !filter.content.is_empty() {
-            let filter_path = get_runtime_data_dir()
-                .join("filters")
-                .join(&validated_filename);
+            let filter_path = get_filters_dir().join(&validated_filename);
             if let Ok(existing_content) = fs::read_to_string(&filter_path) {
                 if existing_content != filter.content
                     && let Err(error) = fs::write(&filter_path, &filter.content)
@@ -1046,9 +1574,7 @@ fn normalize_config(mut config: AppConfig) -> NormalizedConfigResult {
             continue;
         }
 
-        let filter_path = get_runtime_data_dir()
-            .join("filters")
-            .join(&validated_filename);
+        let filter_path = get_filters_dir().join(&validated_filename);
         match fs::read_to_string(&filter_path) {
             Ok(content) => {
                 filter.content = content;
@@ -1074,7 +1600,11 @@ fn sync_builtin_placeholders(config: &mut AppConfig, builtin_config: &AppConfig)
     config.system_removed_placeholder_names.sort();
     config.system_removed_placeholder_names.dedup();
 
-    for placeholder in &mut config.placeholders {
+    for placeholder in config
+        .placeholders
+        .iter_mut()
+        .filter(|placeholder| placeholder.source_id.is_none())
+    {
         if let Some(builtin_placeholder) = builtin_config.placeholders.iter().find(|item| {
             item.name == placeholder.name
                 || placeholder
@@ -1089,10 +1619,11 @@ fn sync_builtin_placeholders(config: &mut AppConfig, builtin_config: &AppConfig)
 
     let before_count = config.placeholders.len();
     config.placeholders.retain(|placeholder| {
-        if builtin_config
-            .placeholders
-            .iter()
-            .any(|builtin| builtin.name == placeholder.name)
+        if placeholder.source_id.is_some()
+            || builtin_config
+                .placeholders
+                .iter()
+                .any(|builtin| builtin.name == placeholder.name)
         {
             return true;
         }
@@ -1122,7 +1653,7 @@ fn sync_builtin_placeholders(config: &mut AppConfig, builtin_config: &AppConfig)
     changed
 }
 
-fn sync_builtin_placeholder(
+pub(crate) fn sync_builtin_placeholder(
     placeholder: &mut Placeholder,
     builtin_placeholder: &Placeholder,
 ) -> bool {
@@ -1139,7 +1670,7 @@ fn sync_builtin_placeholder(
     }
 
     if placeholder.system_base_path.is_none() {
-        placeholder.system_base_path = Some(builtin_placeholder.path.clone());
+        placeholder.system_base_path = Some(builtin_placeholder.value.clone());
         changed = true;
     }
 
@@ -1148,16 +1679,20 @@ fn sync_builtin_placeholder(
             placeholder.name = builtin_placeholder.name.clone();
             changed = true;
         }
-        if placeholder.path != builtin_placeholder.path {
-            placeholder.path = builtin_placeholder.path.clone();
+        if placeholder.value != builtin_placeholder.value {
+            placeholder.value = builtin_placeholder.value.clone();
+            changed = true;
+        }
+        if placeholder.kind != builtin_placeholder.kind {
+            placeholder.kind = builtin_placeholder.kind;
             changed = true;
         }
         if placeholder.system_base_name.as_deref() != Some(builtin_placeholder.name.as_str()) {
             placeholder.system_base_name = Some(builtin_placeholder.name.clone());
             changed = true;
         }
-        if placeholder.system_base_path.as_deref() != Some(builtin_placeholder.path.as_str()) {
-            placeholder.system_base_path = Some(builtin_placeholder.path.clone());
+        if placeholder.system_base_path.as_deref() != Some(builtin_placeholder.value.as_str()) {
+            placeholder.system_base_path = Some(builtin_placeholder.value.clone());
             changed = true;
         }
     }
@@ -1311,10 +1846,11 @@ fn sync_builtin_categories(config: &mut AppConfig, builtin_config: &AppConfig) -
 
     let before_category_count = config.categories.len();
     config.categories.retain(|category| {
-        if builtin_config
-            .categories
-            .iter()
-            .any(|builtin| builtin.id == category.id)
+        if category.source_id.is_some()
+            || builtin_config
+                .categories
+                .iter()
+                .any(|builtin| builtin.id == category.id)
         {
             return true;
         }
@@ -1349,7 +1885,7 @@ fn sync_builtin_categories(config: &mut AppConfig, builtin_config: &AppConfig) -
     changed
 }
 
-fn sync_builtin_category(
+pub(crate) fn sync_builtin_category(
     category: &mut Category,
     builtin_category: &Category,
     removed_strategy_keys: &[String],
@@ -1383,7 +1919,7 @@ fn sync_builtin_category(
             .strategies
             .iter()
             .find(|item| item.id == strategy.id)
-            && sync_builtin_strategy(strategy, builtin_strategy, &builtin_category.name)
+            && sync_builtin_strategy(strategy, builtin_strategy)
         {
             changed = true;
         }
@@ -1423,11 +1959,7 @@ fn sync_builtin_category(
     changed
 }
 
-fn sync_builtin_strategy(
-    strategy: &mut Strategy,
-    builtin_strategy: &Strategy,
-    builtin_category_name: &str,
-) -> bool {
+fn sync_builtin_strategy(strategy: &mut Strategy, builtin_strategy: &Strategy) -> bool {
     let mut changed = false;
 
     if !strategy.system {
@@ -1445,30 +1977,40 @@ fn sync_builtin_strategy(
         changed = true;
     }
 
-    let can_auto_migrate_legacy_name = strategy.content == strategy_base_content(strategy)
-        && strategy_base_name(strategy) == builtin_strategy.name
-        && strategy_base_content(strategy) == builtin_strategy.content
-        && is_legacy_system_strategy_name(
-            &strategy.name,
-            builtin_category_name,
-            &strategy.id,
-            &builtin_strategy.name,
-        );
+    // Configs written before metadata existed have none, so their current
+    // (empty) metadata is the base and every builtin field gets filled in.
+    let base_metadata = strategy.system_base_metadata.take();
+    let previous_metadata = strategy.metadata.clone();
+    merge_strategy_metadata(
+        &mut strategy.metadata,
+        base_metadata.as_ref().unwrap_or(&previous_metadata),
+        &builtin_strategy.metadata,
+    );
+    if strategy.metadata != previous_metadata
+        || base_metadata.as_ref() != Some(&builtin_strategy.metadata)
+    {
+        changed = true;
+    }
+    strategy.system_base_metadata = Some(builtin_strategy.metadata.clone());
 
-    if !is_system_strategy_modified(strategy) || can_auto_migrate_legacy_name {
-        if strategy.name != builtin_strategy.name {
+    if strategy_base_name(strategy) != builtin_strategy.name {
+        if strategy.name == strategy_base_name(strategy) {
             strategy.name = builtin_strategy.name.clone();
-            changed = true;
-        }
-        if strategy.content != builtin_strategy.content {
-            strategy.content = builtin_strategy.content.clone();
-            changed = true;
         }
-        if strategy.system_base_name.as_deref() != Some(builtin_strategy.name.as_str()) {
-            strategy.system_base_name = Some(builtin_strategy.name.clone());
-            changed = true;
-        }
-        if strategy.system_base_content.as_deref() != Some(builtin_strategy.content.as_str()) {
+        strategy.system_base_name = Some(builtin_strategy.name.clone());
+        changed = true;
+    }
+
+    // Conflicting updates leave both the content and its base untouched, so
+    // the update stays pending until the user picks a side.
+    if strategy_base_content(strategy) != builtin_strategy.content {
+        let merge = merge_strategy_content(
+            strategy_base_content(strategy),
+            &strategy.content,
+            &builtin_strategy.content,
+        );
+        if merge.conflicts.is_empty() {
+            strategy.content = merge.content;
             strategy.system_base_content = Some(builtin_strategy.content.clone());
             changed = true;
         }
@@ -1477,20 +2019,32 @@ fn sync_builtin_strategy(
     changed
 }
 
-fn ensure_config_exists_and_normalized() -> Result<ConfigEnsureResult, String> {
+fn ensure_config_exists_and_normalized(
+    last_loaded: Option<&AppConfig>,
+) -> Result<ConfigEnsureResult, String> {
     ensure_managed_resources_dir_ready()?;
 
-    let ensured: ConfigEnsureResult = match read_config_from_disk()? {
-        Some(config) => {
-            let normalized = normalize_config(config);
-            if normalized.changed {
+    let stored = match read_config_from_disk()? {
+        Some(content) => match parse_stored_config(&content) {
+            Ok(stored) => Some(stored),
+            Err(error) => return keep_config_after_invalid_load(last_loaded, error),
+        },
+        None => None,
+    };
+
+    let ensured: ConfigEnsureResult = match stored {
+        Some(stored) => {
+            let normalized = normalize_config(stored.config);
+            let persist = normalized.changed && stored.schema_version <= CONFIG_SCHEMA_VERSION;
+            if persist {
                 save_config_to_disk(&normalized.config)?;
             }
             ConfigEnsureResult {
                 config: normalized.config,
                 restored_default: false,
-                normalized_and_persisted: normalized.changed,
+                normalized_and_persisted: persist,
                 unrecoverable_filters: normalized.unrecoverable_filters,
+                validation_error: None,
             }
         }
         None => {
@@ -1501,6 +2055,7 @@ fn ensure_config_exists_and_normalized() -> Result<ConfigEnsureResult, String> {
                 restored_default: true,
                 normalized_and_persisted: false,
                 unrecoverable_filters: Vec::new(),
+                validation_error: None,
             }
         }
     };
@@ -1508,8 +2063,54 @@ fn ensure_config_exists_and_normalized() -> Result<ConfigEnsureResult, String> {
     Ok(ensured)
 }
 
+/// Leaves a config.json that failed validation in place and keeps the last
+/// config that loaded. On startup there is none yet, so the default config
+/// is used in memory and the broken file is first copied under a timestamped
+/// name, where a later save cannot overwrite it. A config from a newer schema
+/// is reported as an error.
+fn keep_config_after_invalid_load(
+    last_loaded: Option<&AppConfig>,
+    error: String,
+) -> Result<ConfigEnsureResult, String> {
+    let config_path = get_config_path();
+    ensure_config_not_newer(&config_path)?;
+
+    let (config, validation_error) = match last_loaded {
+        Some(config) => (config.clone(), error),
+        None => {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis())
+                .unwrap_or_default();
+            let copy_path = config_path.with_file_name(format!("config.invalid-{timestamp}.json"));
+            fs::copy(&config_path, &copy_path).map_err(|e| {
+                format!(
+                    "config.json содержит ошибку ({error}), и его копию не удалось сохранить: {e}"
+                )
+            })?;
+            let copy_name = copy_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            (
+                AppConfig::default(),
+                format!("{error} (копия: {copy_name})"),
+            )
+        }
+    };
+
+    Ok(ConfigEnsureResult {
+        restored_default: last_loaded.is_none(),
+        config,
+        normalized_and_persisted: false,
+        unrecoverable_filters: Vec::new(),
+        validation_error: Some(validation_error),
+    })
+}
+
 pub fn ensure_config_exists_and_loaded(state: &AppState) -> Result<ConfigEnsureResult, String> {
-    let ensured = ensure_config_exists_and_normalized()?;
+    let last_loaded = current_config(state)?;
+    let ensured = ensure_config_exists_and_normalized(Some(&last_loaded))?;
     let mut cfg = state.config.lock().map_err(|e| e.to_string())?;
     *cfg = ensured.config.clone();
     drop(cfg);
@@ -1527,7 +2128,11 @@ pub fn current_config(state: &AppState) -> Result<AppConfig, String> {
 pub fn save_config_to_disk(config: &AppConfig) -> Result<(), String> {
     let _ = ensure_runtime_data_dir_ready()?;
     let config_path = get_config_path();
+    ensure_config_not_newer(&config_path)?;
     let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    if let Err(error) = record_config_history(&config_path, &content) {
+        eprintln!("Failed to record config history: {error}");
+    }
     let temp_path = config_path.with_extension("json.tmp");
     let mut temp_file = fs::File::create(&temp_path).map_err(|e| e.to_string())?;
     use std::io::Write;
@@ -1557,41 +2162,155 @@ pub fn get_builtin_config() -> AppConfig {
 }
 
 #[tauri::command]
-pub fn save_config(config: AppConfig, state: tauri::State<'_, AppState>) -> Result<(), String> {
+pub fn get_config_schema() -> Value {
+    config_json_schema()
+}
+
+pub(crate) fn replace_current_config(
+    config: AppConfig,
+    state: &AppState,
+) -> Result<AppConfig, String> {
+    validate_global_ports(&config.global_ports)?;
     let normalized = normalize_config(config);
     save_config_to_disk(&normalized.config)?;
     let mut cfg = state.config.lock().map_err(|e| e.to_string())?;
-    *cfg = normalized.config;
-    Ok(())
+    *cfg = normalized.config.clone();
+    Ok(normalized.config)
+}
+
+#[tauri::command]
+pub fn save_config(config: AppConfig, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    replace_current_config(config, &state).map(|_| ())
 }
 
 #[tauri::command]
-pub fn reset_config(state: tauri::State<'_, AppState>) -> Result<AppConfig, String> {
+pub fn reset_config(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<AppConfig, String> {
     let default_config = AppConfig::default();
+    snapshot_config_history()?;
     save_config_to_disk(&default_config)?;
     let mut cfg = state.config.lock().map_err(|e| e.to_string())?;
     *cfg = default_config.clone();
+    drop(cfg);
+    crate::sync_profiles_ui(&app, &default_config)?;
     Ok(default_config)
 }
 
-#[tauri::command]
-pub fn update_list_mode(
-    app: tauri::AppHandle,
-    mode: ListMode,
-    state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
-    let next_config = {
-        let cfg = state.config.lock().map_err(|e| e.to_string())?;
-        if cfg.list_mode == mode {
-            drop(cfg);
-            crate::sync_list_mode_ui(&app, mode)?;
-            return Ok(());
-        }
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyMergeReport {
+    pub category_id: String,
+    pub category_name: String,
+    pub strategy_id: String,
+    pub strategy_name: String,
+    pub conflicts: Vec<MergeConflict>,
+}
 
-        let mut next = cfg.clone();
-        next.list_mode = mode;
-        next
-    };
+fn find_builtin_strategy<'a>(
+    builtin_config: &'a AppConfig,
+    category_id: &str,
+    strategy_id: &str,
+) -> Option<&'a Strategy> {
+    builtin_config
+        .categories
+        .iter()
+        .find(|category| category.id == category_id)?
+        .strategies
+        .iter()
+        .find(|strategy| strategy.id == strategy_id)
+}
+
+/// Builtin updates the sync could not merge into the user's edits.
+fn builtin_merge_conflicts(
+    config: &AppConfig,
+    builtin_config: &AppConfig,
+) -> Vec<StrategyMergeReport> {
+    let mut reports = Vec::new();
+    for category in &config.categories {
+        for strategy in category
+            .strategies
+            .iter()
+            .filter(|strategy| strategy.system)
+        {
+            let Some(builtin_strategy) =
+                find_builtin_strategy(builtin_config, &category.id, &strategy.id)
+            else {
+                continue;
+            };
+            let merge = merge_strategy_content(
+                strategy_base_content(strategy),
+                &strategy.content,
+                &builtin_strategy.content,
+            );
+            if !merge.conflicts.is_empty() {
+                reports.push(StrategyMergeReport {
+                    category_id: category.id.clone(),
+                    category_name: category.name.clone(),
+                    strategy_id: strategy.id.clone(),
+                    strategy_name: strategy.name.clone(),
+                    conflicts: merge.conflicts,
+                });
+            }
+        }
+    }
+    reports
+}
+
+#[tauri::command]
+pub fn get_builtin_merge_conflicts(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<StrategyMergeReport>, String> {
+    Ok(builtin_merge_conflicts(
+        &current_config(&state)?,
+        &AppConfig::default(),
+    ))
+}
+
+/// Resolves a conflicting builtin update in favour of the user's content by
+/// moving the merge base forward to the current builtin version.
+#[tauri::command]
+pub fn keep_user_strategy_content(
+    category_id: String,
+    strategy_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let builtin_config = AppConfig::default();
+    let builtin_content = find_builtin_strategy(&builtin_config, &category_id, &strategy_id)
+        .map(|strategy| strategy.content.clone())
+        .ok_or_else(|| format!("Встроенная стратегия «{category_id}::{strategy_id}» не найдена"))?;
+
+    let mut next = current_config(&state)?;
+    let strategy = next
+        .categories
+        .iter_mut()
+        .filter(|category| category.id == category_id)
+        .flat_map(|category| category.strategies.iter_mut())
+        .find(|strategy| strategy.id == strategy_id)
+        .ok_or_else(|| format!("Стратегия «{category_id}::{strategy_id}» не найдена"))?;
+    strategy.system_base_content = Some(builtin_content);
+    replace_current_config(next, &state)
+}
+
+#[tauri::command]
+pub fn update_list_mode(
+    app: tauri::AppHandle,
+    mode: ListMode,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let next_config = {
+        let cfg = state.config.lock().map_err(|e| e.to_string())?;
+        if cfg.list_mode == mode {
+            drop(cfg);
+            crate::sync_list_mode_ui(&app, mode)?;
+            return Ok(());
+        }
+
+        let mut next = cfg.clone();
+        next.list_mode = mode;
+        next
+    };
 
     save_config_to_disk(&next_config)?;
 
@@ -1612,33 +2331,1291 @@ pub fn config_exists() -> bool {
     get_config_path().is_file()
 }
 
-#[tauri::command]
-pub fn resolve_placeholders(content: String, placeholders: Vec<Placeholder>) -> String {
-    let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+fn resolve_placeholder_path(path: &str, home_dir: &Path) -> String {
+    if let Some(path) = resolve_managed_placeholder_path(path) {
+        return path;
+    }
+
+    if let Some(relative) = path.strip_prefix('~') {
+        let relative_trimmed = relative.trim_start_matches('/').trim_start_matches('\\');
+        let mut resolved = home_dir.to_path_buf();
+        for part in relative_trimmed.split(['/', '\\']) {
+            if !part.is_empty() {
+                resolved.push(part);
+            }
+        }
+        return resolved.to_string_lossy().to_string();
+    }
+
+    path.to_string()
+}
+
+fn is_valid_domain(domain: &str) -> bool {
+    let domain = domain.strip_prefix("*.").unwrap_or(domain);
+    domain.len() <= 253
+        && domain.split('.').count() > 1
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|char| char.is_ascii_alphanumeric() || char == '-')
+        })
+}
+
+/// Validates a non-path placeholder value and returns the text that replaces
+/// its token. Path values are returned as is; they are resolved separately.
+pub(crate) fn placeholder_value(kind: PlaceholderKind, value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("Значение плейсхолдера не может быть пустым".to_string());
+    }
+
+    match kind {
+        PlaceholderKind::Path => Ok(value.to_string()),
+        PlaceholderKind::String => {
+            if value.chars().any(char::is_whitespace) {
+                Err(format!("Строка не должна содержать пробелов: «{value}»"))
+            } else {
+                Ok(value.to_string())
+            }
+        }
+        PlaceholderKind::Integer => value
+            .parse::<i64>()
+            .map(|number| number.to_string())
+            .map_err(|_| format!("«{value}» не является целым числом")),
+        PlaceholderKind::Hex => {
+            let digits = value
+                .strip_prefix("0x")
+                .or_else(|| value.strip_prefix("0X"))
+                .unwrap_or(value);
+            if !digits.is_empty()
+                && digits.len().is_multiple_of(2)
+                && digits.chars().all(|char| char.is_ascii_hexdigit())
+            {
+                Ok(format!("0x{}", digits.to_ascii_lowercase()))
+            } else {
+                Err(format!("«{value}» не является hex-строкой"))
+            }
+        }
+        PlaceholderKind::DomainList => {
+            let domains = value
+                .split(|char: char| char == ',' || char.is_whitespace())
+                .filter(|domain| !domain.is_empty())
+                .map(str::to_ascii_lowercase)
+                .collect::<Vec<_>>();
+            match domains.iter().find(|domain| !is_valid_domain(domain)) {
+                Some(domain) => Err(format!("«{domain}» не является доменом")),
+                None => Ok(domains.join(",")),
+            }
+        }
+    }
+}
+
+/// `None` for a typed value that does not fit its kind. Its token is left in
+/// place and reported by `check_placeholder_resolution`.
+fn resolve_placeholder(placeholder: &Placeholder, home_dir: &Path) -> Option<String> {
+    match placeholder.kind {
+        PlaceholderKind::Path => Some(resolve_placeholder_path(&placeholder.value, home_dir)),
+        kind => placeholder_value(kind, &placeholder.value).ok(),
+    }
+}
+
+/// Reserved placeholders computed at resolve time. They are never stored in
+/// the config, so they cannot be edited, shadowed or deleted.
+const DYNAMIC_PLACEHOLDERS: &[&str] = &[
+    "RESOURCES_DIR",
+    "FILTERS_DIR",
+    "LISTS_DIR",
+    "FAKE_DIR",
+    "RUNTIME_DIR",
+];
+
+pub(crate) fn is_dynamic_placeholder(name: &str) -> bool {
+    DYNAMIC_PLACEHOLDERS.contains(&name)
+}
+
+fn dynamic_placeholder_value(name: &str) -> Option<PathBuf> {
+    match name {
+        "RESOURCES_DIR" => Some(get_managed_resources_dir()),
+        "FILTERS_DIR" => Some(get_filters_dir()),
+        "LISTS_DIR" => Some(get_lists_dir()),
+        "FAKE_DIR" => Some(get_fake_dir()),
+        "RUNTIME_DIR" => Some(get_runtime_data_dir()),
+        _ => None,
+    }
+}
+
+pub(crate) fn dynamic_placeholders() -> Vec<PlaceholderStatus> {
+    DYNAMIC_PLACEHOLDERS
+        .iter()
+        .filter_map(|name| {
+            dynamic_placeholder_value(name).map(|path| PlaceholderStatus {
+                name: name.to_string(),
+                resolved: path.to_string_lossy().to_string(),
+                issue: None,
+            })
+        })
+        .collect()
+}
+
+/// Expands `%NAME%` for variables `env` knows. Anything else, including
+/// unset variables, is left as written.
+fn expand_env_vars(content: &str, env: impl Fn(&str) -> Option<String>) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find('%') {
+        result.push_str(&rest[..start]);
+        let after_open = &rest[start + 1..];
+        let value = after_open.find('%').and_then(|end| {
+            let name = &after_open[..end];
+            let is_name = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|char| char.is_ascii_alphanumeric() || matches!(char, '_' | '(' | ')'));
+            is_name
+                .then(|| env(name))
+                .flatten()
+                .map(|value| (value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                result.push_str(&value);
+                rest = &after_open[end + 1..];
+            }
+            None => {
+                result.push('%');
+                rest = after_open;
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
 
-    let mut result = content;
+pub(crate) fn apply_placeholders(content: &str, placeholders: &[Placeholder]) -> String {
+    let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
 
+    let mut result = expand_env_vars(content, |name| std::env::var(name).ok());
+    for name in DYNAMIC_PLACEHOLDERS {
+        let token = format!("{{{{{name}}}}}");
+        if result.contains(&token)
+            && let Some(path) = dynamic_placeholder_value(name)
+        {
+            result = result.replace(&token, &path.to_string_lossy());
+        }
+    }
     for placeholder in placeholders {
-        let resolved_path = if let Some(path) = resolve_managed_placeholder_path(&placeholder.path)
+        let token = format!("{{{{{}}}}}", placeholder.name);
+        if result.contains(&token)
+            && let Some(value) = resolve_placeholder(placeholder, &home_dir)
+        {
+            result = result.replace(&token, &value);
+        }
+    }
+
+    result
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PlaceholderIssueKind {
+    Unknown,
+    MissingTarget,
+    EmptyTarget,
+    InvalidValue,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedStrategy {
+    pub category_id: String,
+    pub category_name: String,
+    pub strategy_id: String,
+    pub strategy_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceholderIssue {
+    pub name: String,
+    pub kind: PlaceholderIssueKind,
+    pub resolved_path: Option<String>,
+    pub strategies: Vec<AffectedStrategy>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceholderResolutionReport {
+    pub issues: Vec<PlaceholderIssue>,
+}
+
+impl PlaceholderResolutionReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn describe(&self) -> String {
+        let mut lines =
+            vec!["Запуск отменён: стратегии ссылаются на неразрешённые плейсхолдеры".to_string()];
+        lines.extend(
+            self.issues
+                .iter()
+                .map(|issue| format!("- {}", issue.message)),
+        );
+        lines.join("\n")
+    }
+}
+
+pub(crate) fn placeholder_tokens(content: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{{") {
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            break;
+        };
+        let name = &after_open[..end];
+        if !name.is_empty()
+            && !name.contains(['{', '}'])
+            && !name.chars().any(char::is_whitespace)
+            && !tokens.contains(&name)
         {
-            path
-        } else if placeholder.path.starts_with('~') {
-            let relative = &placeholder.path[1..];
-            let relative_trimmed = relative.trim_start_matches('/').trim_start_matches('\\');
-            let mut path = home_dir.clone();
-            for part in relative_trimmed.split(['/', '\\']) {
-                if !part.is_empty() {
-                    path.push(part);
+            tokens.push(name);
+        }
+        rest = &after_open[end + 2..];
+    }
+
+    tokens
+}
+
+fn placeholder_target_issue(resolved_path: &str) -> Option<PlaceholderIssueKind> {
+    match fs::metadata(resolved_path) {
+        Ok(metadata) if !metadata.is_file() => Some(PlaceholderIssueKind::MissingTarget),
+        Ok(metadata) if metadata.len() == 0 => Some(PlaceholderIssueKind::EmptyTarget),
+        Ok(_) => None,
+        Err(_) => Some(PlaceholderIssueKind::MissingTarget),
+    }
+}
+
+/// Path placeholders must point at a non-empty file, the other kinds must
+/// hold a value of their type.
+fn placeholder_issue(
+    placeholder: &Placeholder,
+    home_dir: &Path,
+) -> Option<(PlaceholderIssueKind, Option<String>)> {
+    if placeholder.kind.is_path() {
+        let resolved_path = resolve_placeholder_path(&placeholder.value, home_dir);
+        return placeholder_target_issue(&resolved_path).map(|kind| (kind, Some(resolved_path)));
+    }
+
+    placeholder_value(placeholder.kind, &placeholder.value)
+        .err()
+        .map(|error| (PlaceholderIssueKind::InvalidValue, Some(error)))
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceholderStatus {
+    pub name: String,
+    pub resolved: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<PlaceholderIssueKind>,
+}
+
+pub(crate) fn placeholder_statuses(placeholders: &[Placeholder]) -> Vec<PlaceholderStatus> {
+    let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    placeholders
+        .iter()
+        .map(|placeholder| PlaceholderStatus {
+            name: placeholder.name.clone(),
+            resolved: resolve_placeholder(placeholder, &home_dir)
+                .unwrap_or_else(|| placeholder.value.clone()),
+            issue: placeholder_issue(placeholder, &home_dir).map(|(kind, _)| kind),
+        })
+        .collect()
+}
+
+fn describe_placeholder_issue(
+    name: &str,
+    kind: PlaceholderIssueKind,
+    resolved_path: Option<&str>,
+    strategies: &[AffectedStrategy],
+) -> String {
+    let problem = match (kind, resolved_path) {
+        (PlaceholderIssueKind::Unknown, _) | (_, None) => "плейсхолдер не определён".to_string(),
+        (PlaceholderIssueKind::MissingTarget, Some(path)) => format!("файл не найден: {path}"),
+        (PlaceholderIssueKind::EmptyTarget, Some(path)) => format!("файл пуст: {path}"),
+        (PlaceholderIssueKind::InvalidValue, Some(value)) => {
+            format!("значение не подходит под тип плейсхолдера: {value}")
+        }
+    };
+    let affected = strategies
+        .iter()
+        .map(|strategy| format!("{} / {}", strategy.category_name, strategy.strategy_name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{{{{{name}}}}}: {problem} (стратегии: {affected})")
+}
+
+pub(crate) fn check_placeholder_resolution(config: &AppConfig) -> PlaceholderResolutionReport {
+    let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    let mut resolved_names = HashSet::new();
+    let mut issues: Vec<PlaceholderIssue> = Vec::new();
+
+    for category in &config.categories {
+        for strategy in category
+            .strategies
+            .iter()
+            .filter(|strategy| strategy.active)
+        {
+            let content = strategy
+                .content
+                .replace(LIST_MODE_TOKEN, config.list_mode.strategy_arg());
+            for name in placeholder_tokens(&content) {
+                if resolved_names.contains(name) || is_dynamic_placeholder(name) {
+                    continue;
                 }
+
+                let affected = AffectedStrategy {
+                    category_id: category.id.clone(),
+                    category_name: category.name.clone(),
+                    strategy_id: strategy.id.clone(),
+                    strategy_name: strategy.name.clone(),
+                };
+                if let Some(issue) = issues.iter_mut().find(|issue| issue.name == name) {
+                    issue.strategies.push(affected);
+                    continue;
+                }
+
+                let placeholder = config
+                    .placeholders
+                    .iter()
+                    .find(|placeholder| placeholder.name == name);
+                let (kind, resolved_path) = match placeholder {
+                    Some(placeholder) => match placeholder_issue(placeholder, &home_dir) {
+                        Some(issue) => issue,
+                        None => {
+                            resolved_names.insert(name.to_string());
+                            continue;
+                        }
+                    },
+                    None => (PlaceholderIssueKind::Unknown, None),
+                };
+                issues.push(PlaceholderIssue {
+                    name: name.to_string(),
+                    kind,
+                    resolved_path,
+                    strategies: vec![affected],
+                    message: String::new(),
+                });
             }
-            path.to_string_lossy().to_string()
+        }
+    }
+
+    for issue in &mut issues {
+        issue.message = describe_placeholder_issue(
+            &issue.name,
+            issue.kind,
+            issue.resolved_path.as_deref(),
+            &issue.strategies,
+        );
+    }
+
+    PlaceholderResolutionReport { issues }
+}
+
+fn strategy_content_args(
+    content: &str,
+    list_mode: ListMode,
+    placeholders: &[Placeholder],
+) -> Vec<String> {
+    let content = content.replace(LIST_MODE_TOKEN, list_mode.strategy_arg());
+    apply_placeholders(&content, placeholders)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub(crate) fn build_filter_args(filters: &[Filter], filters_dir: &Path) -> Vec<String> {
+    filters
+        .iter()
+        .filter(|filter| filter.active)
+        .map(|filter| {
+            format!(
+                "--wf-raw-part=@{}",
+                filters_dir.join(&filter.filename).to_string_lossy()
+            )
+        })
+        .collect()
+}
+
+/// Arguments of every active strategy that yields a winws profile, in the
+/// order they are joined with `--new` on the command line.
+pub(crate) fn strategy_segments(config: &AppConfig) -> Vec<(&Category, &Strategy, Vec<String>)> {
+    config
+        .categories
+        .iter()
+        .flat_map(|category| {
+            category
+                .strategies
+                .iter()
+                .filter(|strategy| strategy.active)
+                .map(move |strategy| (category, strategy))
+        })
+        .filter_map(|(category, strategy)| {
+            let args =
+                strategy_content_args(&strategy.content, config.list_mode, &config.placeholders);
+            (!args.is_empty()).then_some((category, strategy, args))
+        })
+        .collect()
+}
+
+pub(crate) fn build_strategy_args(config: &AppConfig) -> Vec<String> {
+    let mut args = Vec::new();
+
+    for (_, _, mut strategy_args) in strategy_segments(config) {
+        if !args.is_empty() {
+            args.push(PROFILE_SEPARATOR_ARG.to_string());
+        }
+        args.append(&mut strategy_args);
+    }
+
+    args
+}
+
+/// `--wf-tcp`/`--wf-udp` for the effective port sets. A protocol no active
+/// profile needs in auto mode is left out instead of passing an empty set.
+pub(crate) fn global_port_args(config: &AppConfig) -> Vec<String> {
+    let ports = effective_global_ports(config);
+    [("tcp", ports.tcp), ("udp", ports.udp)]
+        .into_iter()
+        .filter(|(_, ports)| !ports.trim().is_empty())
+        .map(|(protocol, ports)| format!("--wf-{protocol}={ports}"))
+        .collect()
+}
+
+/// Everything before the first profile: debug output, the WinDivert port
+/// sets and raw filters.
+fn build_winws_base_args_with_filters_dir(config: &AppConfig, filters_dir: &Path) -> Vec<String> {
+    let mut args = Vec::new();
+    if config.winws_debug {
+        args.push("--debug=1".to_string());
+    }
+    args.extend(global_port_args(config));
+    args.extend(build_filter_args(&config.filters, filters_dir));
+    args
+}
+
+fn build_winws_args_with_filters_dir(config: &AppConfig, filters_dir: &Path) -> Vec<String> {
+    let mut args = build_winws_base_args_with_filters_dir(config, filters_dir);
+    args.extend(build_strategy_args(config));
+    args
+}
+
+pub(crate) fn build_winws_base_args(config: &AppConfig) -> Vec<String> {
+    build_winws_base_args_with_filters_dir(config, &get_filters_dir())
+}
+
+pub fn build_winws_args(config: &AppConfig) -> Vec<String> {
+    build_winws_args_with_filters_dir(config, &get_filters_dir())
+}
+
+/// Instance groups that get their own winws.exe: the default group first,
+/// then the others in category order. A group without active profiles is
+/// left out, except the default one while nothing else runs or raw filters
+/// are active.
+pub(crate) fn instance_groups(config: &AppConfig) -> Vec<String> {
+    let mut groups: Vec<String> = Vec::new();
+    for (category, _, _) in strategy_segments(config) {
+        let group = category.instance_group();
+        if !groups.iter().any(|known| known == group) {
+            groups.push(group.to_string());
+        }
+    }
+
+    let has_filters = config.filters.iter().any(|filter| filter.active);
+    if let Some(index) = groups
+        .iter()
+        .position(|group| group == DEFAULT_INSTANCE_GROUP)
+    {
+        let group = groups.remove(index);
+        groups.insert(0, group);
+    } else if groups.is_empty() || has_filters {
+        groups.insert(0, DEFAULT_INSTANCE_GROUP.to_string());
+    }
+    groups
+}
+
+fn group_config(config: &AppConfig, group: &str) -> AppConfig {
+    let mut instance = config.clone();
+    instance
+        .categories
+        .retain(|category| category.instance_group() == group);
+    if group != DEFAULT_INSTANCE_GROUP {
+        instance.filters.clear();
+    }
+    instance
+}
+
+/// Ports the profiles of a group can match, limited to the manual list when
+/// ports are not derived.
+fn group_port_ranges(instance: &AppConfig) -> (Vec<RangeInclusive<u16>>, Vec<RangeInclusive<u16>>) {
+    let parse = |value: &str| {
+        if value.trim().is_empty() {
+            Vec::new()
         } else {
-            placeholder.path.clone()
+            parse_port_list(value).unwrap_or_default()
+        }
+    };
+    let derived = derive_global_ports(instance);
+    let (tcp, udp) = (parse(&derived.tcp), parse(&derived.udp));
+    if instance.global_ports.auto {
+        return (tcp, udp);
+    }
+
+    let within = |ranges: &[RangeInclusive<u16>], allowed: &str| {
+        subtract_ranges(ranges, &subtract_ranges(ranges, &parse(allowed)))
+    };
+    (
+        within(&tcp, &instance.global_ports.tcp),
+        within(&udp, &instance.global_ports.udp),
+    )
+}
+
+/// The part of `config` one winws.exe runs: the categories of `group` and,
+/// for the default group, the raw filters. When several instances run, each
+/// intercepts only the ports its own profiles can match and that no earlier
+/// group intercepts already, so no packet is diverted to two winws.exe.
+pub(crate) fn instance_config(config: &AppConfig, group: &str) -> AppConfig {
+    let mut instance = group_config(config, group);
+    let groups = instance_groups(config);
+    if groups.len() < 2 {
+        return instance;
+    }
+
+    let mut claimed_tcp = Vec::new();
+    let mut claimed_udp = Vec::new();
+    for other in groups {
+        let (tcp, udp) = group_port_ranges(&group_config(config, &other));
+        let tcp = subtract_ranges(&tcp, &claimed_tcp);
+        let udp = subtract_ranges(&udp, &claimed_udp);
+        if other == group {
+            instance.global_ports = GlobalPorts {
+                tcp: format_ranges(&tcp),
+                udp: format_ranges(&udp),
+                auto: false,
+            };
+            break;
+        }
+        claimed_tcp.extend(tcp);
+        claimed_udp.extend(udp);
+    }
+    instance
+}
+
+/// Argument vectors of every winws.exe the config needs, by instance group.
+pub(crate) fn build_instance_args(config: &AppConfig) -> Vec<(String, Vec<String>)> {
+    instance_groups(config)
+        .into_iter()
+        .map(|group| {
+            let args = build_winws_args(&instance_config(config, &group));
+            (group, args)
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_placeholder_report(
+    state: tauri::State<'_, AppState>,
+) -> Result<PlaceholderResolutionReport, String> {
+    Ok(check_placeholder_resolution(&current_config(&state)?))
+}
+
+/// Checks every placeholder, referenced or not, so the editor can flag
+/// missing files and malformed values before a launch does.
+#[tauri::command]
+pub fn get_placeholder_statuses(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<PlaceholderStatus>, String> {
+    Ok(placeholder_statuses(&current_config(&state)?.placeholders))
+}
+
+#[tauri::command]
+pub fn get_dynamic_placeholders() -> Vec<PlaceholderStatus> {
+    dynamic_placeholders()
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WinwsInstanceArgs {
+    pub instance_group: String,
+    pub args: Vec<String>,
+}
+
+#[tauri::command]
+pub fn get_winws_args(state: tauri::State<'_, AppState>) -> Result<Vec<WinwsInstanceArgs>, String> {
+    Ok(build_instance_args(&current_config(&state)?)
+        .into_iter()
+        .map(|(instance_group, args)| WinwsInstanceArgs {
+            instance_group,
+            args,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strategy(id: &str, content: &str, active: bool) -> Strategy {
+        Strategy {
+            id: id.to_string(),
+            name: id.to_string(),
+            content: content.to_string(),
+            active,
+            metadata: StrategyMetadata::default(),
+            system: false,
+            system_base_name: None,
+            system_base_content: None,
+            system_base_metadata: None,
+        }
+    }
+
+    fn category(id: &str, strategies: Vec<Strategy>) -> Category {
+        Category {
+            id: id.to_string(),
+            name: id.to_string(),
+            strategies,
+            system: false,
+            system_base_name: None,
+            source_id: None,
+            instance_group: None,
+        }
+    }
+
+    fn placeholder(name: &str, path: &str) -> Placeholder {
+        Placeholder {
+            name: name.to_string(),
+            value: path.to_string(),
+            kind: PlaceholderKind::Path,
+            system: false,
+            system_base_name: None,
+            system_base_path: None,
+            source_id: None,
+        }
+    }
+
+    fn filter(filename: &str, active: bool) -> Filter {
+        Filter {
+            id: filename.to_string(),
+            name: filename.to_string(),
+            filename: filename.to_string(),
+            active,
+            content: String::new(),
+            system: false,
+            system_base_name: None,
+            system_base_filename: None,
+            system_base_content: None,
+            system_base_active: None,
+        }
+    }
+
+    fn test_config(categories: Vec<Category>) -> AppConfig {
+        AppConfig {
+            categories,
+            placeholders: vec![
+                placeholder("IP_USER", "/lists/ip.txt"),
+                placeholder("HOSTS_USER_EXCLUDE", "/lists/exclude.txt"),
+            ],
+            filters: Vec::new(),
+            list_mode: ListMode::Ipset,
+            ..AppConfig::default()
+        }
+    }
+
+    #[test]
+    fn strategy_args_substitute_list_mode_before_placeholders() {
+        let mut config = test_config(vec![category(
+            "http",
+            vec![strategy("v1", "--filter-l7=http\n<LIST_MODE>", true)],
+        )]);
+
+        assert_eq!(
+            build_strategy_args(&config),
+            vec!["--filter-l7=http", "--ipset=/lists/ip.txt"]
+        );
+
+        config.list_mode = ListMode::Exclude;
+        assert_eq!(
+            build_strategy_args(&config),
+            vec!["--filter-l7=http", "--hostlist-exclude=/lists/exclude.txt"]
+        );
+    }
+
+    #[test]
+    fn strategy_args_join_active_strategies_with_new() {
+        let config = test_config(vec![
+            category(
+                "tls",
+                vec![
+                    strategy("v1", "--filter-l7=tls\n--dpi-desync=fake", true),
+                    strategy("v2", "--filter-l7=tls\n--dpi-desync=split2", false),
+                ],
+            ),
+            category(
+                "quic",
+                vec![
+                    strategy("empty", " \n\n", true),
+                    strategy("v1", "--filter-l7=quic\r\n\n--dpi-desync=fake\n", true),
+                ],
+            ),
+        ]);
+
+        assert_eq!(
+            build_strategy_args(&config),
+            vec![
+                "--filter-l7=tls",
+                "--dpi-desync=fake",
+                "--new",
+                "--filter-l7=quic",
+                "--dpi-desync=fake",
+            ]
+        );
+    }
+
+    #[test]
+    fn strategy_args_resolve_managed_placeholders() {
+        let mut config = test_config(vec![category(
+            "quic",
+            vec![strategy(
+                "v1",
+                "--dpi-desync-fake-quic={{QUIC}}\n--hostlist={{UNKNOWN}}",
+                true,
+            )],
+        )]);
+        config
+            .placeholders
+            .push(placeholder("QUIC", "@resources/fake/quic.bin"));
+
+        let expected_path = get_managed_resources_dir().join("fake").join("quic.bin");
+        assert_eq!(
+            build_strategy_args(&config),
+            vec![
+                format!("--dpi-desync-fake-quic={}", expected_path.to_string_lossy()),
+                "--hostlist={{UNKNOWN}}".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn winws_args_start_with_ports_and_active_filters() {
+        let mut config = test_config(vec![category(
+            "http",
+            vec![strategy("v1", "--filter-l7=http", true)],
+        )]);
+        config.global_ports = GlobalPorts {
+            tcp: "80,443".to_string(),
+            udp: "443".to_string(),
+            auto: false,
         };
+        config.filters = vec![filter("a.txt", true), filter("b.txt", false)];
+        let filters_dir = PathBuf::from("filters");
+
+        assert_eq!(
+            build_winws_args_with_filters_dir(&config, &filters_dir),
+            vec![
+                "--wf-tcp=80,443".to_string(),
+                "--wf-udp=443".to_string(),
+                format!(
+                    "--wf-raw-part=@{}",
+                    filters_dir.join("a.txt").to_string_lossy()
+                ),
+                "--filter-l7=http".to_string(),
+            ]
+        );
+    }
 
-        let token = format!("{{{{{}}}}}", placeholder.name);
-        result = result.replace(&token, &resolved_path);
+    #[test]
+    fn instance_groups_get_their_own_profiles_and_ports() {
+        let mut discord = category(
+            "discord",
+            vec![strategy(
+                "quic",
+                "--filter-udp=443\n--dpi-desync=fake",
+                true,
+            )],
+        );
+        discord.instance_group = Some(" UDP ".to_string());
+        let mut youtube = category(
+            "youtube",
+            vec![strategy(
+                "tls",
+                "--filter-tcp=443\n--dpi-desync=split",
+                true,
+            )],
+        );
+        youtube.instance_group = Some("main".to_string());
+        let mut config = test_config(vec![discord, youtube]);
+        assert!(normalize_instance_groups(&mut config.categories));
+        assert_eq!(config.categories[0].instance_group.as_deref(), Some("udp"));
+        assert_eq!(config.categories[1].instance_group, None);
+
+        config.global_ports = GlobalPorts {
+            tcp: "80,443".to_string(),
+            udp: "443".to_string(),
+            auto: false,
+        };
+        config.filters = vec![filter("a.txt", true)];
+        let filters_dir = PathBuf::from("filters");
+
+        assert_eq!(instance_groups(&config), vec!["main", "udp"]);
+        assert_eq!(
+            build_winws_args_with_filters_dir(&instance_config(&config, "main"), &filters_dir),
+            vec![
+                "--wf-tcp=443".to_string(),
+                format!(
+                    "--wf-raw-part=@{}",
+                    filters_dir.join("a.txt").to_string_lossy()
+                ),
+                "--filter-tcp=443".to_string(),
+                "--dpi-desync=split".to_string(),
+            ]
+        );
+        assert_eq!(
+            build_winws_args_with_filters_dir(&instance_config(&config, "udp"), &filters_dir),
+            vec![
+                "--wf-udp=443".to_string(),
+                "--filter-udp=443".to_string(),
+                "--dpi-desync=fake".to_string(),
+            ]
+        );
     }
 
-    result
+    #[test]
+    fn instance_groups_do_not_intercept_the_same_ports() {
+        let youtube = category(
+            "youtube",
+            vec![strategy(
+                "tls",
+                "--filter-tcp=80,443\n--dpi-desync=split",
+                true,
+            )],
+        );
+        let mut games = category(
+            "games",
+            vec![strategy(
+                "tcp",
+                "--filter-tcp=443,50000-50100\n--dpi-desync=fake",
+                true,
+            )],
+        );
+        games.instance_group = Some("games".to_string());
+        let mut config = test_config(vec![youtube, games]);
+        let wf_args =
+            |config: &AppConfig, group: &str| global_port_args(&instance_config(config, group));
+
+        config.global_ports.auto = true;
+        assert_eq!(wf_args(&config, "main"), vec!["--wf-tcp=80,443"]);
+        assert_eq!(wf_args(&config, "games"), vec!["--wf-tcp=50000-50100"]);
+
+        config.global_ports = GlobalPorts {
+            tcp: "443,50000-65535".to_string(),
+            udp: String::new(),
+            auto: false,
+        };
+        assert_eq!(wf_args(&config, "main"), vec!["--wf-tcp=443"]);
+        assert_eq!(wf_args(&config, "games"), vec!["--wf-tcp=50000-50100"]);
+    }
+
+    #[test]
+    fn placeholder_report_lists_unknown_missing_and_empty_targets() {
+        let dir = std::env::temp_dir().join(format!("zapret-placeholders-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+        fs::write(path("ip.txt"), "1.1.1.1\n").unwrap();
+        fs::write(path("empty.bin"), "").unwrap();
+
+        let mut config = test_config(vec![
+            category(
+                "tls",
+                vec![
+                    strategy("v1", "<LIST_MODE>\n--dpi-desync-fake-tls={{EMPTY}}", true),
+                    strategy("v2", "--hostlist={{UNKNOWN}}", false),
+                ],
+            ),
+            category(
+                "quic",
+                vec![strategy(
+                    "v1",
+                    "--dpi-desync-fake-quic={{MISSING}}\n--hostlist={{UNKNOWN}}\n--ipset={{EMPTY}}",
+                    true,
+                )],
+            ),
+        ]);
+        config.placeholders = vec![
+            placeholder("IP_USER", &path("ip.txt")),
+            placeholder("EMPTY", &path("empty.bin")),
+            placeholder("MISSING", &path("missing.bin")),
+        ];
+
+        let report = check_placeholder_resolution(&config);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let summary = report
+            .issues
+            .iter()
+            .map(|issue| {
+                let strategies = issue
+                    .strategies
+                    .iter()
+                    .map(|strategy| strategy.category_id.as_str())
+                    .collect::<Vec<_>>();
+                (issue.name.as_str(), issue.kind, strategies)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "EMPTY",
+                    PlaceholderIssueKind::EmptyTarget,
+                    vec!["tls", "quic"]
+                ),
+                ("MISSING", PlaceholderIssueKind::MissingTarget, vec!["quic"]),
+                ("UNKNOWN", PlaceholderIssueKind::Unknown, vec!["quic"]),
+            ]
+        );
+        assert!(
+            report
+                .describe()
+                .contains("{{UNKNOWN}}: плейсхолдер не определён")
+        );
+    }
+
+    #[test]
+    fn typed_placeholders_are_validated_and_substituted() {
+        let typed = |name: &str, kind: PlaceholderKind, value: &str| Placeholder {
+            kind,
+            ..placeholder(name, value)
+        };
+        let mut config = test_config(vec![category(
+            "tls",
+            vec![strategy(
+                "v1",
+                "--dpi-desync-ttl={{TTL}}\n--dpi-desync-fake-tls={{BLOB}}\n--hostlist-domains={{SITES}}\n--dpi-desync-split-pos={{POS}}",
+                true,
+            )],
+        )]);
+        config.placeholders = vec![
+            typed("TTL", PlaceholderKind::Integer, " 5 "),
+            typed("BLOB", PlaceholderKind::Hex, "0x1603"),
+            typed(
+                "SITES",
+                PlaceholderKind::DomainList,
+                "Example.com, *.discord.gg",
+            ),
+            typed("POS", PlaceholderKind::Integer, "1,midsld"),
+        ];
+
+        let report = check_placeholder_resolution(&config);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].name, "POS");
+        assert_eq!(report.issues[0].kind, PlaceholderIssueKind::InvalidValue);
+        assert!(
+            report
+                .describe()
+                .contains("«1,midsld» не является целым числом")
+        );
+        // The invalid value is never passed to winws as is.
+        assert!(
+            build_strategy_args(&config).contains(&"--dpi-desync-split-pos={{POS}}".to_string())
+        );
+
+        config.placeholders[3] = typed("POS", PlaceholderKind::String, "1,midsld");
+        assert!(check_placeholder_resolution(&config).is_clean());
+        assert_eq!(
+            build_strategy_args(&config),
+            vec![
+                "--dpi-desync-ttl=5",
+                "--dpi-desync-fake-tls=0x1603",
+                "--hostlist-domains=example.com,*.discord.gg",
+                "--dpi-desync-split-pos=1,midsld",
+            ]
+        );
+        assert!(placeholder_value(PlaceholderKind::Hex, "abc").is_err());
+        assert!(placeholder_value(PlaceholderKind::DomainList, "bad_domain").is_err());
+    }
+
+    #[test]
+    fn dynamic_placeholders_and_env_vars_resolve_without_config_entries() {
+        let mut config = test_config(vec![category(
+            "custom",
+            vec![strategy(
+                "v1",
+                "--hostlist={{LISTS_DIR}}/my.txt\n--dpi-desync-fooling=%UNSET_ZAPRET_VAR%",
+                true,
+            )],
+        )]);
+        // A user entry with a reserved name must not take over the builtin value.
+        config
+            .placeholders
+            .push(placeholder("LISTS_DIR", "C:/elsewhere"));
+
+        assert!(check_placeholder_resolution(&config).is_clean());
+        assert_eq!(
+            build_strategy_args(&config),
+            vec![
+                format!("--hostlist={}/my.txt", get_lists_dir().to_string_lossy()),
+                "--dpi-desync-fooling=%UNSET_ZAPRET_VAR%".to_string(),
+            ]
+        );
+
+        let env = std::collections::BTreeMap::from([("LISTS", "D:\\lists")]);
+        assert_eq!(
+            expand_env_vars("%LISTS%\\extra.txt 100% %UNSET% %%", |name| env
+                .get(name)
+                .map(|value| value.to_string())),
+            "D:\\lists\\extra.txt 100% %UNSET% %%"
+        );
+        assert!(
+            dynamic_placeholders()
+                .iter()
+                .any(|placeholder| placeholder.name == "RUNTIME_DIR")
+        );
+    }
+
+    #[test]
+    fn migration_v0_rewrites_legacy_managed_path_alias() {
+        let mut value = serde_json::json!({
+            "placeholders": [
+                { "name": "A", "path": "@thirdparty\\fake\\a.bin", "systemBasePath": "@thirdparty/fake/a.bin" },
+                { "name": "B", "path": "@thirdparty" },
+                { "name": "C", "path": "C:/lists/c.txt" },
+            ]
+        });
+
+        migrate_legacy_managed_path_alias(&mut value);
+
+        assert_eq!(value["placeholders"][0]["path"], "@resources/fake/a.bin");
+        assert_eq!(
+            value["placeholders"][0]["systemBasePath"],
+            "@resources/fake/a.bin"
+        );
+        assert_eq!(value["placeholders"][1]["path"], "@resources");
+        assert_eq!(value["placeholders"][2]["path"], "C:/lists/c.txt");
+    }
+
+    #[test]
+    fn migration_v1_renames_legacy_system_strategy_names() {
+        let builtin = AppConfig::default();
+        let builtin_category = &builtin.categories[0];
+        let builtin_strategy = &builtin_category.strategies[0];
+        let legacy_name = format!("{} {}", builtin_category.name, builtin_strategy.name);
+        let mut value = serde_json::json!({
+            "categories": [{
+                "id": builtin_category.id,
+                "strategies": [
+                    { "id": builtin_strategy.id, "name": legacy_name, "content": builtin_strategy.content },
+                    { "id": builtin_strategy.id, "name": legacy_name, "content": "--edited" },
+                ]
+            }]
+        });
+
+        migrate_legacy_system_strategy_names(&mut value);
+
+        let strategies = &value["categories"][0]["strategies"];
+        assert_eq!(strategies[0]["name"], builtin_strategy.name.as_str());
+        assert_eq!(strategies[1]["name"], legacy_name.as_str());
+    }
+
+    #[test]
+    fn migration_chain_runs_from_stored_version() {
+        let mut legacy =
+            serde_json::json!({ "placeholders": [{ "name": "A", "path": "@thirdparty/a.bin" }] });
+        assert_eq!(migrate_config_value(&mut legacy), Ok(0));
+        assert_eq!(legacy["placeholders"][0]["path"], "@resources/a.bin");
+        assert_eq!(stored_schema_version(&legacy), CONFIG_SCHEMA_VERSION);
+
+        let mut current = serde_json::json!({
+            "schemaVersion": CONFIG_SCHEMA_VERSION,
+            "placeholders": [{ "name": "A", "path": "@thirdparty/a.bin" }]
+        });
+        assert_eq!(
+            migrate_config_value(&mut current),
+            Ok(CONFIG_SCHEMA_VERSION)
+        );
+        assert_eq!(current["placeholders"][0]["path"], "@thirdparty/a.bin");
+
+        assert!(migrate_config_value(&mut serde_json::json!([])).is_err());
+    }
+
+    #[test]
+    fn invalid_config_reports_the_failing_field_path() {
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        assert!(validate_config_value(&value).is_ok());
+        assert_eq!(
+            validate_config_value(&serde_json::from_str(DEFAULT_CONFIG).unwrap()),
+            Ok(())
+        );
+        value["categories"][0]["strategies"][1]["content"] = Value::from(42);
+        let error = deserialize_config(value.clone()).err().unwrap();
+        assert!(
+            error.starts_with("categories[0].strategies[1].content: invalid type"),
+            "{error}"
+        );
+
+        value["globalPortz"] = Value::from("443");
+        value["placeholders"][0]["kind"] = Value::from(7);
+        let error = parse_stored_config(&value.to_string()).err().unwrap();
+        let violations: Vec<&str> = error.split("; ").collect();
+        assert!(
+            violations.iter().any(
+                |violation| violation.starts_with(".: ") && violation.contains("'globalPortz'")
+            ),
+            "{error}"
+        );
+        assert!(
+            violations
+                .iter()
+                .any(|violation| violation.starts_with("categories[0].strategies[1].content: ")),
+            "{error}"
+        );
+        assert!(
+            violations
+                .iter()
+                .any(|violation| violation.starts_with("placeholders[0].kind: ")),
+            "{error}"
+        );
+
+        let schema = config_json_schema();
+        assert!(schema["properties"]["categories"].is_object());
+        assert!(
+            schema["required"]
+                .as_array()
+                .unwrap()
+                .contains(&Value::from("global_ports"))
+        );
+    }
+
+    #[test]
+    fn refuses_to_overwrite_config_from_newer_schema() {
+        let path = std::env::temp_dir().join(format!("zapret-config-{}.json", Uuid::new_v4()));
+        assert!(ensure_config_not_newer(&path).is_ok());
+
+        fs::write(
+            &path,
+            format!(r#"{{"schemaVersion": {CONFIG_SCHEMA_VERSION}}}"#),
+        )
+        .unwrap();
+        assert!(ensure_config_not_newer(&path).is_ok());
+
+        fs::write(
+            &path,
+            format!(r#"{{"schemaVersion": {}}}"#, CONFIG_SCHEMA_VERSION + 1),
+        )
+        .unwrap();
+        let result = ensure_config_not_newer(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn global_ports_are_canonicalized_and_invalid_lists_reported() {
+        let mut ports = GlobalPorts {
+            tcp: "443, 80,80-90".to_string(),
+            udp: "443,70000".to_string(),
+            auto: false,
+        };
+        assert!(validate_global_ports(&ports).is_err());
+        assert!(normalize_global_ports(&mut ports));
+        assert_eq!(ports.tcp, "80-90,443");
+        assert_eq!(ports.udp, "443,70000");
+        assert!(!normalize_global_ports(&mut ports));
+
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        value["global_ports"] = serde_json::to_value(&ports).unwrap();
+        let error = parse_stored_config(&value.to_string()).err().unwrap();
+        assert!(
+            error.starts_with("global_ports: некорректные UDP-порты: "),
+            "{error}"
+        );
+
+        ports.udp = "443".to_string();
+        assert!(validate_global_ports(&ports).is_ok());
+    }
+
+    #[test]
+    fn builtin_sync_fills_metadata_and_keeps_user_edits() {
+        let mut builtin = strategy("yt", "--dpi-desync=fake", false);
+        builtin.metadata = StrategyMetadata {
+            description: Some("fake".to_string()),
+            tags: vec!["fake".to_string()],
+            targets: vec!["YouTube".to_string()],
+            author: None,
+            last_verified: Some("2025-06-01".to_string()),
+        };
+
+        let mut legacy = strategy("yt", "--dpi-desync=fake", true);
+        legacy.system_base_content = Some(legacy.content.clone());
+        assert!(sync_builtin_strategy(&mut legacy, &builtin));
+        assert_eq!(legacy.metadata, builtin.metadata);
+        assert!(!is_system_strategy_modified(&legacy));
+
+        legacy.metadata.description = Some("works on my ISP".to_string());
+        builtin.content = "--dpi-desync=fake\n--dpi-desync-repeats=6".to_string();
+        builtin.metadata.description = Some("fake, 6 repeats".to_string());
+        builtin.metadata.last_verified = Some("2025-07-01".to_string());
+        assert!(sync_builtin_strategy(&mut legacy, &builtin));
+        assert_eq!(legacy.content, builtin.content);
+        assert_eq!(
+            legacy.metadata.description.as_deref(),
+            Some("works on my ISP")
+        );
+        assert_eq!(legacy.metadata.last_verified.as_deref(), Some("2025-07-01"));
+        assert!(is_system_strategy_modified(&legacy));
+    }
+
+    #[test]
+    fn builtin_sync_keeps_user_content_on_conflict() {
+        let base = "--dpi-desync=fake\n--dpi-desync-repeats=6";
+        let mut builtin = strategy("yt", "--dpi-desync=fake\n--dpi-desync-repeats=8", false);
+        let mut user = strategy("yt", "--dpi-desync=fake\n--dpi-desync-repeats=11", true);
+        user.system_base_content = Some(base.to_string());
+        user.system = true;
+        user.system_base_name = Some(user.name.clone());
+        user.system_base_metadata = Some(StrategyMetadata::default());
+
+        assert!(!sync_builtin_strategy(&mut user, &builtin));
+        assert_eq!(user.content, "--dpi-desync=fake\n--dpi-desync-repeats=11");
+        assert_eq!(user.system_base_content.as_deref(), Some(base));
+
+        let mut config = AppConfig::default();
+        config.categories[0].strategies = vec![user.clone()];
+        let mut builtin_config = config.clone();
+        builtin_config.categories[0].strategies = vec![builtin.clone()];
+        let reports = builtin_merge_conflicts(&config, &builtin_config);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].strategy_id, "yt");
+        assert_eq!(reports[0].conflicts.len(), 1);
+
+        builtin.content = "--dpi-desync=fake,multisplit\n--dpi-desync-repeats=6".to_string();
+        assert!(sync_builtin_strategy(&mut user, &builtin));
+        assert_eq!(
+            user.content,
+            "--dpi-desync=fake,multisplit\n--dpi-desync-repeats=11"
+        );
+        assert_eq!(
+            user.system_base_content.as_deref(),
+            Some(builtin.content.as_str())
+        );
+    }
 }
@@ -0,0 +1,449 @@
+use super::config::{AppState, apply_placeholders, current_config};
+use super::strategy::{ProfileSource, active_profiles, parse_strategy};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+
+/// Counters by instance group; profile numbers only mean something within
+/// one winws.exe.
+static DEBUG_COUNTERS: Mutex<BTreeMap<String, DebugCounters>> = Mutex::new(BTreeMap::new());
+
+/// Hosts kept per instance group. A long debug session sees every host the
+/// machine talks to, so the least recently seen ones are dropped.
+const MAX_DEBUG_HOSTS: usize = 512;
+
+/// One meaningful line of the winws `--debug` stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DebugEvent {
+    /// `desync profile search for tcp ip=… port=… l7proto=tls … hostname='…'`
+    ProfileSearch { host: String, l7: Option<String> },
+    /// `desync profile N (name) matches` or `using cached desync profile N`.
+    /// Profile 0 is the winws default profile that does nothing.
+    ProfileMatched { profile: usize },
+    /// `hostlist check for HOST : positive|negative`
+    HostlistCheck { host: String, positive: bool },
+    /// `sending fake …`, `sending multisplit …` and the like.
+    Desync { action: String },
+}
+
+fn key_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.find(&format!(" {key}="))? + key.len() + 2;
+    let rest = &line[start..];
+    if let Some(quoted) = rest.strip_prefix('\'') {
+        return quoted.split('\'').next();
+    }
+    rest.split_whitespace().next()
+}
+
+fn profile_number(rest: &str) -> Option<usize> {
+    rest.split_whitespace().next()?.parse().ok()
+}
+
+pub(crate) fn parse_debug_line(line: &str) -> Option<DebugEvent> {
+    let line = line.trim();
+
+    if line.starts_with("desync profile search for ") {
+        let host = key_value(line, "hostname")
+            .filter(|host| !host.is_empty())
+            .or_else(|| key_value(line, "ip"))?
+            .to_lowercase();
+        let l7 = key_value(line, "l7proto")
+            .filter(|l7| !l7.is_empty() && *l7 != "unknown")
+            .map(str::to_string);
+        return Some(DebugEvent::ProfileSearch { host, l7 });
+    }
+    if let Some(rest) = line.strip_prefix("using cached desync profile ") {
+        return profile_number(rest).map(|profile| DebugEvent::ProfileMatched { profile });
+    }
+    if let Some(rest) = line.strip_prefix("desync profile ") {
+        if line.ends_with(" matches") {
+            return profile_number(rest).map(|profile| DebugEvent::ProfileMatched { profile });
+        }
+        return None;
+    }
+    if let Some(rest) = line.strip_prefix("hostlist check for ") {
+        let (host, verdict) = rest.rsplit_once(" : ")?;
+        let positive = match verdict.trim() {
+            "positive" => true,
+            "negative" => false,
+            _ => return None,
+        };
+        return Some(DebugEvent::HostlistCheck {
+            host: host.trim().to_lowercase(),
+            positive,
+        });
+    }
+    if let Some(rest) = line.strip_prefix("sending ") {
+        let action = rest
+            .split(|char: char| !char.is_ascii_alphanumeric() && char != '_' && char != '-')
+            .next()
+            .filter(|action| !action.is_empty())?;
+        return Some(DebugEvent::Desync {
+            action: action.to_string(),
+        });
+    }
+
+    None
+}
+
+#[derive(Debug, Default)]
+struct ProfileCounters {
+    matches: u64,
+    actions: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Default)]
+struct HostCounters {
+    searches: u64,
+    l7: BTreeSet<String>,
+    profiles: BTreeMap<usize, u64>,
+    hostlist_positive: u64,
+    hostlist_negative: u64,
+    /// Value of `DebugCounters::events` when the host was last seen.
+    last_seen: u64,
+}
+
+/// Per-profile and per-host counters of one winws.exe run. winws prints a flow's
+/// profile search, match and desync actions one after another, so the last
+/// search and match give the context for the lines that follow.
+#[derive(Debug)]
+struct DebugCounters {
+    events: u64,
+    profiles: BTreeMap<usize, ProfileCounters>,
+    hosts: BTreeMap<String, HostCounters>,
+    current_host: Option<String>,
+    current_profile: Option<usize>,
+}
+
+impl DebugCounters {
+    const fn new() -> Self {
+        Self {
+            events: 0,
+            profiles: BTreeMap::new(),
+            hosts: BTreeMap::new(),
+            current_host: None,
+            current_profile: None,
+        }
+    }
+
+    fn host_counters(&mut self, host: &str) -> &mut HostCounters {
+        if !self.hosts.contains_key(host) && self.hosts.len() >= MAX_DEBUG_HOSTS {
+            let oldest = self
+                .hosts
+                .iter()
+                .min_by_key(|(_, counters)| counters.last_seen)
+                .map(|(host, _)| host.clone());
+            if let Some(oldest) = oldest {
+                self.hosts.remove(&oldest);
+            }
+        }
+        let counters = self.hosts.entry(host.to_string()).or_default();
+        counters.last_seen = self.events;
+        counters
+    }
+
+    fn record(&mut self, event: DebugEvent) {
+        self.events += 1;
+        match event {
+            DebugEvent::ProfileSearch { host, l7 } => {
+                let counters = self.host_counters(&host);
+                counters.searches += 1;
+                if let Some(l7) = l7 {
+                    counters.l7.insert(l7);
+                }
+                self.current_host = Some(host);
+                self.current_profile = None;
+            }
+            DebugEvent::ProfileMatched { profile } => {
+                self.profiles.entry(profile).or_default().matches += 1;
+                if let Some(counters) = self
+                    .current_host
+                    .as_ref()
+                    .and_then(|host| self.hosts.get_mut(host))
+                {
+                    *counters.profiles.entry(profile).or_default() += 1;
+                }
+                self.current_profile = Some(profile);
+            }
+            DebugEvent::HostlistCheck { host, positive } => {
+                let counters = self.host_counters(&host);
+                if positive {
+                    counters.hostlist_positive += 1;
+                } else {
+                    counters.hostlist_negative += 1;
+                }
+            }
+            DebugEvent::Desync { action } => {
+                if let Some(profile) = self.current_profile {
+                    let counters = self.profiles.entry(profile).or_default();
+                    *counters.actions.entry(action).or_default() += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Feeds one line of winws output into the counters. Returns `false` for
+/// lines that are not debug events.
+pub(crate) fn record_debug_line(group: &str, line: &str) -> bool {
+    let Some(event) = parse_debug_line(line) else {
+        return false;
+    };
+    if let Ok(mut counters) = DEBUG_COUNTERS.lock() {
+        counters
+            .entry(group.to_string())
+            .or_insert_with(DebugCounters::new)
+            .record(event);
+    }
+    true
+}
+
+/// With `--debug` winws prints several lines for every packet it looks at.
+/// Of the lines that are not counted, only those reporting a problem are
+/// worth streaming to the UI and keeping for the crash report.
+pub(crate) fn is_notable_debug_line(line: &str) -> bool {
+    let line = line.to_lowercase();
+    [
+        "error",
+        "fail",
+        "cannot",
+        "could not",
+        "invalid",
+        "unrecognized",
+    ]
+    .iter()
+    .any(|word| line.contains(word))
+}
+
+/// Forgets the counters of the group's previous winws run.
+pub(crate) fn reset_debug_counters(group: &str) {
+    if let Ok(mut counters) = DEBUG_COUNTERS.lock() {
+        counters.remove(group);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileDebugStats {
+    pub instance_group: String,
+    pub profile: usize,
+    /// `None` for the winws default profile and for numbers the current
+    /// config no longer has.
+    pub source: Option<ProfileSource>,
+    pub matches: u64,
+    pub actions: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostDebugStats {
+    pub instance_group: String,
+    pub host: String,
+    pub searches: u64,
+    pub l7: Vec<String>,
+    /// Profile number to how many times it handled this host.
+    pub profiles: BTreeMap<usize, u64>,
+    pub hostlist_positive: u64,
+    pub hostlist_negative: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugStats {
+    pub events: u64,
+    pub profiles: Vec<ProfileDebugStats>,
+    pub hosts: Vec<HostDebugStats>,
+}
+
+fn debug_stats(groups: &BTreeMap<String, DebugCounters>, sources: &[ProfileSource]) -> DebugStats {
+    let mut stats = DebugStats {
+        events: 0,
+        profiles: Vec::new(),
+        hosts: Vec::new(),
+    };
+
+    for (group, counters) in groups {
+        stats.events += counters.events;
+        stats
+            .profiles
+            .extend(counters.profiles.iter().map(|(profile, profile_stats)| {
+                ProfileDebugStats {
+                    instance_group: group.clone(),
+                    profile: *profile,
+                    source: sources
+                        .iter()
+                        .find(|source| {
+                            source.instance_group == *group && source.position == *profile
+                        })
+                        .cloned(),
+                    matches: profile_stats.matches,
+                    actions: profile_stats.actions.clone(),
+                }
+            }));
+        stats.hosts.extend(
+            counters
+                .hosts
+                .iter()
+                .map(|(host, host_stats)| HostDebugStats {
+                    instance_group: group.clone(),
+                    host: host.clone(),
+                    searches: host_stats.searches,
+                    l7: host_stats.l7.iter().cloned().collect(),
+                    profiles: host_stats.profiles.clone(),
+                    hostlist_positive: host_stats.hostlist_positive,
+                    hostlist_negative: host_stats.hostlist_negative,
+                }),
+        );
+    }
+    stats
+        .hosts
+        .sort_by(|a, b| b.searches.cmp(&a.searches).then(a.host.cmp(&b.host)));
+    stats
+}
+
+/// Counters collected from winws `--debug` output since the last start.
+/// Profile numbers are resolved against the current config, which matches
+/// the running winws as long as the config was not changed without a reload.
+#[tauri::command]
+pub fn get_debug_stats(state: tauri::State<'_, AppState>) -> Result<DebugStats, String> {
+    let config = current_config(&state)?;
+    let parse = |content: &str| parse_strategy(&apply_placeholders(content, &config.placeholders));
+    let sources: Vec<ProfileSource> = active_profiles(&config, parse)
+        .into_iter()
+        .map(|(source, _)| source)
+        .collect();
+    let counters = DEBUG_COUNTERS.lock().map_err(|e| e.to_string())?;
+    Ok(debug_stats(&counters, &sources))
+}
+
+#[tauri::command]
+pub fn reset_debug_stats() {
+    if let Ok(mut counters) = DEBUG_COUNTERS.lock() {
+        counters.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_debug_lines() {
+        assert_eq!(
+            parse_debug_line(
+                "desync profile search for tcp ip=142.250.74.14 port=443 l7proto=tls ssid='' hostname='www.YouTube.com'"
+            ),
+            Some(DebugEvent::ProfileSearch {
+                host: "www.youtube.com".to_string(),
+                l7: Some("tls".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_debug_line("desync profile 3 (noname) matches"),
+            Some(DebugEvent::ProfileMatched { profile: 3 })
+        );
+        assert_eq!(
+            parse_debug_line("using cached desync profile 2"),
+            Some(DebugEvent::ProfileMatched { profile: 2 })
+        );
+        assert_eq!(
+            parse_debug_line("hostlist check for youtube.com : positive"),
+            Some(DebugEvent::HostlistCheck {
+                host: "youtube.com".to_string(),
+                positive: true,
+            })
+        );
+        assert_eq!(
+            parse_debug_line("sending fake request : 517 bytes"),
+            Some(DebugEvent::Desync {
+                action: "fake".to_string(),
+            })
+        );
+        assert_eq!(parse_debug_line("desync profile 1 (noname) checking"), None);
+        assert_eq!(parse_debug_line("windivert initialized"), None);
+    }
+
+    #[test]
+    fn attributes_hosts_and_actions_to_matched_profiles() {
+        let mut counters = DebugCounters::new();
+        for line in [
+            "desync profile search for tcp ip=1.1.1.1 port=443 l7proto=tls hostname='youtube.com'",
+            "hostlist check for youtube.com : positive",
+            "desync profile 2 (noname) matches",
+            "sending fake request : 517 bytes",
+            "sending multisplit part 1",
+            "desync profile search for udp ip=1.1.1.1 port=443 l7proto=quic hostname=''",
+            "desync profile 0 (default) matches",
+        ] {
+            if let Some(event) = parse_debug_line(line) {
+                counters.record(event);
+            }
+        }
+
+        let source = ProfileSource {
+            position: 2,
+            instance_group: "main".to_string(),
+            category_id: "youtube".to_string(),
+            category_name: "YouTube".to_string(),
+            strategy_id: "fake".to_string(),
+            strategy_name: "Fake".to_string(),
+        };
+        let groups = BTreeMap::from([("main".to_string(), counters)]);
+        let stats = debug_stats(&groups, std::slice::from_ref(&source));
+
+        assert_eq!(stats.events, 7);
+        let youtube = stats
+            .hosts
+            .iter()
+            .find(|host| host.host == "youtube.com")
+            .unwrap();
+        assert_eq!(youtube.profiles, BTreeMap::from([(2, 1)]));
+        assert_eq!(youtube.l7, vec!["tls".to_string()]);
+        assert_eq!(youtube.hostlist_positive, 1);
+        assert!(stats.hosts.iter().any(|host| host.host == "1.1.1.1"));
+
+        let profile = stats
+            .profiles
+            .iter()
+            .find(|stats| stats.profile == 2)
+            .unwrap();
+        assert_eq!(profile.source, Some(source));
+        assert_eq!(
+            profile.actions,
+            BTreeMap::from([("fake".to_string(), 1), ("multisplit".to_string(), 1)])
+        );
+        assert!(
+            stats
+                .profiles
+                .iter()
+                .any(|stats| stats.profile == 0 && stats.source.is_none())
+        );
+    }
+
+    #[test]
+    fn evicts_least_recently_seen_hosts() {
+        let mut counters = DebugCounters::new();
+        for index in 0..MAX_DEBUG_HOSTS {
+            counters.record(DebugEvent::ProfileSearch {
+                host: format!("host{index}.example"),
+                l7: None,
+            });
+        }
+        counters.record(DebugEvent::HostlistCheck {
+            host: "host0.example".to_string(),
+            positive: true,
+        });
+        counters.record(DebugEvent::ProfileSearch {
+            host: "new.example".to_string(),
+            l7: None,
+        });
+
+        assert_eq!(counters.hosts.len(), MAX_DEBUG_HOSTS);
+        assert!(counters.hosts.contains_key("host0.example"));
+        assert!(counters.hosts.contains_key("new.example"));
+        assert!(!counters.hosts.contains_key("host1.example"));
+
+        assert!(is_notable_debug_line("could not read hostlist youtube.txt"));
+        assert!(!is_notable_debug_line("desync profile 1 (noname) checking"));
+    }
+}
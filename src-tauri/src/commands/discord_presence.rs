@@ -69,7 +69,7 @@ pub async fn sync_discord_presence(
     let client = presence_state
         .client
         .as_mut()
-        .ok_or("Client not initialized")?;
+        .ok_or("Клиент Discord не инициализирован")?;
 
     let details_clone = details.clone();
     let state_clone = state.clone();
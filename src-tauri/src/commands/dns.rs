@@ -330,7 +330,7 @@ fn enumerate_target_adapters() -> Result<Vec<TargetAdapter>, String> {
         }
 
         if result != 0 {
-            return Err(format!("GetAdaptersAddresses failed: {result}"));
+            return Err(format!("Ошибка GetAdaptersAddresses: {result}"));
         }
 
         let mut preferred = Vec::new();
@@ -423,7 +423,7 @@ fn get_configured_dns_servers(interface_guid: windows::core::GUID) -> Result<Vec
         };
         let result = GetInterfaceDnsSettings(interface_guid, &mut settings);
         if result != WIN32_ERROR(0) {
-            return Err(format!("GetInterfaceDnsSettings failed: {result:?}"));
+            return Err(format!("Ошибка GetInterfaceDnsSettings: {result:?}"));
         }
 
         let mut servers = parse_dns_server_list(&wide_ptr_to_string(settings.NameServer));
@@ -546,7 +546,7 @@ fn to_wide(value: &str) -> Vec<u16> {
 fn query_dns_proxy_service_state() -> Result<Option<bool>, String> {
     unsafe {
         let manager = OpenSCManagerW(None, None, SC_MANAGER_CONNECT)
-            .map_err(|e| format!("Failed to open Service Control Manager: {e}"))?;
+            .map_err(|e| format!("Не удалось открыть Service Control Manager: {e}"))?;
         let service_name = to_wide(DNSCRYPT_PROXY_SERVICE_NAME);
         let service = OpenServiceW(manager, PCWSTR(service_name.as_ptr()), SERVICE_QUERY_STATUS);
 
@@ -562,7 +562,9 @@ fn query_dns_proxy_service_state() -> Result<Option<bool>, String> {
         if let Err(error) = QueryServiceStatus(service, &mut status) {
             let _ = CloseServiceHandle(service);
             let _ = CloseServiceHandle(manager);
-            return Err(format!("Failed to query dnscrypt-proxy service: {error}"));
+            return Err(format!(
+                "Не удалось получить состояние службы dnscrypt-proxy: {error}"
+            ));
         }
 
         let _ = CloseServiceHandle(service);
@@ -636,7 +638,7 @@ fn run_command_capture(
     } else if !stdout.is_empty() {
         stdout
     } else {
-        format!("exit code {}", output.status)
+        format!("статус {}", output.status)
     };
     Err(format!("{error_context}: {detail}"))
 }
@@ -653,7 +655,7 @@ fn run_dns_proxy_action(config_path: &Path, action: &str) -> Result<String, Stri
             action.to_string(),
         ],
         Some(&module_dir),
-        &format!("Failed to {action} dnscrypt-proxy service"),
+        &format!("Не удалось выполнить {action} для службы dnscrypt-proxy"),
     )
 }
 
@@ -668,7 +670,7 @@ fn check_dns_proxy_config(config_path: &Path) -> Result<(), String> {
             "-check".to_string(),
         ],
         Some(&module_dir),
-        "Failed to validate dnscrypt-proxy config",
+        "Конфиг dnscrypt-proxy не прошёл проверку",
     )?;
     Ok(())
 }
@@ -0,0 +1,406 @@
+use super::config::{
+    AppConfig, AppState, Category, Strategy, current_config, get_config_path, get_runtime_data_dir,
+    parse_config_snapshot, replace_current_config,
+};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CONFIG_HISTORY_DIR_NAME: &str = "config-history";
+const CONFIG_HISTORY_PREFIX: &str = "config-";
+const CONFIG_HISTORY_LIMIT: usize = 50;
+/// Autosaves of one editing session land within this window of the newest
+/// snapshot and are coalesced into it, so a burst keeps the config from
+/// before it started instead of evicting older sessions.
+const CONFIG_HISTORY_INTERVAL_MS: u64 = 10 * 60 * 1000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigHistoryEntry {
+    pub id: String,
+    pub created_at: u64,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigItemChange {
+    pub id: String,
+    pub name: String,
+    pub change: ConfigChangeKind,
+    pub fields: Vec<String>,
+}
+
+/// Changes made since the snapshot was taken, i.e. what restoring it would revert.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigHistoryDiff {
+    pub categories: Vec<ConfigItemChange>,
+    pub strategies: Vec<ConfigItemChange>,
+    pub placeholders: Vec<ConfigItemChange>,
+    pub filters: Vec<ConfigItemChange>,
+}
+
+fn get_config_history_dir() -> PathBuf {
+    get_runtime_data_dir().join(CONFIG_HISTORY_DIR_NAME)
+}
+
+fn history_entry_path(dir: &Path, id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || !id.chars().all(|char| char.is_ascii_digit()) {
+        return Err(format!("Некорректный id снимка конфигурации: {id}"));
+    }
+    Ok(dir.join(format!("{CONFIG_HISTORY_PREFIX}{id}.json")))
+}
+
+fn list_history_in(dir: &Path) -> Result<Vec<ConfigHistoryEntry>, String> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(id) = file_name
+            .strip_prefix(CONFIG_HISTORY_PREFIX)
+            .and_then(|name| name.strip_suffix(".json"))
+        else {
+            continue;
+        };
+        let Ok(created_at) = id.parse::<u64>() else {
+            continue;
+        };
+        entries.push(ConfigHistoryEntry {
+            id: id.to_string(),
+            created_at,
+            size: entry.metadata().map_err(|e| e.to_string())?.len(),
+        });
+    }
+
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.created_at));
+    Ok(entries)
+}
+
+fn prune_history_in(dir: &Path, limit: usize) -> Result<(), String> {
+    for entry in list_history_in(dir)?.into_iter().skip(limit) {
+        fs::remove_file(history_entry_path(dir, &entry.id)?).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Without `force`, a snapshot is skipped when the newest one is younger than
+/// `CONFIG_HISTORY_INTERVAL_MS`.
+fn record_history_in(
+    dir: &Path,
+    previous: &str,
+    created_at: u64,
+    force: bool,
+) -> Result<(), String> {
+    let newest = list_history_in(dir)?.into_iter().next();
+    if let Some(newest) = newest {
+        if !force && created_at.saturating_sub(newest.created_at) < CONFIG_HISTORY_INTERVAL_MS {
+            return Ok(());
+        }
+        let newest_content = fs::read_to_string(history_entry_path(dir, &newest.id)?);
+        if newest_content.is_ok_and(|content| content == previous) {
+            return Ok(());
+        }
+    }
+
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let path = history_entry_path(dir, &created_at.to_string())?;
+    if !path.exists() {
+        fs::write(&path, previous).map_err(|e| e.to_string())?;
+    }
+    prune_history_in(dir, CONFIG_HISTORY_LIMIT)
+}
+
+/// Keeps the config that is about to be overwritten, unless nothing changed
+/// or a snapshot was taken less than `CONFIG_HISTORY_INTERVAL_MS` ago.
+pub(crate) fn record_config_history(config_path: &Path, next_content: &str) -> Result<(), String> {
+    let Ok(previous) = fs::read_to_string(config_path) else {
+        return Ok(());
+    };
+    if previous == next_content {
+        return Ok(());
+    }
+
+    record_history_in(&get_config_history_dir(), &previous, now_ms(), false)
+}
+
+/// Snapshots config.json even within `CONFIG_HISTORY_INTERVAL_MS` of the
+/// last snapshot. Restores and imports call it before overwriting the config
+/// so they can be undone right after an autosave.
+pub(crate) fn snapshot_config_history() -> Result<(), String> {
+    let Ok(current) = fs::read_to_string(get_config_path()) else {
+        return Ok(());
+    };
+    record_history_in(&get_config_history_dir(), &current, now_ms(), true)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn diff_items<T>(
+    before: &[T],
+    after: &[T],
+    key: impl Fn(&T) -> String,
+    name: impl Fn(&T) -> String,
+    changed_fields: impl Fn(&T, &T) -> Vec<&'static str>,
+) -> Vec<ConfigItemChange> {
+    let mut changes = Vec::new();
+
+    for item in after {
+        let id = key(item);
+        let change = match before.iter().find(|candidate| key(candidate) == id) {
+            None => (ConfigChangeKind::Added, Vec::new()),
+            Some(previous) => {
+                let fields = changed_fields(previous, item);
+                if fields.is_empty() {
+                    continue;
+                }
+                (ConfigChangeKind::Modified, fields)
+            }
+        };
+        changes.push(ConfigItemChange {
+            id,
+            name: name(item),
+            change: change.0,
+            fields: change.1.into_iter().map(str::to_string).collect(),
+        });
+    }
+
+    for item in before {
+        let id = key(item);
+        if !after.iter().any(|candidate| key(candidate) == id) {
+            changes.push(ConfigItemChange {
+                id,
+                name: name(item),
+                change: ConfigChangeKind::Removed,
+                fields: Vec::new(),
+            });
+        }
+    }
+
+    changes
+}
+
+fn flatten_strategies(config: &AppConfig) -> Vec<(&Category, &Strategy)> {
+    config
+        .categories
+        .iter()
+        .flat_map(|category| {
+            category
+                .strategies
+                .iter()
+                .map(move |strategy| (category, strategy))
+        })
+        .collect()
+}
+
+fn diff_configs(snapshot: &AppConfig, current: &AppConfig) -> ConfigHistoryDiff {
+    ConfigHistoryDiff {
+        categories: diff_items(
+            &snapshot.categories,
+            &current.categories,
+            |category| category.id.clone(),
+            |category| category.name.clone(),
+            |before, after| {
+                let mut fields = Vec::new();
+                if before.name != after.name {
+                    fields.push("name");
+                }
+                if before.instance_group != after.instance_group {
+                    fields.push("instanceGroup");
+                }
+                fields
+            },
+        ),
+        strategies: diff_items(
+            &flatten_strategies(snapshot),
+            &flatten_strategies(current),
+            |(category, strategy)| format!("{}/{}", category.id, strategy.id),
+            |(category, strategy)| format!("{} / {}", category.name, strategy.name),
+            |(_, before), (_, after)| {
+                let mut fields = Vec::new();
+                if before.name != after.name {
+                    fields.push("name");
+                }
+                if before.content != after.content {
+                    fields.push("content");
+                }
+                if before.active != after.active {
+                    fields.push("active");
+                }
+                if before.metadata != after.metadata {
+                    fields.push("metadata");
+                }
+                fields
+            },
+        ),
+        placeholders: diff_items(
+            &snapshot.placeholders,
+            &current.placeholders,
+            |placeholder| placeholder.name.clone(),
+            |placeholder| placeholder.name.clone(),
+            |before, after| {
+                let mut fields = Vec::new();
+                if before.value != after.value {
+                    fields.push("path");
+                }
+                if before.kind != after.kind {
+                    fields.push("kind");
+                }
+                fields
+            },
+        ),
+        filters: diff_items(
+            &snapshot.filters,
+            &current.filters,
+            |filter| filter.id.clone(),
+            |filter| filter.name.clone(),
+            |before, after| {
+                let mut fields = Vec::new();
+                if before.name != after.name {
+                    fields.push("name");
+                }
+                if before.filename != after.filename {
+                    fields.push("filename");
+                }
+                if before.active != after.active {
+                    fields.push("active");
+                }
+                if before.content != after.content {
+                    fields.push("content");
+                }
+                fields
+            },
+        ),
+    }
+}
+
+fn read_history_snapshot(id: &str) -> Result<AppConfig, String> {
+    let path = history_entry_path(&get_config_history_dir(), id)?;
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    parse_config_snapshot(&content)
+}
+
+#[tauri::command]
+pub fn list_config_history() -> Result<Vec<ConfigHistoryEntry>, String> {
+    list_history_in(&get_config_history_dir())
+}
+
+#[tauri::command]
+pub fn diff_config_history(
+    id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<ConfigHistoryDiff, String> {
+    let snapshot = read_history_snapshot(&id)?;
+    Ok(diff_configs(&snapshot, &current_config(&state)?))
+}
+
+#[tauri::command]
+pub fn restore_config_history(
+    app: tauri::AppHandle,
+    id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let snapshot = read_history_snapshot(&id)?;
+    snapshot_config_history()?;
+    let restored = replace_current_config(snapshot, &state)?;
+    crate::sync_list_mode_ui(&app, restored.list_mode)?;
+    crate::sync_profiles_ui(&app, &restored)?;
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::config::PlaceholderKind;
+    use uuid::Uuid;
+
+    #[test]
+    fn history_keeps_newest_entries() {
+        let dir = std::env::temp_dir().join(format!("zapret-history-{}", Uuid::new_v4()));
+        let session = |index: u64| index * CONFIG_HISTORY_INTERVAL_MS;
+        for index in 1..=CONFIG_HISTORY_LIMIT as u64 + 3 {
+            let content = index.to_string();
+            record_history_in(&dir, &content, session(index), false).unwrap();
+            // Later autosaves of the same session are coalesced.
+            record_history_in(&dir, "{}", session(index) + 1_000, false).unwrap();
+        }
+
+        let entries = list_history_in(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(entries.len(), CONFIG_HISTORY_LIMIT);
+        assert_eq!(
+            entries[0].created_at,
+            session(CONFIG_HISTORY_LIMIT as u64 + 3)
+        );
+        assert_eq!(entries[CONFIG_HISTORY_LIMIT - 1].created_at, session(4));
+        assert!(history_entry_path(&dir, "../config").is_err());
+    }
+
+    #[test]
+    fn diff_reports_changes_since_snapshot() {
+        let snapshot = AppConfig::default();
+        let mut current = snapshot.clone();
+        let removed_placeholder = current.placeholders.remove(0);
+        current.categories[0].strategies[0].active = !snapshot.categories[0].strategies[0].active;
+        current.categories[0].strategies[0]
+            .content
+            .push_str("\n--dpi-desync-repeats=2");
+        current.filters[1].name = "renamed".to_string();
+        current.categories[0].strategies[0].metadata.author = Some("someone".to_string());
+        current.placeholders[0].kind = PlaceholderKind::Integer;
+
+        let diff = diff_configs(&snapshot, &current);
+        assert!(diff.categories.is_empty());
+        assert_eq!(diff.strategies.len(), 1);
+        assert_eq!(diff.strategies[0].change, ConfigChangeKind::Modified);
+        assert_eq!(
+            diff.strategies[0].fields,
+            vec!["content", "active", "metadata"]
+        );
+        assert_eq!(diff.placeholders.len(), 2);
+        assert_eq!(diff.placeholders[0].fields, vec!["kind"]);
+        assert_eq!(diff.placeholders[1].id, removed_placeholder.name);
+        assert_eq!(diff.placeholders[1].change, ConfigChangeKind::Removed);
+        assert_eq!(diff.filters[0].fields, vec!["name"]);
+    }
+
+    #[test]
+    fn forced_snapshots_make_restores_reversible() {
+        let dir = std::env::temp_dir().join(format!("zapret-history-{}", Uuid::new_v4()));
+        record_history_in(&dir, "before", 0, false).unwrap();
+        // An autosave within the interval is coalesced...
+        record_history_in(&dir, "edited", 1_000, false).unwrap();
+        // ...but restoring the first snapshot keeps the edited config first.
+        record_history_in(&dir, "edited", 2_000, true).unwrap();
+        let edited = list_history_in(&dir).unwrap()[0].id.clone();
+        // Restoring it back snapshots the restored config, once.
+        record_history_in(&dir, "before", 3_000, true).unwrap();
+        record_history_in(&dir, "before", 4_000, true).unwrap();
+
+        let entries = list_history_in(&dir).unwrap();
+        let read = |id: &str| fs::read_to_string(history_entry_path(&dir, id).unwrap()).unwrap();
+        let contents: Vec<String> = entries.iter().map(|entry| read(&entry.id)).collect();
+        let edited = read(&edited);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(edited, "edited");
+        assert_eq!(contents, vec!["before", "edited", "before"]);
+    }
+}
@@ -1,7 +1,17 @@
 pub mod admin;
+pub mod backup;
+pub mod batch;
 pub mod binaries;
 pub mod config;
+pub mod debug_log;
 pub mod discord_presence;
 pub mod dns;
+pub mod history;
+pub mod pack;
+pub mod ports;
+pub mod preflight;
 pub mod process;
+pub mod profile;
+pub mod share;
+pub mod strategy;
 pub mod tg_proxy;
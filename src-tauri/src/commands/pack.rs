@@ -0,0 +1,771 @@
+use super::binaries::{create_http_client, download_bytes};
+use super::config::{
+    AppConfig, AppState, Category, PackSubscription, Placeholder, PlaceholderKind, Strategy,
+    StrategyMetadata, annotate_builtin_category, annotate_builtin_placeholder, current_config,
+    is_dynamic_placeholder, is_system_category_modified, is_system_placeholder_modified,
+    normalize_placeholder_path, placeholder_value, replace_current_config, strategy_base_content,
+    sync_builtin_category, sync_builtin_placeholder,
+};
+use super::strategy::{
+    DiagnosticSeverity, is_global_only_option, lint_strategy_content, option_takes_file,
+    parse_strategy, strategy_options,
+};
+use minisign_verify::{PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+const PACK_FORMAT: &str = "zapret-interactive-pack";
+const PACK_FORMAT_VERSION: u32 = 1;
+const MAX_PACK_SIZE: usize = 4 * 1024 * 1024;
+const PACK_REFRESH_INTERVAL_MS: u64 = 12 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackStrategy {
+    id: String,
+    name: String,
+    content: String,
+    #[serde(flatten)]
+    metadata: StrategyMetadata,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackCategory {
+    id: String,
+    name: String,
+    #[serde(default)]
+    strategies: Vec<PackStrategy>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackPlaceholder {
+    name: String,
+    path: String,
+    #[serde(default)]
+    kind: PlaceholderKind,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StrategyPack {
+    format: String,
+    format_version: u32,
+    name: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    categories: Vec<PackCategory>,
+    #[serde(default)]
+    placeholders: Vec<PackPlaceholder>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackRefreshResult {
+    pub id: String,
+    pub name: String,
+    pub updated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn pack_category_id(subscription_id: &str, category_id: &str) -> String {
+    format!("pack:{subscription_id}:{category_id}")
+}
+
+fn is_from_source(source_id: Option<&str>, subscription_id: &str) -> bool {
+    source_id == Some(subscription_id)
+}
+
+fn parse_pack(bytes: &[u8]) -> Result<StrategyPack, String> {
+    if bytes.len() > MAX_PACK_SIZE {
+        return Err("Пакет стратегий слишком большой".to_string());
+    }
+
+    let pack: StrategyPack =
+        serde_json::from_slice(bytes).map_err(|e| format!("Некорректный пакет стратегий: {e}"))?;
+    if pack.format != PACK_FORMAT {
+        return Err(format!("Неподдерживаемый формат пакета: {}", pack.format));
+    }
+    if pack.format_version > PACK_FORMAT_VERSION {
+        return Err(format!(
+            "Версия формата пакета {} новее поддерживаемой {PACK_FORMAT_VERSION}",
+            pack.format_version
+        ));
+    }
+    if pack.name.trim().is_empty() {
+        return Err("У пакета стратегий нет названия".to_string());
+    }
+
+    let mut category_ids = Vec::new();
+    for category in &pack.categories {
+        if category.id.trim().is_empty() || category_ids.contains(&category.id) {
+            return Err(format!(
+                "Повторяющийся или пустой id категории: «{}»",
+                category.id
+            ));
+        }
+        category_ids.push(category.id.clone());
+
+        let mut strategy_ids = Vec::new();
+        for strategy in &category.strategies {
+            if strategy.id.trim().is_empty() || strategy_ids.contains(&strategy.id) {
+                return Err(format!(
+                    "Повторяющийся или пустой id стратегии в «{}»: «{}»",
+                    category.id, strategy.id
+                ));
+            }
+            strategy_ids.push(strategy.id.clone());
+            validate_pack_strategy(&strategy.content).map_err(|error| {
+                format!(
+                    "Стратегия «{}» в «{}» отклонена: {error}",
+                    strategy.id, category.id
+                )
+            })?;
+        }
+    }
+
+    for placeholder in &pack.placeholders {
+        if placeholder.name.trim().is_empty() {
+            return Err("В пакете стратегий есть плейсхолдер без названия".to_string());
+        }
+        if is_dynamic_placeholder(&placeholder.name) {
+            return Err(format!(
+                "Имя плейсхолдера «{}» зарезервировано",
+                placeholder.name
+            ));
+        }
+        if !placeholder.kind.is_path() {
+            placeholder_value(placeholder.kind, &placeholder.path)?;
+            continue;
+        }
+        if !is_managed_pack_path(&placeholder.path) {
+            return Err(format!(
+                "Плейсхолдер «{}» указывает за пределы управляемых ресурсов: {}",
+                placeholder.name, placeholder.path
+            ));
+        }
+    }
+
+    Ok(pack)
+}
+
+/// Packs may only point into the managed resources directory.
+fn is_managed_pack_path(path: &str) -> bool {
+    normalize_placeholder_path(path).is_some()
+        && !path.split(['/', '\\']).any(|segment| segment == "..")
+}
+
+/// File values of pack strategies must be a single `{{PLACEHOLDER}}` or a
+/// managed path; hex blobs are inline data.
+fn is_pack_file_value(value: &str) -> bool {
+    let placeholder = value
+        .strip_prefix("{{")
+        .and_then(|value| value.strip_suffix("}}"))
+        .is_some_and(|name| !name.is_empty() && !name.contains(['{', '}']));
+    placeholder || value.starts_with("0x") || is_managed_pack_path(value)
+}
+
+/// Pack strategies end up on the command line of an elevated winws.exe, so
+/// besides passing the linter they may neither set global options nor name
+/// files the pack does not control.
+fn validate_pack_strategy(content: &str) -> Result<(), String> {
+    if let Some(error) = lint_strategy_content(content)
+        .into_iter()
+        .find(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error)
+    {
+        return Err(format!("строка {}: {}", error.line, error.message));
+    }
+
+    for option in strategy_options(&parse_strategy(content)) {
+        if is_global_only_option(&option.name) {
+            return Err(format!("опция {} запрещена", option.name));
+        }
+        if option_takes_file(&option.name)
+            && let Some(value) = option.raw_value.as_deref()
+            && !is_pack_file_value(value)
+        {
+            return Err(format!(
+                "{} указывает за пределы управляемых ресурсов: {value}",
+                option.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks a minisign signature when the subscription has a public key,
+/// otherwise the first token of a `sha256sum`-style file.
+fn verify_pack(bytes: &[u8], public_key: Option<&str>, proof: &str) -> Result<(), String> {
+    match public_key {
+        Some(public_key) => {
+            let public_key = public_key.trim();
+            let public_key = if public_key.contains('\n') {
+                PublicKey::decode(public_key)
+            } else {
+                PublicKey::from_base64(public_key)
+            }
+            .map_err(|e| format!("Некорректный публичный ключ пакета: {e}"))?;
+            let signature = Signature::decode(proof)
+                .map_err(|e| format!("Некорректная подпись пакета: {e}"))?;
+            public_key
+                .verify(bytes, &signature, false)
+                .map_err(|e| format!("Подпись пакета стратегий не прошла проверку: {e}"))
+        }
+        None => {
+            let expected = proof
+                .split_whitespace()
+                .next()
+                .ok_or_else(|| "Файл контрольной суммы пакета пуст".to_string())?;
+            let actual = format!("{:x}", Sha256::digest(bytes));
+            if expected.eq_ignore_ascii_case(&actual) {
+                Ok(())
+            } else {
+                Err("Контрольная сумма пакета стратегий не совпадает".to_string())
+            }
+        }
+    }
+}
+
+async fn fetch_pack(subscription: &PackSubscription) -> Result<StrategyPack, String> {
+    let client = create_http_client()?;
+    let bytes = download_bytes(&client, &subscription.url, "пакет стратегий").await?;
+    let proof_url = match subscription.public_key {
+        Some(_) => format!("{}.minisig", subscription.url),
+        None => format!("{}.sha256", subscription.url),
+    };
+    let proof = download_bytes(&client, &proof_url, "подпись пакета стратегий").await?;
+    let proof = String::from_utf8(proof).map_err(|e| e.to_string())?;
+    verify_pack(&bytes, subscription.public_key.as_deref(), &proof)?;
+    parse_pack(&bytes)
+}
+
+fn pack_categories(subscription_id: &str, pack: &StrategyPack) -> Vec<Category> {
+    pack.categories
+        .iter()
+        .map(|category| {
+            let mut category = Category {
+                id: pack_category_id(subscription_id, &category.id),
+                name: category.name.clone(),
+                strategies: category
+                    .strategies
+                    .iter()
+                    .map(|strategy| Strategy {
+                        id: strategy.id.clone(),
+                        name: strategy.name.clone(),
+                        content: strategy.content.clone(),
+                        active: false,
+                        metadata: strategy.metadata.clone(),
+                        system: false,
+                        system_base_name: None,
+                        system_base_content: None,
+                        system_base_metadata: None,
+                    })
+                    .collect(),
+                system: false,
+                system_base_name: None,
+                source_id: Some(subscription_id.to_string()),
+                instance_group: None,
+            };
+            annotate_builtin_category(&mut category);
+            category
+        })
+        .collect()
+}
+
+fn pack_placeholders(subscription_id: &str, pack: &StrategyPack) -> Vec<Placeholder> {
+    pack.placeholders
+        .iter()
+        .map(|placeholder| {
+            let value = match placeholder.kind {
+                PlaceholderKind::Path => placeholder.path.replace('\\', "/"),
+                kind => placeholder_value(kind, &placeholder.path)
+                    .unwrap_or_else(|_| placeholder.path.clone()),
+            };
+            let mut placeholder = Placeholder {
+                name: placeholder.name.clone(),
+                value,
+                kind: placeholder.kind,
+                system: false,
+                system_base_name: None,
+                system_base_path: None,
+                source_id: Some(subscription_id.to_string()),
+            };
+            annotate_builtin_placeholder(&mut placeholder);
+            placeholder
+        })
+        .collect()
+}
+
+/// Keeps the content of strategies the user has activated: they run in an
+/// elevated winws.exe, so a pack update waits until they are deactivated.
+fn hold_active_strategy_updates(category: &Category, pack_category: &Category) -> Category {
+    let mut pack_category = pack_category.clone();
+    for pack_strategy in &mut pack_category.strategies {
+        if let Some(strategy) = category
+            .strategies
+            .iter()
+            .find(|strategy| strategy.id == pack_strategy.id && strategy.active)
+        {
+            pack_strategy.content = strategy_base_content(strategy).to_string();
+        }
+    }
+    pack_category
+}
+
+/// Syncs the pack into the config the same way builtin categories are
+/// synced: untouched items follow the pack, edited ones are kept.
+fn apply_pack(config: &mut AppConfig, subscription_id: &str, pack: &StrategyPack) -> bool {
+    let mut changed = false;
+    let categories = pack_categories(subscription_id, pack);
+    let placeholders = pack_placeholders(subscription_id, pack);
+
+    for category in config
+        .categories
+        .iter_mut()
+        .filter(|category| is_from_source(category.source_id.as_deref(), subscription_id))
+    {
+        if let Some(pack_category) = categories.iter().find(|item| item.id == category.id)
+            && sync_builtin_category(
+                category,
+                &hold_active_strategy_updates(category, pack_category),
+                &config.system_removed_strategy_keys,
+                true,
+            )
+        {
+            changed = true;
+        }
+    }
+
+    let before_category_count = config.categories.len();
+    config.categories.retain(|category| {
+        if !is_from_source(category.source_id.as_deref(), subscription_id)
+            || categories.iter().any(|item| item.id == category.id)
+        {
+            return true;
+        }
+
+        !category.system || is_system_category_modified(category)
+    });
+    changed |= config.categories.len() != before_category_count;
+
+    for pack_category in categories {
+        let already_present = config
+            .categories
+            .iter()
+            .any(|category| category.id == pack_category.id);
+        let removed_by_user = config
+            .system_removed_category_ids
+            .contains(&pack_category.id);
+        if !already_present && !removed_by_user {
+            config.categories.push(pack_category);
+            changed = true;
+        }
+    }
+
+    for placeholder in config
+        .placeholders
+        .iter_mut()
+        .filter(|placeholder| is_from_source(placeholder.source_id.as_deref(), subscription_id))
+    {
+        if let Some(pack_placeholder) = placeholders.iter().find(|item| {
+            item.name == placeholder.name
+                || placeholder.system_base_name.as_deref() == Some(item.name.as_str())
+        }) && sync_builtin_placeholder(placeholder, pack_placeholder)
+        {
+            changed = true;
+        }
+    }
+
+    let before_placeholder_count = config.placeholders.len();
+    config.placeholders.retain(|placeholder| {
+        if !is_from_source(placeholder.source_id.as_deref(), subscription_id)
+            || placeholders
+                .iter()
+                .any(|item| item.name == placeholder.name)
+        {
+            return true;
+        }
+
+        !placeholder.system || is_system_placeholder_modified(placeholder)
+    });
+    changed |= config.placeholders.len() != before_placeholder_count;
+
+    // Names are global, so a pack never takes over a placeholder it did not add.
+    for pack_placeholder in placeholders {
+        let already_present = config
+            .placeholders
+            .iter()
+            .any(|placeholder| placeholder.name == pack_placeholder.name);
+        let removed_by_user = config
+            .system_removed_placeholder_names
+            .contains(&pack_placeholder.name);
+        if !already_present && !removed_by_user {
+            config.placeholders.push(pack_placeholder);
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Drops the untouched items of a subscription and turns edited ones into
+/// regular user items.
+fn remove_pack_items(config: &mut AppConfig, subscription_id: &str) {
+    config.categories.retain(|category| {
+        !is_from_source(category.source_id.as_deref(), subscription_id)
+            || is_system_category_modified(category)
+    });
+    for category in config
+        .categories
+        .iter_mut()
+        .filter(|category| is_from_source(category.source_id.as_deref(), subscription_id))
+    {
+        category.source_id = None;
+        category.system = false;
+        category.system_base_name = None;
+        for strategy in &mut category.strategies {
+            strategy.system = false;
+            strategy.system_base_name = None;
+            strategy.system_base_content = None;
+            strategy.system_base_metadata = None;
+        }
+    }
+
+    config.placeholders.retain(|placeholder| {
+        !is_from_source(placeholder.source_id.as_deref(), subscription_id)
+            || is_system_placeholder_modified(placeholder)
+    });
+    for placeholder in config
+        .placeholders
+        .iter_mut()
+        .filter(|placeholder| is_from_source(placeholder.source_id.as_deref(), subscription_id))
+    {
+        placeholder.source_id = None;
+        placeholder.system = false;
+        placeholder.system_base_name = None;
+        placeholder.system_base_path = None;
+    }
+
+    let prefix = pack_category_id(subscription_id, "");
+    config
+        .system_removed_category_ids
+        .retain(|id| !id.starts_with(&prefix));
+    config
+        .system_removed_strategy_keys
+        .retain(|key| !key.starts_with(&prefix));
+    config
+        .pack_subscriptions
+        .retain(|subscription| subscription.id != subscription_id);
+}
+
+fn normalize_public_key(public_key: Option<String>) -> Option<String> {
+    public_key
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
+
+#[tauri::command]
+pub async fn add_pack_subscription(
+    url: String,
+    public_key: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let url = url.trim().to_string();
+    if !url.to_ascii_lowercase().starts_with("https://") {
+        return Err("Адрес пакета стратегий должен использовать https".to_string());
+    }
+    if current_config(&state)?
+        .pack_subscriptions
+        .iter()
+        .any(|subscription| subscription.url == url)
+    {
+        return Err(format!("Подписка на {url} уже есть"));
+    }
+
+    let mut subscription = PackSubscription {
+        id: Uuid::new_v4().to_string(),
+        url,
+        name: String::new(),
+        public_key: normalize_public_key(public_key),
+        version: None,
+        last_updated_at: None,
+        last_error: None,
+    };
+    let pack = fetch_pack(&subscription).await?;
+    subscription.name = pack.name.trim().to_string();
+    subscription.version = pack.version.clone();
+    subscription.last_updated_at = Some(now_ms());
+
+    let mut next = current_config(&state)?;
+    apply_pack(&mut next, &subscription.id, &pack);
+    next.pack_subscriptions.push(subscription);
+    replace_current_config(next, &state)
+}
+
+#[tauri::command]
+pub fn remove_pack_subscription(
+    id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let mut next = current_config(&state)?;
+    if !next
+        .pack_subscriptions
+        .iter()
+        .any(|subscription| subscription.id == id)
+    {
+        return Err(format!("Подписка на пакет «{id}» не найдена"));
+    }
+    remove_pack_items(&mut next, &id);
+    replace_current_config(next, &state)
+}
+
+/// Refreshes subscriptions older than the refresh interval, or all of them
+/// when forced. Failures are recorded on the subscription and retried later.
+#[tauri::command]
+pub async fn refresh_pack_subscriptions(
+    force: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<PackRefreshResult>, String> {
+    let force = force.unwrap_or(false);
+    let now = now_ms();
+    let due: Vec<PackSubscription> = current_config(&state)?
+        .pack_subscriptions
+        .into_iter()
+        .filter(|subscription| {
+            force
+                || subscription.last_updated_at.is_none_or(|updated_at| {
+                    now.saturating_sub(updated_at) >= PACK_REFRESH_INTERVAL_MS
+                })
+        })
+        .collect();
+    if due.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut fetched = Vec::new();
+    for subscription in due {
+        let pack = fetch_pack(&subscription).await;
+        fetched.push((subscription.id, pack));
+    }
+
+    // The config may have changed while downloading, so apply to a fresh copy.
+    let mut next = current_config(&state)?;
+    let mut results = Vec::new();
+    for (id, pack) in fetched {
+        let Some(index) = next
+            .pack_subscriptions
+            .iter()
+            .position(|subscription| subscription.id == id)
+        else {
+            continue;
+        };
+
+        let (updated, error) = match pack {
+            Ok(pack) => {
+                let updated = apply_pack(&mut next, &id, &pack);
+                let subscription = &mut next.pack_subscriptions[index];
+                subscription.name = pack.name.trim().to_string();
+                subscription.version = pack.version;
+                subscription.last_updated_at = Some(now_ms());
+                subscription.last_error = None;
+                (updated, None)
+            }
+            Err(error) => {
+                next.pack_subscriptions[index].last_error = Some(error.clone());
+                (false, Some(error))
+            }
+        };
+        results.push(PackRefreshResult {
+            id,
+            name: next.pack_subscriptions[index].name.clone(),
+            updated,
+            error,
+        });
+    }
+
+    replace_current_config(next, &state)?;
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pack(strategy_content: &str, with_second_category: bool) -> StrategyPack {
+        let mut json = serde_json::json!({
+            "format": PACK_FORMAT,
+            "formatVersion": 1,
+            "name": "Community",
+            "version": "1",
+            "categories": [{
+                "id": "games",
+                "name": "Games",
+                "strategies": [
+                    { "id": "a", "name": "A", "content": "--dpi-desync=fake" },
+                    { "id": "b", "name": "B", "content": strategy_content }
+                ]
+            }],
+            "placeholders": [{ "name": "pack_bin", "path": "@resources/fake/pack.bin" }]
+        });
+        if with_second_category {
+            json["categories"]
+                .as_array_mut()
+                .unwrap()
+                .push(serde_json::json!({ "id": "voice", "name": "Voice", "strategies": [] }));
+        }
+        parse_pack(json.to_string().as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn pack_updates_follow_builtin_sync_and_removal_keeps_user_edits() {
+        let mut config = AppConfig::default();
+        let user_categories = config.categories.len();
+
+        assert!(apply_pack(
+            &mut config,
+            "sub",
+            &pack("--dpi-desync=split2", true)
+        ));
+        let category_id = pack_category_id("sub", "games");
+        assert_eq!(config.categories.len(), user_categories + 2);
+        assert!(!apply_pack(
+            &mut config,
+            "sub",
+            &pack("--dpi-desync=split2", true)
+        ));
+
+        let games = config
+            .categories
+            .iter_mut()
+            .find(|category| category.id == category_id)
+            .unwrap();
+        games.strategies[0].name = "My A".to_string();
+
+        assert!(apply_pack(
+            &mut config,
+            "sub",
+            &pack("--dpi-desync=disorder2", false)
+        ));
+        let games = config
+            .categories
+            .iter()
+            .find(|category| category.id == category_id)
+            .unwrap();
+        assert_eq!(games.strategies[0].name, "My A");
+        assert_eq!(games.strategies[1].content, "--dpi-desync=disorder2");
+        assert_eq!(config.categories.len(), user_categories + 1);
+        assert!(
+            config
+                .placeholders
+                .iter()
+                .any(|placeholder| placeholder.name == "pack_bin")
+        );
+
+        remove_pack_items(&mut config, "sub");
+        assert_eq!(config.categories.len(), user_categories + 1);
+        let games = config
+            .categories
+            .iter()
+            .find(|category| category.id == category_id)
+            .unwrap();
+        assert!(games.source_id.is_none() && !games.system);
+        assert!(
+            config
+                .placeholders
+                .iter()
+                .all(|placeholder| placeholder.name != "pack_bin")
+        );
+    }
+
+    #[test]
+    fn verifies_checksum_and_rejects_unmanaged_paths() {
+        let bytes = br#"{"format":"zapret-interactive-pack","formatVersion":1,"name":"x"}"#;
+        let hash = format!("{:x}  pack.json\n", Sha256::digest(bytes));
+        assert!(verify_pack(bytes, None, &hash).is_ok());
+        assert!(verify_pack(b"tampered", None, &hash).is_err());
+        assert!(parse_pack(bytes).is_ok());
+
+        let unmanaged = br#"{"format":"zapret-interactive-pack","formatVersion":1,"name":"x",
+            "placeholders":[{"name":"evil","path":"@resources/../../evil.bin"}]}"#;
+        assert!(parse_pack(unmanaged).is_err());
+    }
+
+    #[test]
+    fn rejects_malicious_strategies_and_holds_active_updates() {
+        let parse = |content: &str| {
+            let json = serde_json::json!({
+                "format": PACK_FORMAT,
+                "formatVersion": 1,
+                "name": "Evil",
+                "categories": [{
+                    "id": "games",
+                    "name": "Games",
+                    "strategies": [{ "id": "a", "name": "A", "content": content }]
+                }]
+            });
+            parse_pack(json.to_string().as_bytes())
+        };
+        for content in [
+            "--dpi-desync=fake\n--debug=@C:\\Windows\\System32\\evil.dll",
+            "--wf-raw-part=@C:\\evil.txt",
+            "--hostlist=C:\\Users\\Public\\list.txt",
+            "--dpi-desync=fake\n--dpi-desync-fake-tls=../../secret.bin",
+            "--hostlist={{LIST}}/../../evil.txt",
+            "--unknown-option",
+        ] {
+            assert!(parse(content).is_err(), "{content}");
+        }
+        assert!(
+            parse("--hostlist={{LIST}}\n--dpi-desync=fake\n--dpi-desync-fake-tls=0x1603").is_ok()
+        );
+
+        let mut config = AppConfig::default();
+        apply_pack(&mut config, "sub", &pack("--dpi-desync=split2", false));
+        let category_id = pack_category_id("sub", "games");
+        let find_b = |config: &AppConfig| {
+            config
+                .categories
+                .iter()
+                .find(|category| category.id == category_id)
+                .unwrap()
+                .strategies[1]
+                .clone()
+        };
+        config
+            .categories
+            .iter_mut()
+            .find(|category| category.id == category_id)
+            .unwrap()
+            .strategies[1]
+            .active = true;
+
+        apply_pack(&mut config, "sub", &pack("--dpi-desync=disorder2", false));
+        assert_eq!(find_b(&config).content, "--dpi-desync=split2");
+
+        config
+            .categories
+            .iter_mut()
+            .find(|category| category.id == category_id)
+            .unwrap()
+            .strategies[1]
+            .active = false;
+        assert!(apply_pack(
+            &mut config,
+            "sub",
+            &pack("--dpi-desync=disorder2", false)
+        ));
+        assert_eq!(find_b(&config).content, "--dpi-desync=disorder2");
+    }
+}
@@ -0,0 +1,156 @@
+use std::ops::RangeInclusive;
+
+/// Parses a winws port expression: comma-separated ports and `a-b` ranges,
+/// or `*` for every port. A leading `~` (negation, only meaningful for
+/// `--filter-tcp`/`--filter-udp`) is stripped; callers that care inspect the
+/// raw value themselves.
+pub(crate) fn parse_port_list(value: &str) -> Result<Vec<RangeInclusive<u16>>, String> {
+    let value = value.strip_prefix('~').unwrap_or(value);
+    if value == "*" {
+        return Ok(vec![1..=u16::MAX]);
+    }
+
+    let mut ranges = Vec::new();
+    for item in value.split(',') {
+        let item = item.trim();
+        if item.is_empty() {
+            return Err("пустой элемент в списке портов".to_string());
+        }
+        let (start, end) = item.split_once('-').unwrap_or((item, item));
+        let start = parse_port(start.trim())?;
+        let end = parse_port(end.trim())?;
+        if start > end {
+            return Err(format!("диапазон портов «{item}» перевёрнут"));
+        }
+        ranges.push(start..=end);
+    }
+
+    Ok(ranges)
+}
+
+fn parse_port(raw: &str) -> Result<u16, String> {
+    if raw.is_empty() || !raw.chars().all(|char| char.is_ascii_digit()) {
+        return Err(format!("некорректный порт «{raw}»"));
+    }
+    raw.parse::<u16>()
+        .ok()
+        .filter(|port| *port != 0)
+        .ok_or_else(|| format!("порт {raw} вне диапазона 1..65535"))
+}
+
+/// Parses a port expression and returns it in canonical form: ranges sorted,
+/// overlapping and adjacent ranges merged, no whitespace, negation kept.
+pub(crate) fn canonical_port_list(value: &str) -> Result<String, String> {
+    let ranges = normalize_ranges(parse_port_list(value)?);
+    let negation = if value.starts_with('~') { "~" } else { "" };
+    Ok(format!("{negation}{}", format_ranges(&ranges)))
+}
+
+pub(crate) fn normalize_ranges(mut ranges: Vec<RangeInclusive<u16>>) -> Vec<RangeInclusive<u16>> {
+    ranges.sort_by_key(|range| *range.start());
+    let mut merged: Vec<RangeInclusive<u16>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if u32::from(*range.start()) <= u32::from(*last.end()) + 1 => {
+                if range.end() > last.end() {
+                    *last = *last.start()..=*range.end();
+                }
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Ports of `ranges` that are not in `covered`.
+pub(crate) fn subtract_ranges(
+    ranges: &[RangeInclusive<u16>],
+    covered: &[RangeInclusive<u16>],
+) -> Vec<RangeInclusive<u16>> {
+    let mut remaining = normalize_ranges(ranges.to_vec());
+    for cut in covered {
+        remaining = remaining
+            .into_iter()
+            .flat_map(|range| {
+                if range.end() < cut.start() || range.start() > cut.end() {
+                    return vec![range];
+                }
+                let mut parts = Vec::new();
+                if range.start() < cut.start() {
+                    parts.push(*range.start()..=cut.start() - 1);
+                }
+                if range.end() > cut.end() {
+                    parts.push(cut.end() + 1..=*range.end());
+                }
+                parts
+            })
+            .collect();
+    }
+    remaining
+}
+
+pub(crate) fn format_ranges(ranges: &[RangeInclusive<u16>]) -> String {
+    ranges
+        .iter()
+        .map(|range| {
+            if range.start() == range.end() {
+                range.start().to_string()
+            } else {
+                format!("{}-{}", range.start(), range.end())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Checks a `--wf-tcp`/`--wf-udp` value and returns its canonical form. An
+/// empty value is allowed and means the protocol is not intercepted.
+pub(crate) fn normalize_global_port_list(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(String::new());
+    }
+    if value.starts_with('~') {
+        return Err("отрицание «~» недопустимо в глобальных портах".to_string());
+    }
+    canonical_port_list(value)
+}
+
+#[tauri::command]
+pub fn normalize_port_list(value: String) -> Result<String, String> {
+    normalize_global_port_list(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_port_lists() {
+        assert_eq!(
+            parse_port_list("88,1024-2407").unwrap(),
+            vec![88..=88, 1024..=2407]
+        );
+        assert!(parse_port_list("443-80").is_err());
+        assert!(parse_port_list("0").is_err());
+        assert!(parse_port_list("80,").is_err());
+        assert!(parse_port_list("+80").is_err());
+        assert_eq!(
+            parse_port_list("70000").unwrap_err(),
+            "порт 70000 вне диапазона 1..65535"
+        );
+    }
+
+    #[test]
+    fn canonicalizes_port_lists() {
+        assert_eq!(
+            canonical_port_list("443, 80,1000-2000,1500-2500,2501").unwrap(),
+            "80,443,1000-2501"
+        );
+        assert_eq!(canonical_port_list("~443,80").unwrap(), "~80,443");
+        assert_eq!(canonical_port_list("*").unwrap(), "1-65535");
+        assert_eq!(normalize_global_port_list("  ").unwrap(), "");
+        assert!(normalize_global_port_list("~443").is_err());
+        assert!(normalize_global_port_list("80,,443").is_err());
+    }
+}
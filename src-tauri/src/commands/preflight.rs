@@ -0,0 +1,205 @@
+use super::config::{
+    AppConfig, DEFAULT_INSTANCE_GROUP, PROFILE_SEPARATOR_ARG, build_winws_base_args,
+    instance_config, instance_groups, strategy_segments,
+};
+use super::strategy::{ProfileSource, describe_source};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightFailure {
+    pub instance_group: String,
+    /// `None` when winws rejects the arguments shared by every profile of
+    /// the group (ports, raw filters) or only the combination of profiles
+    /// that each pass on their own.
+    pub profile: Option<ProfileSource>,
+    pub output: String,
+}
+
+/// Result of running every winws.exe a connect would start with `--dry-run`
+/// on its exact argument vector.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WinwsPreflightReport {
+    pub failures: Vec<PreflightFailure>,
+}
+
+impl WinwsPreflightReport {
+    pub fn is_clean(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn describe(&self) -> String {
+        let mut lines = vec!["Запуск отменён: winws.exe отклонил аргументы".to_string()];
+        lines.extend(self.failures.iter().map(|failure| {
+            let source = failure.profile.as_ref().map_or_else(
+                || {
+                    if failure.instance_group == DEFAULT_INSTANCE_GROUP {
+                        "общие параметры (порты, фильтры)".to_string()
+                    } else {
+                        format!(
+                            "общие параметры группы «{}» (порты)",
+                            failure.instance_group
+                        )
+                    }
+                },
+                describe_source,
+            );
+            format!("- {source}: {}", failure.output)
+        }));
+        lines.join("\n")
+    }
+}
+
+/// Dry-runs the full argument vector and, if winws rejects it, each `--new`
+/// segment on its own after the shared arguments to find the strategies at
+/// fault. `run` returns `Ok(Some(output))` when winws rejects the arguments
+/// and `Err` when it could not be run at all.
+fn preflight_segments(
+    group: &str,
+    base: &[String],
+    segments: &[(ProfileSource, Vec<String>)],
+    run: impl Fn(&[String]) -> Result<Option<String>, String>,
+) -> Result<Vec<PreflightFailure>, String> {
+    let mut full = base.to_vec();
+    for (index, (_, args)) in segments.iter().enumerate() {
+        if index > 0 {
+            full.push(PROFILE_SEPARATOR_ARG.to_string());
+        }
+        full.extend(args.iter().cloned());
+    }
+
+    let Some(output) = run(&full)? else {
+        return Ok(Vec::new());
+    };
+
+    let failure = |profile, output| PreflightFailure {
+        instance_group: group.to_string(),
+        profile,
+        output,
+    };
+    if let Some(output) = run(base)? {
+        return Ok(vec![failure(None, output)]);
+    }
+
+    let mut failures = Vec::new();
+    for (source, args) in segments {
+        let segment_args = [base, args.as_slice()].concat();
+        if let Some(output) = run(&segment_args)? {
+            failures.push(failure(Some(source.clone()), output));
+        }
+    }
+    if failures.is_empty() {
+        // Every profile passes on its own; only the combination fails.
+        failures.push(failure(None, output));
+    }
+    Ok(failures)
+}
+
+/// Dry-runs the winws.exe of one instance group; `instance` is the group's
+/// part of the config as returned by `instance_config`.
+pub(crate) fn preflight_instance(
+    group: &str,
+    instance: &AppConfig,
+    run: impl Fn(&[String]) -> Result<Option<String>, String>,
+) -> Result<Vec<PreflightFailure>, String> {
+    let segments: Vec<(ProfileSource, Vec<String>)> = strategy_segments(instance)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (category, strategy, args))| {
+            let source = ProfileSource {
+                position: index + 1,
+                instance_group: group.to_string(),
+                category_id: category.id.clone(),
+                category_name: category.name.clone(),
+                strategy_id: strategy.id.clone(),
+                strategy_name: strategy.name.clone(),
+            };
+            (source, args)
+        })
+        .collect();
+    preflight_segments(group, &build_winws_base_args(instance), &segments, run)
+}
+
+pub(crate) fn preflight_winws_args(
+    config: &AppConfig,
+    run: impl Fn(&[String]) -> Result<Option<String>, String>,
+) -> Result<WinwsPreflightReport, String> {
+    let mut failures = Vec::new();
+    for group in instance_groups(config) {
+        failures.extend(preflight_instance(
+            &group,
+            &instance_config(config, &group),
+            &run,
+        )?);
+    }
+    Ok(WinwsPreflightReport { failures })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(position: usize, name: &str) -> ProfileSource {
+        ProfileSource {
+            position,
+            instance_group: "main".to_string(),
+            category_id: "category".to_string(),
+            category_name: "Category".to_string(),
+            strategy_id: name.to_lowercase(),
+            strategy_name: name.to_string(),
+        }
+    }
+
+    fn reject_bad_args(args: &[String]) -> Result<Option<String>, String> {
+        Ok(args
+            .iter()
+            .find(|arg| arg.starts_with("--bad"))
+            .map(|arg| format!("unrecognized option '{arg}'")))
+    }
+
+    #[test]
+    fn maps_rejected_arguments_to_their_strategy() {
+        let base = vec!["--wf-tcp=80,443".to_string()];
+        let segments = vec![
+            (source(1, "Good"), vec!["--filter-tcp=443".to_string()]),
+            (source(2, "Broken"), vec!["--bad-option".to_string()]),
+        ];
+
+        let failures = preflight_segments("main", &base, &segments, reject_bad_args).unwrap();
+
+        assert_eq!(
+            failures,
+            vec![PreflightFailure {
+                instance_group: "main".to_string(),
+                profile: Some(source(2, "Broken")),
+                output: "unrecognized option '--bad-option'".to_string(),
+            }]
+        );
+        let report = WinwsPreflightReport { failures };
+        assert!(!report.is_clean());
+        assert!(report.describe().contains("профиль #2 «Category / Broken»"));
+    }
+
+    #[test]
+    fn blames_shared_arguments_and_stops_on_run_errors() {
+        let segments = vec![(source(1, "Good"), vec!["--filter-tcp=443".to_string()])];
+
+        let failures = preflight_segments("main", &[], &segments, reject_bad_args).unwrap();
+        assert!(failures.is_empty());
+
+        let base = vec!["--bad-port".to_string()];
+        let failures = preflight_segments("udp", &base, &segments, reject_bad_args).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].profile, None);
+        let report = WinwsPreflightReport { failures };
+        assert!(report.describe().contains("общие параметры группы «udp»"));
+
+        assert!(
+            preflight_segments("main", &base, &segments, |_| Err(
+                "winws.exe not found".to_string()
+            ))
+            .is_err()
+        );
+    }
+}
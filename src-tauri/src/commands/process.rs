@@ -1,10 +1,18 @@
-use super::config::{get_managed_resources_dir, get_runtime_data_dir};
+use super::config::{
+    AppConfig, AppState, DEFAULT_INSTANCE_GROUP, build_instance_args, check_placeholder_resolution,
+    current_config, get_managed_resources_dir, get_runtime_data_dir, instance_config,
+};
+use super::debug_log::{is_notable_debug_line, record_debug_line, reset_debug_counters};
+use super::preflight::{WinwsPreflightReport, preflight_instance, preflight_winws_args};
 use duct::{Expression, Handle, cmd};
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Duration;
 use sysinfo::{Pid, ProcessesToUpdate, System};
+use tauri::{AppHandle, Emitter, Manager};
 
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
@@ -54,16 +62,127 @@ const STILL_ACTIVE_EXIT_CODE: u32 = 259;
 #[cfg(windows)]
 const DELETE_ACCESS_MASK: u32 = 0x0001_0000;
 const WINWS_PROCESS_NAME: &str = "winws.exe";
+/// winws exits almost immediately when it rejects its arguments or cannot
+/// open WinDivert, so surviving this long counts as a successful start.
+const WINWS_STARTUP_GRACE: Duration = Duration::from_millis(1_500);
+const WINWS_LOG_MAX_BYTES: u64 = 1024 * 1024;
+const WINWS_LOG_FILES: usize = 3;
+const WINWS_OUTPUT_TAIL_LINES: usize = 20;
+const SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const RESTART_BACKOFF_BASE: Duration = Duration::from_secs(1);
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// winws.exe processes started or recovered by this app, one per instance
+/// group. A group is removed once its process is stopped or crashed.
+static INSTANCES: Mutex<BTreeMap<String, WinwsInstance>> = Mutex::new(BTreeMap::new());
+static RELOAD_LOCK: Mutex<()> = Mutex::new(());
+static OUTPUT_TAILS: Mutex<BTreeMap<String, VecDeque<String>>> = Mutex::new(BTreeMap::new());
+static SUPERVISION_EPOCH: AtomicU64 = AtomicU64::new(0);
+static SUPERVISOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+struct WinwsInstance {
+    pid: u32,
+    handle: Option<Handle>,
+    /// Arguments it was started with; `None` when it was recovered from a
+    /// previous session.
+    args: Option<Vec<String>>,
+    /// Set once it survived its startup; only then does the supervisor treat
+    /// an exit as a crash.
+    supervised: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WinwsReloadOutcome {
+    Unchanged,
+    /// The group was not running before, e.g. a category moved into it.
+    Started,
+    Restarted,
+    /// The group no longer has active profiles.
+    Stopped,
+    /// The new arguments were rejected; winws runs with the previous ones.
+    RolledBack,
+    /// Neither the new nor the previous arguments could be started.
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WinwsExitedEvent {
+    pub instance_group: String,
+    pub pid: u32,
+    pub exit_code: Option<i32>,
+    pub output_tail: Vec<String>,
+    pub restarting: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WinwsReloadEvent {
+    pub instance_group: String,
+    pub outcome: WinwsReloadOutcome,
+    pub pid: Option<u32>,
+    pub added_args: Vec<String>,
+    pub removed_args: Vec<String>,
+    pub error: Option<String>,
+    /// Whether any winws.exe still runs once the reload is done.
+    pub connected: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WinwsOutputEvent {
+    pub instance_group: String,
+    pub line: String,
+}
 
-static RUNNING_PID: AtomicU32 = AtomicU32::new(0);
-static RUNNING_HANDLE: Mutex<Option<Handle>> = Mutex::new(None);
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WinwsInstanceStatus {
+    pub instance_group: String,
+    pub pid: u32,
+    pub running: bool,
+}
 
 fn winws_binary_path() -> PathBuf {
     get_managed_resources_dir().join(WINWS_PROCESS_NAME)
 }
 
-fn winws_pid_path() -> PathBuf {
-    get_runtime_data_dir().join("winws.pid")
+/// `winws.<extension>` for the default group, which keeps the file names
+/// of the single-instance days, and `winws-<group>.<extension>` otherwise.
+fn instance_file_name(group: &str, extension: &str) -> String {
+    if group == DEFAULT_INSTANCE_GROUP {
+        format!("winws.{extension}")
+    } else {
+        format!("winws-{group}.{extension}")
+    }
+}
+
+fn winws_pid_path(group: &str) -> PathBuf {
+    get_runtime_data_dir().join(instance_file_name(group, "pid"))
+}
+
+/// Instance group a PID file belongs to; the reverse of `instance_file_name`.
+fn pid_file_group(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    if name == "winws.pid" {
+        return Some(DEFAULT_INSTANCE_GROUP.to_string());
+    }
+    name.strip_prefix("winws-")?
+        .strip_suffix(".pid")
+        .filter(|group| !group.is_empty())
+        .map(str::to_string)
+}
+
+fn winws_pid_files() -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(get_runtime_data_dir()) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter_map(|path| Some((pid_file_group(&path)?, path)))
+        .collect()
 }
 
 fn write_pid_file(path: &Path, pid: u32) -> Result<(), String> {
@@ -101,7 +220,7 @@ fn kill_process_by_pid_taskkill(pid: u32) -> Result<(), String> {
     let output = std::process::Command::new("taskkill")
         .args(["/PID", &pid.to_string(), "/T", "/F"])
         .output()
-        .map_err(|error| format!("Failed to launch taskkill for process {pid}: {error}"))?;
+        .map_err(|error| format!("Не удалось запустить taskkill для процесса {pid}: {error}"))?;
 
     if output.status.success() {
         Ok(())
@@ -113,9 +232,11 @@ fn kill_process_by_pid_taskkill(pid: u32) -> Result<(), String> {
         } else if !stdout.is_empty() {
             stdout
         } else {
-            format!("exit code {}", output.status)
+            format!("статус {}", output.status)
         };
-        Err(format!("taskkill failed for process {pid}: {detail}"))
+        Err(format!(
+            "taskkill не смог завершить процесс {pid}: {detail}"
+        ))
     }
 }
 
@@ -219,25 +340,25 @@ fn terminate_process_by_pid(pid: u32) -> Result<(), String> {
                         return Ok(());
                     } else if wait_result == WAIT_TIMEOUT {
                         errors.push(format!(
-                            "Process {pid} did not exit within 2000 ms after WinAPI termination"
+                            "Процесс {pid} не завершился за 2000 мс после завершения через WinAPI"
                         ));
                     } else if wait_result == WAIT_FAILED {
                         errors.push(format!(
-                            "Failed to wait for process {pid} termination after WinAPI kill"
+                            "Не удалось дождаться завершения процесса {pid} после завершения через WinAPI"
                         ));
                     } else {
                         errors.push(format!(
-                            "Unexpected wait result while terminating process {pid}: {:?}",
+                            "Неожиданный результат ожидания при завершении процесса {pid}: {:?}",
                             wait_result
                         ));
                     }
                 }
                 Err(error) => {
                     let _ = CloseHandle(handle);
-                    errors.push(format!("Failed to terminate process {pid}: {error}"));
+                    errors.push(format!("Не удалось завершить процесс {pid}: {error}"));
                 }
             },
-            Err(error) => errors.push(format!("Failed to open process {pid}: {error}")),
+            Err(error) => errors.push(format!("Не удалось открыть процесс {pid}: {error}")),
         }
     }
 
@@ -250,10 +371,10 @@ fn terminate_process_by_pid(pid: u32) -> Result<(), String> {
             std::thread::sleep(Duration::from_millis(100));
         }
         errors.push(format!(
-            "Process {pid} did not exit within 5000 ms after sysinfo kill"
+            "Процесс {pid} не завершился за 5000 мс после завершения через sysinfo"
         ));
     } else {
-        errors.push(format!("sysinfo failed to kill process {pid}"));
+        errors.push(format!("sysinfo не смог завершить процесс {pid}"));
     }
 
     match kill_process_by_pid_taskkill(pid) {
@@ -266,7 +387,7 @@ fn terminate_process_by_pid(pid: u32) -> Result<(), String> {
                 std::thread::sleep(Duration::from_millis(100));
             }
             errors.push(format!(
-                "Process {pid} did not exit within 5000 ms after taskkill fallback"
+                "Процесс {pid} не завершился за 5000 мс после taskkill"
             ));
         }
         Err(error) => errors.push(error),
@@ -342,7 +463,7 @@ fn find_expected_winws_pid() -> Option<u32> {
 fn stop_and_delete_service(service_name: &str) -> Result<(), String> {
     unsafe {
         let manager = OpenSCManagerW(None, None, SC_MANAGER_CONNECT)
-            .map_err(|e| format!("Failed to open Service Control Manager: {e}"))?;
+            .map_err(|e| format!("Не удалось открыть Service Control Manager: {e}"))?;
 
         let service_name_w = to_wide(service_name);
         let service = OpenServiceW(
@@ -374,7 +495,7 @@ fn stop_and_delete_service(service_name: &str) -> Result<(), String> {
         match delete_result {
             Ok(()) => Ok(()),
             Err(e) if is_benign_service_delete_error(e.code().0) => Ok(()),
-            Err(e) => Err(format!("Failed to delete service {service_name}: {e}")),
+            Err(e) => Err(format!("Не удалось удалить службу {service_name}: {e}")),
         }
     }
 }
@@ -396,7 +517,7 @@ fn read_tcp1323_opts() -> Result<u32, String> {
 
         if open_result != WIN32_ERROR(0) {
             return Err(format!(
-                "Failed to open TCP parameters registry key: {open_result:?}"
+                "Не удалось открыть раздел реестра с параметрами TCP: {open_result:?}"
             ));
         }
 
@@ -418,7 +539,7 @@ fn read_tcp1323_opts() -> Result<u32, String> {
         } else if result == ERROR_FILE_NOT_FOUND {
             Ok(0)
         } else {
-            Err(format!("Failed to read Tcp1323Opts: {result:?}"))
+            Err(format!("Не удалось прочитать Tcp1323Opts: {result:?}"))
         }
     }
 }
@@ -444,7 +565,7 @@ fn write_tcp1323_opts(value: u32) -> Result<(), String> {
 
         if create_result != WIN32_ERROR(0) {
             return Err(format!(
-                "Failed to open/create TCP parameters registry key: {create_result:?}"
+                "Не удалось открыть или создать раздел реестра с параметрами TCP: {create_result:?}"
             ));
         }
 
@@ -462,83 +583,546 @@ fn write_tcp1323_opts(value: u32) -> Result<(), String> {
         if result == WIN32_ERROR(0) {
             Ok(())
         } else {
-            Err(format!("Failed to write Tcp1323Opts: {result:?}"))
+            Err(format!("Не удалось записать Tcp1323Opts: {result:?}"))
         }
     }
 }
 
-pub fn set_running_pid(pid: u32) {
-    RUNNING_PID.store(pid, Ordering::SeqCst);
-    if pid == 0 {
-        let _ = clear_pid_file(&winws_pid_path());
-    } else {
-        let _ = write_pid_file(&winws_pid_path(), pid);
+fn insert_instance(group: &str, instance: WinwsInstance) {
+    let pid = instance.pid;
+    if let Ok(mut instances) = INSTANCES.lock() {
+        instances.insert(group.to_string(), instance);
     }
+    let _ = write_pid_file(&winws_pid_path(group), pid);
+}
+
+fn remove_instance(group: &str) -> Option<WinwsInstance> {
+    let instance = INSTANCES.lock().ok()?.remove(group);
+    let _ = clear_pid_file(&winws_pid_path(group));
+    instance
+}
+
+fn is_instance_running(group: &str) -> bool {
+    INSTANCES
+        .lock()
+        .is_ok_and(|instances| instances.contains_key(group))
+}
+
+/// PIDs of every instance, default group first.
+fn instance_pids() -> Vec<(String, u32)> {
+    let Ok(instances) = INSTANCES.lock() else {
+        return Vec::new();
+    };
+    let mut pids: Vec<(String, u32)> = instances
+        .iter()
+        .map(|(group, instance)| (group.clone(), instance.pid))
+        .collect();
+    pids.sort_by_key(|(group, _)| group != DEFAULT_INSTANCE_GROUP);
+    pids
+}
+
+fn output_tail(group: &str) -> Vec<String> {
+    OUTPUT_TAILS
+        .lock()
+        .ok()
+        .and_then(|tails| tails.get(group).map(|tail| tail.iter().cloned().collect()))
+        .unwrap_or_default()
 }
 
 pub(crate) fn cleanup_orphaned_winws_on_startup() -> Result<(), String> {
-    let pid_path = winws_pid_path();
-    let Some(pid) = read_pid_file(&pid_path) else {
+    let pid_paths = winws_pid_files();
+    if pid_paths.is_empty() {
         return Ok(());
-    };
+    }
 
-    if is_process_running_by_pid(pid) {
-        terminate_process_by_pid(pid)?;
+    for (_, pid_path) in pid_paths {
+        if let Some(pid) = read_pid_file(&pid_path)
+            && is_process_running_by_pid(pid)
+        {
+            terminate_process_by_pid(pid)?;
+        }
+        clear_pid_file(&pid_path)?;
     }
 
-    RUNNING_PID.store(0, Ordering::SeqCst);
-    clear_pid_file(&pid_path)?;
+    if let Ok(mut instances) = INSTANCES.lock() {
+        instances.clear();
+    }
     kill_windivert_service()?;
     Ok(())
 }
 
-#[tauri::command]
-pub fn start_winws(args: Vec<String>, tcp_ports: String, udp_ports: String) -> Result<u32, String> {
+/// Appends lines to `path`, moving it to `path.1` (and older copies further
+/// down to `path.WINWS_LOG_FILES`) once it grows past `WINWS_LOG_MAX_BYTES`.
+struct RotatingLog {
+    path: PathBuf,
+    file: Option<std::fs::File>,
+    written: u64,
+}
+
+impl RotatingLog {
+    fn open(path: PathBuf) -> Self {
+        let file = Self::open_file(&path);
+        let written = file
+            .as_ref()
+            .and_then(|file| file.metadata().ok())
+            .map_or(0, |metadata| metadata.len());
+        Self {
+            path,
+            file,
+            written,
+        }
+    }
+
+    fn open_file(path: &Path) -> Option<std::fs::File> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).ok()?;
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .ok()
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) {
+        self.file = None;
+        for index in (1..WINWS_LOG_FILES).rev() {
+            let _ = std::fs::rename(self.rotated_path(index), self.rotated_path(index + 1));
+        }
+        let _ = std::fs::rename(&self.path, self.rotated_path(1));
+        self.file = Self::open_file(&self.path);
+        self.written = 0;
+    }
+
+    fn write_line(&mut self, line: &str) {
+        use std::io::Write;
+
+        let len = line.len() as u64 + 1;
+        if self.written > 0 && self.written + len > WINWS_LOG_MAX_BYTES {
+            self.rotate();
+        }
+        if let Some(file) = self.file.as_mut()
+            && writeln!(file, "{line}").is_ok()
+        {
+            self.written += len;
+        }
+    }
+}
+
+fn winws_log_path(group: &str) -> PathBuf {
+    get_runtime_data_dir()
+        .join("logs")
+        .join(instance_file_name(group, "log"))
+}
+
+/// Copies winws output into the group's rotating log, keeps the last lines
+/// for startup errors and forwards them as `winws-output` events. With
+/// `--debug` only lines reporting a problem leave the log file. The thread
+/// ends when the pipe closes, i.e. once winws exits and its handle is dropped.
+fn forward_winws_output(
+    app: AppHandle,
+    reader: os_pipe::PipeReader,
+    group: String,
+    pid: u32,
+    debug: bool,
+) {
+    std::thread::spawn(move || {
+        use std::io::BufRead;
+
+        let mut log = RotatingLog::open(winws_log_path(&group));
+        log.write_line(&format!("--- winws.exe started, PID {pid} ---"));
+        let mut reader = std::io::BufReader::new(reader);
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            match reader.read_until(b'\n', &mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            let line = String::from_utf8_lossy(&buffer).trim_end().to_string();
+            if line.is_empty() {
+                continue;
+            }
+
+            log.write_line(&line);
+            // Per-connection debug lines only go to the log file and the
+            // counters; streaming them would flood the UI log and push the
+            // startup errors out of the tail.
+            if record_debug_line(&group, &line) || (debug && !is_notable_debug_line(&line)) {
+                continue;
+            }
+            if let Ok(mut tails) = OUTPUT_TAILS.lock() {
+                let tail = tails.entry(group.clone()).or_default();
+                if tail.len() == WINWS_OUTPUT_TAIL_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(line.clone());
+            }
+            let _ = app.emit(
+                "winws-output",
+                WinwsOutputEvent {
+                    instance_group: group.clone(),
+                    line,
+                },
+            );
+        }
+    });
+}
+
+pub(crate) fn spawn_winws(app: &AppHandle, group: &str, args: Vec<String>) -> Result<u32, String> {
     let winws_path = winws_binary_path();
+    let args_snapshot = args.clone();
+    let debug = args.iter().any(|arg| arg.starts_with("--debug"));
 
     if !winws_path.exists() {
-        return Err("winws.exe not found. Please download binaries first.".to_string());
+        return Err("winws.exe не найден. Сначала скачайте бинарные файлы.".to_string());
     }
 
-    let mut full_args: Vec<String> = vec![
-        format!("--wf-tcp={}", tcp_ports),
-        format!("--wf-udp={}", udp_ports),
-    ];
-    full_args.extend(args);
-
-    let handle = configure_expression(cmd(winws_path.to_string_lossy().into_owned(), full_args))
+    let (reader, writer) =
+        os_pipe::pipe().map_err(|e| format!("Не удалось создать канал вывода winws.exe: {e}"))?;
+    let stderr_writer = writer
+        .try_clone()
+        .map_err(|e| format!("Не удалось создать канал вывода winws.exe: {e}"))?;
+    let handle = configure_expression(cmd(winws_path.to_string_lossy().into_owned(), args))
+        .stdout_file(writer)
+        .stderr_file(stderr_writer)
+        .unchecked()
         .start()
-        .map_err(|e| format!("Failed to start winws.exe: {e}"))?;
+        .map_err(|e| format!("Не удалось запустить winws.exe: {e}"))?;
     let pid = handle
         .pids()
         .into_iter()
         .next()
-        .ok_or_else(|| "Failed to get winws.exe PID from duct handle".to_string())?;
+        .ok_or_else(|| "Не удалось получить PID winws.exe".to_string())?;
+
+    if let Ok(mut tails) = OUTPUT_TAILS.lock() {
+        tails.remove(group);
+    }
+    reset_debug_counters(group);
+    forward_winws_output(app.clone(), reader, group.to_string(), pid, debug);
 
-    let mut running_handle = RUNNING_HANDLE.lock().map_err(|e| e.to_string())?;
-    *running_handle = Some(handle);
-    set_running_pid(pid);
+    insert_instance(
+        group,
+        WinwsInstance {
+            pid,
+            handle: Some(handle),
+            args: Some(args_snapshot),
+            supervised: false,
+        },
+    );
 
     Ok(pid)
 }
 
+/// Polls the group's freshly started winws.exe for `WINWS_STARTUP_GRACE` and
+/// returns its exit status if it quit in the meantime.
+fn startup_exit_status(group: &str) -> Option<String> {
+    let deadline = std::time::Instant::now() + WINWS_STARTUP_GRACE;
+    loop {
+        {
+            let instances = INSTANCES.lock().ok()?;
+            let handle = instances
+                .get(group)
+                .and_then(|instance| instance.handle.as_ref());
+            match handle.map(Handle::try_wait) {
+                Some(Ok(None)) => {}
+                Some(Ok(Some(output))) => return Some(format!("{:?}", output.status)),
+                Some(Err(error)) => return Some(error.to_string()),
+                None => return Some("дескриптор процесса потерян".to_string()),
+            }
+        }
+        if std::time::Instant::now() >= deadline {
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+fn spawn_winws_verified(app: &AppHandle, group: &str, args: Vec<String>) -> Result<u32, String> {
+    let pid = spawn_winws(app, group, args)?;
+    let Some(status) = startup_exit_status(group) else {
+        if let Ok(mut instances) = INSTANCES.lock()
+            && let Some(instance) = instances.get_mut(group)
+        {
+            instance.supervised = true;
+        }
+        return Ok(pid);
+    };
+
+    // Give the output thread a moment to drain what winws printed on exit.
+    std::thread::sleep(Duration::from_millis(200));
+    let _ = stop_winws_process(group);
+    let tail = output_tail(group).join("\n");
+    if tail.is_empty() {
+        Err(format!(
+            "winws.exe завершился сразу после запуска ({status})"
+        ))
+    } else {
+        Err(format!(
+            "winws.exe завершился сразу после запуска ({status}):\n{tail}"
+        ))
+    }
+}
+
+/// Runs winws.exe with `--dry-run`, which parses the arguments, loads the
+/// files they reference and exits without opening WinDivert. Returns what
+/// winws printed when it rejects the arguments.
+fn dry_run_winws(args: &[String]) -> Result<Option<String>, String> {
+    let winws_path = winws_binary_path();
+    if !winws_path.exists() {
+        return Err("winws.exe не найден. Сначала скачайте бинарные файлы.".to_string());
+    }
+
+    let mut dry_run_args = vec!["--dry-run".to_string()];
+    dry_run_args.extend(args.iter().cloned());
+    let output = configure_expression(cmd(winws_path.to_string_lossy().into_owned(), dry_run_args))
+        .stderr_to_stdout()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .map_err(|e| format!("Не удалось запустить winws.exe --dry-run: {e}"))?;
+    if output.status.success() {
+        return Ok(None);
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(Some(if text.is_empty() {
+        format!("{:?}", output.status)
+    } else {
+        text
+    }))
+}
+
+/// Checks every instance of `config` with a winws dry run and turns a
+/// rejection into an error naming the strategies at fault.
+fn preflight_config(config: &AppConfig) -> Result<(), String> {
+    let report = preflight_winws_args(config, dry_run_winws)?;
+    if report.is_clean() {
+        Ok(())
+    } else {
+        Err(report.describe())
+    }
+}
+
+fn diff_args(previous: &[String], next: &[String]) -> (Vec<String>, Vec<String>) {
+    let added = next
+        .iter()
+        .filter(|arg| !previous.contains(arg))
+        .cloned()
+        .collect();
+    let removed = previous
+        .iter()
+        .filter(|arg| !next.contains(arg))
+        .cloned()
+        .collect();
+    (added, removed)
+}
+
+/// Brings one instance group in line with `config`: restarts its winws.exe
+/// when the argument vector changed, going back to the previous arguments if
+/// the new ones fail to start, or starts it if the group was not running.
+/// `running` is the group's current PID and arguments.
+fn reload_instance(
+    app: &AppHandle,
+    config: &AppConfig,
+    group: &str,
+    running: Option<(u32, Option<Vec<String>>)>,
+    next: Vec<String>,
+) -> WinwsReloadEvent {
+    let running_pid = running.as_ref().map(|(pid, _)| *pid);
+    let previous = running.and_then(|(_, args)| args);
+    let (added_args, removed_args) = diff_args(previous.as_deref().unwrap_or_default(), &next);
+    let event = |outcome, pid, error| WinwsReloadEvent {
+        instance_group: group.to_string(),
+        outcome,
+        pid,
+        added_args: added_args.clone(),
+        removed_args: removed_args.clone(),
+        error,
+        connected: true,
+    };
+    // The running winws.exe, if any, is left untouched.
+    let rejected = |error| match running_pid {
+        Some(pid) => event(WinwsReloadOutcome::RolledBack, Some(pid), Some(error)),
+        None => event(WinwsReloadOutcome::Failed, None, Some(error)),
+    };
+
+    if previous.as_ref() == Some(&next) {
+        return event(WinwsReloadOutcome::Unchanged, running_pid, None);
+    }
+
+    let placeholder_report = check_placeholder_resolution(config);
+    if !placeholder_report.is_clean() {
+        return rejected(placeholder_report.describe());
+    }
+    match preflight_instance(group, &instance_config(config, group), dry_run_winws) {
+        Ok(failures) if failures.is_empty() => {}
+        Ok(failures) => return rejected(WinwsPreflightReport { failures }.describe()),
+        Err(error) => return rejected(error),
+    }
+    if running_pid.is_some()
+        && let Err(error) = stop_winws_process(group)
+    {
+        return rejected(error);
+    }
+
+    match spawn_winws_verified(app, group, next) {
+        Ok(pid) if running_pid.is_none() => event(WinwsReloadOutcome::Started, Some(pid), None),
+        Ok(pid) => event(WinwsReloadOutcome::Restarted, Some(pid), None),
+        Err(error) => match previous.map(|args| spawn_winws_verified(app, group, args)) {
+            Some(Ok(pid)) => event(WinwsReloadOutcome::RolledBack, Some(pid), Some(error)),
+            Some(Err(rollback_error)) => event(
+                WinwsReloadOutcome::Failed,
+                None,
+                Some(format!("{error}; откат не удался: {rollback_error}")),
+            ),
+            None => event(WinwsReloadOutcome::Failed, None, Some(error)),
+        },
+    }
+}
+
+/// Applies `config` to the running winws instances group by group, so a
+/// change to one group never restarts the others. Groups that lost all
+/// their profiles are stopped, new ones started. Returns nothing when winws
+/// is not running. Every outcome except `Unchanged` is also emitted as
+/// `winws-reloaded`.
+pub(crate) fn reload_winws_with_config(
+    app: &AppHandle,
+    config: &AppConfig,
+) -> Result<Vec<WinwsReloadEvent>, String> {
+    let _guard = RELOAD_LOCK.lock().map_err(|e| e.to_string())?;
+    if !is_winws_running() {
+        return Ok(Vec::new());
+    }
+
+    let mut running: BTreeMap<String, (u32, Option<Vec<String>>)> = INSTANCES
+        .lock()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|(group, instance)| (group.clone(), (instance.pid, instance.args.clone())))
+        .collect();
+
+    let mut events = Vec::new();
+    for (group, next) in build_instance_args(config) {
+        let current = running.remove(&group);
+        events.push(reload_instance(app, config, &group, current, next));
+    }
+    for (group, (pid, args)) in running {
+        let (outcome, pid, error) = match stop_winws_process(&group) {
+            Ok(()) => (WinwsReloadOutcome::Stopped, None, None),
+            Err(error) => (WinwsReloadOutcome::RolledBack, Some(pid), Some(error)),
+        };
+        events.push(WinwsReloadEvent {
+            instance_group: group,
+            outcome,
+            pid,
+            added_args: Vec::new(),
+            removed_args: args.unwrap_or_default(),
+            error,
+            connected: true,
+        });
+    }
+
+    let connected = is_winws_running();
+    for event in &mut events {
+        event.connected = connected;
+        if event.outcome != WinwsReloadOutcome::Unchanged {
+            let _ = app.emit("winws-reloaded", event.clone());
+        }
+    }
+    Ok(events)
+}
+
+/// Starts the winws.exe of every instance group and returns the PID of the
+/// first one. Either all of them start or none is left running.
+fn start_instances(app: &AppHandle, config: &AppConfig) -> Result<u32, String> {
+    preflight_config(config)?;
+
+    let mut first_pid = None;
+    for (group, args) in build_instance_args(config) {
+        match spawn_winws_verified(app, &group, args) {
+            Ok(pid) => {
+                first_pid.get_or_insert(pid);
+            }
+            Err(error) => {
+                let _ = stop_all_instances();
+                return Err(if group == DEFAULT_INSTANCE_GROUP {
+                    error
+                } else {
+                    format!("{group}: {error}")
+                });
+            }
+        }
+    }
+    first_pid.ok_or_else(|| "Нет экземпляров winws.exe для запуска".to_string())
+}
+
+#[tauri::command]
+pub async fn start_winws(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<u32, String> {
+    let config = current_config(&state)?;
+    let placeholder_report = check_placeholder_resolution(&config);
+    if !placeholder_report.is_clean() {
+        return Err(placeholder_report.describe());
+    }
+    tauri::async_runtime::spawn_blocking(move || start_instances(&app, &config))
+        .await
+        .map_err(|e| format!("Задача запуска winws завершилась с ошибкой: {e}"))?
+}
+
+/// Dry-runs the current config without starting winws.exe.
+#[tauri::command]
+pub async fn preflight_winws(
+    state: tauri::State<'_, AppState>,
+) -> Result<WinwsPreflightReport, String> {
+    let config = current_config(&state)?;
+    tauri::async_runtime::spawn_blocking(move || preflight_winws_args(&config, dry_run_winws))
+        .await
+        .map_err(|e| format!("Задача проверки аргументов winws завершилась с ошибкой: {e}"))?
+}
+
 #[tauri::command]
 pub fn stop_winws() -> Result<(), String> {
-    let pid = RUNNING_PID.load(Ordering::SeqCst);
-    let handle = RUNNING_HANDLE.lock().map_err(|e| e.to_string())?.take();
+    // A supervisor waiting out a restart backoff sees the new epoch and
+    // leaves winws.exe stopped.
+    SUPERVISION_EPOCH.fetch_add(1, Ordering::SeqCst);
+    stop_all_instances()
+}
 
-    if pid == 0 && handle.is_none() {
-        return Ok(());
+fn stop_all_instances() -> Result<(), String> {
+    let errors: Vec<String> = instance_pids()
+        .into_iter()
+        .filter_map(|(group, _)| stop_winws_process(&group).err())
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
     }
+}
+
+/// Stops the group's winws.exe. The WinDivert driver service is only removed
+/// once no other instance is left using it.
+fn stop_winws_process(group: &str) -> Result<(), String> {
+    let Some(mut instance) = remove_instance(group) else {
+        return Ok(());
+    };
+    let pid = instance.pid;
+    // Puts the instance back when it could not be stopped.
+    let restore = |instance: WinwsInstance| insert_instance(group, instance);
 
-    if let Some(handle) = handle {
+    if let Some(handle) = instance.handle.take() {
         let wait_result = match handle.try_wait() {
             Ok(wait_result) => wait_result,
             Err(error) => {
-                if let Ok(mut running_handle) = RUNNING_HANDLE.lock() {
-                    *running_handle = Some(handle);
-                }
-                return Err(format!("Failed to inspect winws.exe state: {error}"));
+                instance.handle = Some(handle);
+                restore(instance);
+                return Err(format!("Не удалось проверить состояние winws.exe: {error}"));
             }
         };
 
@@ -549,11 +1133,10 @@ pub fn stop_winws() -> Result<(), String> {
                     match terminate_process_by_pid(pid) {
                         Ok(()) => {}
                         Err(fallback_error) => {
-                            if let Ok(mut running_handle) = RUNNING_HANDLE.lock() {
-                                *running_handle = Some(handle);
-                            }
+                            instance.handle = Some(handle);
+                            restore(instance);
                             return Err(format!(
-                                "Failed to kill winws.exe: {error}; fallback by PID failed: {fallback_error}"
+                                "Не удалось завершить winws.exe: {error}; завершение по PID тоже не удалось: {fallback_error}"
                             ));
                         }
                     }
@@ -561,41 +1144,228 @@ pub fn stop_winws() -> Result<(), String> {
 
                 #[cfg(not(windows))]
                 {
-                    if let Ok(mut running_handle) = RUNNING_HANDLE.lock() {
-                        *running_handle = Some(handle);
-                    }
-                    return Err(format!("Failed to kill winws.exe: {error}"));
+                    instance.handle = Some(handle);
+                    restore(instance);
+                    return Err(format!("Не удалось завершить winws.exe: {error}"));
                 }
             }
             let _ = handle.wait_timeout(Duration::from_secs(2));
         }
     } else {
         #[cfg(windows)]
-        terminate_process_by_pid(pid)?;
+        {
+            if let Err(error) = terminate_process_by_pid(pid) {
+                restore(instance);
+                return Err(error);
+            }
+        }
 
         #[cfg(not(windows))]
         {
-            Command::new("kill")
-                .args(["-9", &pid.to_string()])
-                .output()
-                .map_err(|e| format!("Failed to kill winws.exe: {}", e))?;
+            if let Err(error) = Command::new("kill").args(["-9", &pid.to_string()]).output() {
+                restore(instance);
+                return Err(format!("Не удалось завершить winws.exe: {}", error));
+            }
         }
     }
 
-    set_running_pid(0);
-    kill_windivert_service()?;
+    if instance_pids().is_empty() {
+        kill_windivert_service()?;
+    }
 
     Ok(())
 }
 
-#[tauri::command]
-pub fn is_winws_running() -> bool {
-    let pid = RUNNING_PID.load(Ordering::SeqCst);
+struct CrashedInstance {
+    group: String,
+    pid: u32,
+    exit_code: Option<i32>,
+    args: Option<Vec<String>>,
+}
 
-    if pid == 0 {
-        return false;
+/// Takes a supervised winws.exe that exited on its own out of the instance
+/// list.
+fn take_crashed_winws() -> Option<CrashedInstance> {
+    let instances = INSTANCES.lock().ok()?;
+    let (group, exit_code) = instances.iter().find_map(|(group, instance)| {
+        if !instance.supervised {
+            return None;
+        }
+        match instance.handle.as_ref()?.try_wait() {
+            Ok(None) => None,
+            Ok(Some(output)) => Some((group.clone(), output.status.code())),
+            Err(_) => Some((group.clone(), None)),
+        }
+    })?;
+    drop(instances);
+
+    let instance = remove_instance(&group)?;
+    Some(CrashedInstance {
+        group,
+        pid: instance.pid,
+        exit_code,
+        args: instance.args,
+    })
+}
+
+fn restart_backoff(attempt: u32) -> Duration {
+    RESTART_BACKOFF_BASE
+        .saturating_mul(1 << attempt.min(5))
+        .min(RESTART_BACKOFF_MAX)
+}
+
+/// Restarts a group's winws.exe with the arguments it crashed with. Returns
+/// `None` when the user stopped the connection or a reload started the group
+/// again while a restart was pending.
+///
+/// The backoff is slept without `RELOAD_LOCK` so that reloads are not held
+/// up by a crash loop; the lock is only taken around each spawn.
+fn restart_crashed_winws(
+    app: &AppHandle,
+    crashed: &CrashedInstance,
+    epoch: u64,
+    limit: u32,
+) -> Option<Result<u32, String>> {
+    let Some(args) = crashed.args.clone() else {
+        return Some(Err("аргументы winws.exe неизвестны".to_string()));
+    };
+    let stopped = || SUPERVISION_EPOCH.load(Ordering::SeqCst) != epoch;
+
+    let mut last_error = "автоматический перезапуск отключён".to_string();
+    for attempt in 0..limit {
+        std::thread::sleep(restart_backoff(attempt));
+        let Ok(_guard) = RELOAD_LOCK.lock() else {
+            return Some(Err("блокировка перезапуска winws повреждена".to_string()));
+        };
+        if stopped() || is_instance_running(&crashed.group) {
+            return None;
+        }
+        match spawn_winws_verified(app, &crashed.group, args.clone()) {
+            Ok(_) if stopped() => {
+                let _ = stop_winws_process(&crashed.group);
+                return None;
+            }
+            Ok(pid) => return Some(Ok(pid)),
+            Err(error) => last_error = error,
+        }
+    }
+    Some(Err(last_error))
+}
+
+/// Watches the winws.exe instances started by this app. When one exits on
+/// its own the supervisor emits `winws-exited`, restarts it with exponential
+/// backoff up to `winwsRestartLimit` times and reports the result as
+/// `winws-reloaded`; the other instances keep running. The tray is switched
+/// back to disconnected only once no instance is left.
+pub(crate) fn start_winws_supervisor(app: AppHandle) {
+    if SUPERVISOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
     }
 
+    std::thread::spawn(move || {
+        loop {
+            std::thread::sleep(SUPERVISOR_POLL_INTERVAL);
+            let epoch = SUPERVISION_EPOCH.load(Ordering::SeqCst);
+            // A reload stops and starts winws itself; check again next tick.
+            let crashed = {
+                let Ok(_guard) = RELOAD_LOCK.try_lock() else {
+                    continue;
+                };
+                take_crashed_winws()
+            };
+            let Some(crashed) = crashed else {
+                continue;
+            };
+
+            std::thread::sleep(Duration::from_millis(200));
+            let limit = current_config(&app.state::<AppState>())
+                .map(|config| config.winws_restart_limit)
+                .unwrap_or_default();
+            let _ = app.emit(
+                "winws-exited",
+                WinwsExitedEvent {
+                    instance_group: crashed.group.clone(),
+                    pid: crashed.pid,
+                    exit_code: crashed.exit_code,
+                    output_tail: output_tail(&crashed.group),
+                    restarting: limit > 0,
+                },
+            );
+
+            let (outcome, pid, error) = match restart_crashed_winws(&app, &crashed, epoch, limit) {
+                None => continue,
+                Some(Ok(pid)) => (WinwsReloadOutcome::Restarted, Some(pid), None),
+                Some(Err(error)) => (WinwsReloadOutcome::Failed, None, Some(error)),
+            };
+            let connected = is_winws_running();
+            if !connected {
+                let _ = crate::sync_connected_ui(&app, false);
+            }
+            let _ = app.emit(
+                "winws-reloaded",
+                WinwsReloadEvent {
+                    instance_group: crashed.group,
+                    outcome,
+                    pid,
+                    added_args: Vec::new(),
+                    removed_args: Vec::new(),
+                    error,
+                    connected,
+                },
+            );
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn reload_winws(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<WinwsReloadEvent>, String> {
+    let config = current_config(&state)?;
+    tauri::async_runtime::spawn_blocking(move || reload_winws_with_config(&app, &config))
+        .await
+        .map_err(|e| format!("Задача перезагрузки winws завершилась с ошибкой: {e}"))?
+}
+
+/// Restarts one instance group with the current config while the others
+/// keep running.
+#[tauri::command]
+pub async fn restart_winws_instance(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    instance_group: String,
+) -> Result<u32, String> {
+    let config = current_config(&state)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let _guard = RELOAD_LOCK.lock().map_err(|e| e.to_string())?;
+        let args = build_instance_args(&config)
+            .into_iter()
+            .find_map(|(group, args)| (group == instance_group).then_some(args))
+            .ok_or_else(|| {
+                format!("В группе экземпляров «{instance_group}» нет активных профилей")
+            })?;
+        let placeholder_report = check_placeholder_resolution(&config);
+        if !placeholder_report.is_clean() {
+            return Err(placeholder_report.describe());
+        }
+        let failures = preflight_instance(
+            &instance_group,
+            &instance_config(&config, &instance_group),
+            dry_run_winws,
+        )?;
+        if !failures.is_empty() {
+            return Err(WinwsPreflightReport { failures }.describe());
+        }
+
+        stop_winws_process(&instance_group)?;
+        spawn_winws_verified(&app, &instance_group, args)
+    })
+    .await
+    .map_err(|e| format!("Задача перезапуска winws завершилась с ошибкой: {e}"))?
+}
+
+fn is_pid_running(pid: u32) -> bool {
     #[cfg(windows)]
     {
         is_process_running_by_pid(pid)
@@ -611,6 +1381,25 @@ pub fn is_winws_running() -> bool {
     }
 }
 
+#[tauri::command]
+pub fn is_winws_running() -> bool {
+    instance_pids()
+        .into_iter()
+        .any(|(_, pid)| pid != 0 && is_pid_running(pid))
+}
+
+#[tauri::command]
+pub fn get_winws_instances() -> Vec<WinwsInstanceStatus> {
+    instance_pids()
+        .into_iter()
+        .map(|(instance_group, pid)| WinwsInstanceStatus {
+            instance_group,
+            pid,
+            running: is_pid_running(pid),
+        })
+        .collect()
+}
+
 #[tauri::command]
 pub fn kill_windivert_service() -> Result<(), String> {
     #[cfg(windows)]
@@ -634,9 +1423,23 @@ pub fn kill_windivert_service() -> Result<(), String> {
     Ok(())
 }
 
+/// PID of the default group's winws.exe, or of the first other instance
+/// when the default group does not run.
 #[tauri::command]
 pub fn get_running_pid() -> u32 {
-    RUNNING_PID.load(Ordering::SeqCst)
+    instance_pids().first().map_or(0, |(_, pid)| *pid)
+}
+
+fn recover_instance(group: &str, pid: u32) {
+    insert_instance(
+        group,
+        WinwsInstance {
+            pid,
+            handle: None,
+            args: None,
+            supervised: false,
+        },
+    );
 }
 
 #[tauri::command]
@@ -664,23 +1467,44 @@ pub fn enable_tcp_timestamps() -> Result<(), String> {
     Ok(())
 }
 
+/// Adopts the winws.exe instances left running by a previous session, each
+/// into the group its PID file names, and returns the PID shown in the UI.
 #[tauri::command]
 pub fn check_and_recover_orphan() -> Option<u32> {
+    let pid_files = winws_pid_files();
+    if pid_files.is_empty() && instance_pids().is_empty() {
+        // Without PID files the group is unknown; adopt a single winws.exe
+        // into the default group as before.
+        if let Some(pid) = find_orphaned_winws_pid() {
+            recover_instance(DEFAULT_INSTANCE_GROUP, pid);
+        }
+    }
+
+    for (group, path) in pid_files {
+        if is_instance_running(&group) {
+            continue;
+        }
+        match read_pid_file(&path).filter(|pid| is_pid_running(*pid)) {
+            Some(pid) => recover_instance(&group, pid),
+            None => {
+                let _ = clear_pid_file(&path);
+            }
+        }
+    }
+
+    instance_pids().first().map(|(_, pid)| *pid)
+}
+
+fn find_orphaned_winws_pid() -> Option<u32> {
     #[cfg(windows)]
     {
-        let pid = find_expected_winws_pid()?;
-        set_running_pid(pid);
-        Some(pid)
+        find_expected_winws_pid()
     }
 
     #[cfg(not(windows))]
     {
         let output = Command::new("pgrep").arg("winws").output().ok()?;
         let stdout = String::from_utf8_lossy(&output.stdout);
-        if let Ok(pid) = stdout.trim().parse::<u32>() {
-            set_running_pid(pid);
-            return Some(pid);
-        }
-        None
+        stdout.trim().parse::<u32>().ok()
     }
 }
@@ -0,0 +1,275 @@
+use super::config::{
+    AppConfig, AppState, ProfileStrategyRef, StrategyProfile, current_config,
+    replace_current_config,
+};
+use tauri::Emitter;
+use uuid::Uuid;
+
+fn capture_profile(config: &AppConfig, id: String, name: String) -> StrategyProfile {
+    StrategyProfile {
+        id,
+        name,
+        active_strategies: config
+            .categories
+            .iter()
+            .flat_map(|category| {
+                category
+                    .strategies
+                    .iter()
+                    .filter(|strategy| strategy.active)
+                    .map(|strategy| ProfileStrategyRef {
+                        category_id: category.id.clone(),
+                        strategy_id: strategy.id.clone(),
+                    })
+            })
+            .collect(),
+        active_filter_ids: config
+            .filters
+            .iter()
+            .filter(|filter| filter.active)
+            .map(|filter| filter.id.clone())
+            .collect(),
+        list_mode: config.list_mode,
+        global_ports: config.global_ports.clone(),
+    }
+}
+
+fn apply_profile(config: &mut AppConfig, profile: &StrategyProfile) {
+    for category in &mut config.categories {
+        for strategy in &mut category.strategies {
+            strategy.active = profile.active_strategies.iter().any(|reference| {
+                reference.category_id == category.id && reference.strategy_id == strategy.id
+            });
+        }
+    }
+    for filter in &mut config.filters {
+        filter.active = profile.active_filter_ids.contains(&filter.id);
+    }
+    config.list_mode = profile.list_mode;
+    config.global_ports = profile.global_ports.clone();
+    config.active_profile_id = Some(profile.id.clone());
+}
+
+fn find_profile<'a>(config: &'a AppConfig, id: &str) -> Result<&'a StrategyProfile, String> {
+    config
+        .profiles
+        .iter()
+        .find(|profile| profile.id == id)
+        .ok_or_else(|| format!("Профиль «{id}» не найден"))
+}
+
+fn normalize_profile_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Название профиля не может быть пустым".to_string());
+    }
+    Ok(name.to_string())
+}
+
+/// Drops duplicate profiles and dangling references, and keeps the active
+/// profile in step with the live `active` flags so edits land in it.
+pub(crate) fn normalize_profiles(config: &mut AppConfig) -> bool {
+    let mut changed = false;
+
+    let mut seen_ids = Vec::new();
+    let profile_count = config.profiles.len();
+    config.profiles.retain(|profile| {
+        if profile.id.is_empty() || seen_ids.contains(&profile.id) {
+            return false;
+        }
+        seen_ids.push(profile.id.clone());
+        true
+    });
+    changed |= config.profiles.len() != profile_count;
+
+    if let Some(active_id) = config.active_profile_id.clone() {
+        match config
+            .profiles
+            .iter()
+            .position(|profile| profile.id == active_id)
+        {
+            Some(index) => {
+                let profile = &config.profiles[index];
+                let captured = capture_profile(config, profile.id.clone(), profile.name.clone());
+                let profile = &mut config.profiles[index];
+                if profile.active_strategies != captured.active_strategies
+                    || profile.active_filter_ids != captured.active_filter_ids
+                    || profile.list_mode != captured.list_mode
+                    || profile.global_ports != captured.global_ports
+                {
+                    *profile = captured;
+                    changed = true;
+                }
+            }
+            None => {
+                config.active_profile_id = None;
+                changed = true;
+            }
+        }
+    }
+
+    for profile in &mut config.profiles {
+        let strategy_count = profile.active_strategies.len();
+        profile.active_strategies.retain(|reference| {
+            config.categories.iter().any(|category| {
+                category.id == reference.category_id
+                    && category
+                        .strategies
+                        .iter()
+                        .any(|strategy| strategy.id == reference.strategy_id)
+            })
+        });
+        let filter_count = profile.active_filter_ids.len();
+        profile
+            .active_filter_ids
+            .retain(|id| config.filters.iter().any(|filter| &filter.id == id));
+        changed |= profile.active_strategies.len() != strategy_count
+            || profile.active_filter_ids.len() != filter_count;
+    }
+
+    changed
+}
+
+fn commit_profiles(
+    app: &tauri::AppHandle,
+    config: AppConfig,
+    state: &AppState,
+) -> Result<AppConfig, String> {
+    let config = replace_current_config(config, state)?;
+    crate::sync_list_mode_ui(app, config.list_mode)?;
+    crate::sync_profiles_ui(app, &config)?;
+    Ok(config)
+}
+
+/// Switches the live flags to the given profile. The frontend reconnects on
+/// `profile-changed`, which is also what tray switching relies on.
+#[tauri::command]
+pub fn switch_profile(
+    app: tauri::AppHandle,
+    id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let mut next = current_config(&state)?;
+    if next.active_profile_id.as_deref() == Some(id.as_str()) {
+        crate::sync_profiles_ui(&app, &next)?;
+        return Ok(next);
+    }
+
+    let profile = find_profile(&next, &id)?.clone();
+    apply_profile(&mut next, &profile);
+    let config = commit_profiles(&app, next, &state)?;
+    app.emit("profile-changed", &profile.name)
+        .map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+#[tauri::command]
+pub fn create_profile(
+    app: tauri::AppHandle,
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let name = normalize_profile_name(&name)?;
+    let mut next = current_config(&state)?;
+    let profile = capture_profile(&next, Uuid::new_v4().to_string(), name);
+    next.active_profile_id = Some(profile.id.clone());
+    next.profiles.push(profile);
+    commit_profiles(&app, next, &state)
+}
+
+#[tauri::command]
+pub fn rename_profile(
+    app: tauri::AppHandle,
+    id: String,
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let name = normalize_profile_name(&name)?;
+    let mut next = current_config(&state)?;
+    let profile = next
+        .profiles
+        .iter_mut()
+        .find(|profile| profile.id == id)
+        .ok_or_else(|| format!("Профиль «{id}» не найден"))?;
+    profile.name = name;
+    commit_profiles(&app, next, &state)
+}
+
+/// Removing the active profile keeps the current flags, just unbound.
+#[tauri::command]
+pub fn delete_profile(
+    app: tauri::AppHandle,
+    id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<AppConfig, String> {
+    let mut next = current_config(&state)?;
+    find_profile(&next, &id)?;
+    next.profiles.retain(|profile| profile.id != id);
+    if next.active_profile_id.as_deref() == Some(id.as_str()) {
+        next.active_profile_id = None;
+    }
+    commit_profiles(&app, next, &state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::config::ListMode;
+
+    #[test]
+    fn applying_profile_restores_captured_flags() {
+        let mut config = AppConfig {
+            list_mode: ListMode::Exclude,
+            ..AppConfig::default()
+        };
+        config.global_ports.udp = "443".to_string();
+        let profile = capture_profile(&config, "home".to_string(), "Home ISP".to_string());
+
+        for category in &mut config.categories {
+            for strategy in &mut category.strategies {
+                strategy.active = !strategy.active;
+            }
+        }
+        config.filters[0].active = !config.filters[0].active;
+        config.list_mode = ListMode::Ipset;
+        config.global_ports.udp = "1-65535".to_string();
+        config.profiles.push(profile.clone());
+
+        apply_profile(&mut config, &profile);
+        let reapplied = capture_profile(&config, profile.id.clone(), profile.name.clone());
+        assert_eq!(reapplied.active_strategies, profile.active_strategies);
+        assert_eq!(reapplied.active_filter_ids, profile.active_filter_ids);
+        assert_eq!(config.list_mode, ListMode::Exclude);
+        assert_eq!(config.global_ports.udp, "443");
+        assert_eq!(config.active_profile_id.as_deref(), Some("home"));
+    }
+
+    #[test]
+    fn normalize_tracks_active_profile_and_drops_dangling_refs() {
+        let mut config = AppConfig::default();
+        let mut stale = capture_profile(&config, "office".to_string(), "Office".to_string());
+        stale.active_strategies.push(ProfileStrategyRef {
+            category_id: "missing".to_string(),
+            strategy_id: "missing".to_string(),
+        });
+        let active = capture_profile(&config, "home".to_string(), "Home ISP".to_string());
+        config.profiles = vec![stale, active.clone(), active];
+        config.active_profile_id = Some("home".to_string());
+        config.list_mode = ListMode::Exclude;
+
+        assert!(normalize_profiles(&mut config));
+        assert_eq!(config.profiles.len(), 2);
+        assert!(
+            config.profiles[0]
+                .active_strategies
+                .iter()
+                .all(|reference| reference.category_id != "missing")
+        );
+        assert_eq!(config.profiles[1].list_mode, ListMode::Exclude);
+        assert!(!normalize_profiles(&mut config));
+
+        config.active_profile_id = Some("deleted".to_string());
+        assert!(normalize_profiles(&mut config));
+        assert_eq!(config.active_profile_id, None);
+    }
+}
@@ -0,0 +1,489 @@
+use super::config::{
+    AppConfig, AppState, Category, Placeholder, PlaceholderKind, Strategy, StrategyMetadata,
+    current_config, is_dynamic_placeholder, normalize_placeholder_path, placeholder_tokens,
+    placeholder_value, replace_current_config,
+};
+use super::history::snapshot_config_history;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use uuid::Uuid;
+
+const SHARE_CODE_PREFIX: &str = "zs";
+const SHARE_CODE_VERSION: u32 = 1;
+const SHARE_CHECKSUM_LEN: usize = 4;
+const MAX_SHARE_PAYLOAD_SIZE: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedStrategy {
+    pub name: String,
+    pub content: String,
+    #[serde(flatten)]
+    pub metadata: StrategyMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedPlaceholder {
+    pub name: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "PlaceholderKind::is_path")]
+    pub kind: PlaceholderKind,
+}
+
+/// What a share code carries. `category_name` is set when a whole category
+/// was shared, otherwise the code holds a single strategy.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SharePayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_name: Option<String>,
+    pub strategies: Vec<SharedStrategy>,
+    #[serde(default)]
+    pub placeholders: Vec<SharedPlaceholder>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareImportResult {
+    pub config: AppConfig,
+    pub category_id: String,
+    pub strategy_ids: Vec<String>,
+    pub added_placeholders: Vec<String>,
+    pub unresolved_placeholders: Vec<String>,
+}
+
+fn share_checksum(json: &[u8]) -> [u8; SHARE_CHECKSUM_LEN] {
+    let digest = Sha256::digest(json);
+    let mut checksum = [0; SHARE_CHECKSUM_LEN];
+    checksum.copy_from_slice(&digest[..SHARE_CHECKSUM_LEN]);
+    checksum
+}
+
+/// `zs1.` followed by URL-safe base64 of a short SHA-256 checksum of the JSON
+/// payload and the deflated payload itself.
+fn encode_payload(payload: &SharePayload) -> Result<String, String> {
+    let json = serde_json::to_vec(payload).map_err(|e| e.to_string())?;
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&json).map_err(|e| e.to_string())?;
+    let compressed = encoder.finish().map_err(|e| e.to_string())?;
+
+    let mut bytes = share_checksum(&json).to_vec();
+    bytes.extend(compressed);
+    Ok(format!(
+        "{SHARE_CODE_PREFIX}{SHARE_CODE_VERSION}.{}",
+        URL_SAFE_NO_PAD.encode(bytes)
+    ))
+}
+
+fn decode_payload(code: &str) -> Result<SharePayload, String> {
+    // Chats tend to wrap long codes, so whitespace anywhere is ignored.
+    let code = code
+        .chars()
+        .filter(|char| !char.is_whitespace())
+        .collect::<String>();
+    let (version, data) = code
+        .split_once('.')
+        .and_then(|(version, data)| Some((version.strip_prefix(SHARE_CODE_PREFIX)?, data)))
+        .ok_or_else(|| "Это не код для обмена".to_string())?;
+    if version.parse::<u32>().ok() != Some(SHARE_CODE_VERSION) {
+        return Err(format!(
+            "Неподдерживаемая версия кода для обмена: {version}"
+        ));
+    }
+
+    let bytes = URL_SAFE_NO_PAD
+        .decode(data)
+        .map_err(|_| "Код для обмена повреждён".to_string())?;
+    if bytes.len() <= SHARE_CHECKSUM_LEN {
+        return Err("Код для обмена повреждён".to_string());
+    }
+    let (checksum, compressed) = bytes.split_at(SHARE_CHECKSUM_LEN);
+
+    let mut json = Vec::new();
+    DeflateDecoder::new(compressed)
+        .take(MAX_SHARE_PAYLOAD_SIZE + 1)
+        .read_to_end(&mut json)
+        .map_err(|_| "Код для обмена повреждён".to_string())?;
+    if json.len() as u64 > MAX_SHARE_PAYLOAD_SIZE {
+        return Err("Код для обмена слишком большой".to_string());
+    }
+    if share_checksum(&json) != checksum {
+        return Err("Контрольная сумма кода для обмена не совпадает".to_string());
+    }
+
+    let payload: SharePayload =
+        serde_json::from_slice(&json).map_err(|e| format!("Некорректный код для обмена: {e}"))?;
+    validate_payload(&payload)?;
+    Ok(payload)
+}
+
+fn validate_payload(payload: &SharePayload) -> Result<(), String> {
+    if payload.strategies.is_empty() {
+        return Err("В коде для обмена нет стратегий".to_string());
+    }
+    if payload.category_name.is_none() && payload.strategies.len() > 1 {
+        return Err("Код стратегии содержит несколько стратегий".to_string());
+    }
+    if payload
+        .category_name
+        .as_deref()
+        .is_some_and(|name| name.trim().is_empty())
+    {
+        return Err("У категории из кода нет названия".to_string());
+    }
+    if payload
+        .strategies
+        .iter()
+        .any(|strategy| strategy.name.trim().is_empty())
+    {
+        return Err("У стратегии из кода нет названия".to_string());
+    }
+    Ok(())
+}
+
+fn shared_strategy(strategy: &Strategy) -> SharedStrategy {
+    SharedStrategy {
+        name: strategy.name.clone(),
+        content: strategy.content.clone(),
+        metadata: strategy.metadata.clone(),
+    }
+}
+
+fn build_payload(
+    config: &AppConfig,
+    category_id: &str,
+    strategy_id: Option<&str>,
+) -> Result<SharePayload, String> {
+    let category = config
+        .categories
+        .iter()
+        .find(|category| category.id == category_id)
+        .ok_or_else(|| format!("Категория «{category_id}» не найдена"))?;
+    let strategies = match strategy_id {
+        Some(strategy_id) => vec![shared_strategy(
+            category
+                .strategies
+                .iter()
+                .find(|strategy| strategy.id == strategy_id)
+                .ok_or_else(|| format!("Стратегия «{category_id}::{strategy_id}» не найдена"))?,
+        )],
+        None => category.strategies.iter().map(shared_strategy).collect(),
+    };
+
+    let mut placeholders = Vec::<SharedPlaceholder>::new();
+    for strategy in &strategies {
+        for name in placeholder_tokens(&strategy.content) {
+            if placeholders
+                .iter()
+                .any(|placeholder| placeholder.name == name)
+            {
+                continue;
+            }
+            if let Some(placeholder) = config
+                .placeholders
+                .iter()
+                .find(|placeholder| placeholder.name == name)
+            {
+                placeholders.push(SharedPlaceholder {
+                    name: placeholder.name.clone(),
+                    path: placeholder.value.clone(),
+                    kind: placeholder.kind,
+                });
+            }
+        }
+    }
+
+    Ok(SharePayload {
+        category_name: strategy_id.is_none().then(|| category.name.clone()),
+        strategies,
+        placeholders,
+    })
+}
+
+#[derive(Debug, Default)]
+struct PlaceholderRemap {
+    renames: Vec<(String, String)>,
+    added: Vec<String>,
+    unresolved: Vec<String>,
+}
+
+/// Points the shared `{{NAME}}` references at local placeholders: same name
+/// first, then same path. Unknown managed paths and valid typed values are
+/// added as new placeholders, anything else is left for the user to define.
+fn remap_placeholders(config: &mut AppConfig, payload: &SharePayload) -> PlaceholderRemap {
+    let mut remap = PlaceholderRemap::default();
+    let mut names = Vec::new();
+    for strategy in &payload.strategies {
+        for name in placeholder_tokens(&strategy.content) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+
+    for name in names {
+        if is_dynamic_placeholder(name)
+            || config
+                .placeholders
+                .iter()
+                .any(|placeholder| placeholder.name == name)
+        {
+            continue;
+        }
+        let Some(shared) = payload
+            .placeholders
+            .iter()
+            .find(|placeholder| placeholder.name == name)
+        else {
+            remap.unresolved.push(name.to_string());
+            continue;
+        };
+
+        if !shared.kind.is_path() {
+            match placeholder_value(shared.kind, &shared.path) {
+                Ok(value) => {
+                    config.placeholders.push(Placeholder {
+                        name: name.to_string(),
+                        value,
+                        kind: shared.kind,
+                        system: false,
+                        system_base_name: None,
+                        system_base_path: None,
+                        source_id: None,
+                    });
+                    remap.added.push(name.to_string());
+                }
+                Err(_) => remap.unresolved.push(name.to_string()),
+            }
+            continue;
+        }
+
+        let shared_path = normalize_placeholder_path(&shared.path);
+        if let Some(local) = config.placeholders.iter().find(|placeholder| {
+            placeholder.kind.is_path()
+                && (placeholder.value == shared.path
+                    || shared_path.is_some()
+                        && normalize_placeholder_path(&placeholder.value) == shared_path)
+        }) {
+            remap.renames.push((name.to_string(), local.name.clone()));
+        } else if let Some(path) = shared_path {
+            config.placeholders.push(Placeholder {
+                name: name.to_string(),
+                value: path,
+                kind: PlaceholderKind::Path,
+                system: false,
+                system_base_name: None,
+                system_base_path: None,
+                source_id: None,
+            });
+            remap.added.push(name.to_string());
+        } else {
+            remap.unresolved.push(name.to_string());
+        }
+    }
+
+    remap
+}
+
+fn import_payload(
+    config: &mut AppConfig,
+    payload: &SharePayload,
+    category_id: Option<&str>,
+) -> Result<(String, Vec<String>, PlaceholderRemap), String> {
+    let remap = remap_placeholders(config, payload);
+    let strategies = payload
+        .strategies
+        .iter()
+        .map(|shared| {
+            let mut content = shared.content.clone();
+            for (from, to) in &remap.renames {
+                content = content.replace(&format!("{{{{{from}}}}}"), &format!("{{{{{to}}}}}"));
+            }
+            Strategy {
+                id: Uuid::new_v4().to_string(),
+                name: shared.name.trim().to_string(),
+                content,
+                active: false,
+                metadata: shared.metadata.clone(),
+                system: false,
+                system_base_name: None,
+                system_base_content: None,
+                system_base_metadata: None,
+            }
+        })
+        .collect::<Vec<_>>();
+    let strategy_ids = strategies
+        .iter()
+        .map(|strategy| strategy.id.clone())
+        .collect();
+
+    let category_id = match (&payload.category_name, category_id) {
+        (Some(name), _) => {
+            let category = Category {
+                id: Uuid::new_v4().to_string(),
+                name: name.trim().to_string(),
+                strategies,
+                system: false,
+                system_base_name: None,
+                source_id: None,
+                instance_group: None,
+            };
+            let id = category.id.clone();
+            config.categories.push(category);
+            id
+        }
+        (None, Some(category_id)) => {
+            config
+                .categories
+                .iter_mut()
+                .find(|category| category.id == category_id)
+                .ok_or_else(|| format!("Категория «{category_id}» не найдена"))?
+                .strategies
+                .extend(strategies);
+            category_id.to_string()
+        }
+        (None, None) => {
+            return Err("Для импорта стратегии нужно выбрать категорию".to_string());
+        }
+    };
+
+    Ok((category_id, strategy_ids, remap))
+}
+
+/// Shares a single strategy when `strategy_id` is given, otherwise the whole category.
+#[tauri::command]
+pub fn encode_share_code(
+    category_id: String,
+    strategy_id: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let config = current_config(&state)?;
+    encode_payload(&build_payload(
+        &config,
+        &category_id,
+        strategy_id.as_deref(),
+    )?)
+}
+
+#[tauri::command]
+pub fn decode_share_code(code: String) -> Result<SharePayload, String> {
+    decode_payload(&code)
+}
+
+/// Strategy codes are added to `category_id`; category codes always create a new category.
+#[tauri::command]
+pub fn import_share_code(
+    code: String,
+    category_id: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<ShareImportResult, String> {
+    let payload = decode_payload(&code)?;
+    snapshot_config_history()?;
+    let mut next = current_config(&state)?;
+    let (category_id, strategy_ids, remap) =
+        import_payload(&mut next, &payload, category_id.as_deref())?;
+    Ok(ShareImportResult {
+        config: replace_current_config(next, &state)?,
+        category_id,
+        strategy_ids,
+        added_placeholders: remap.added,
+        unresolved_placeholders: remap.unresolved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn share_code_round_trips_and_rejects_damage() {
+        let config = AppConfig::default();
+        let category = &config.categories[0];
+        let payload = build_payload(&config, &category.id, None).unwrap();
+        let code = encode_payload(&payload).unwrap();
+        assert!(code.starts_with("zs1."));
+        assert!(
+            code.chars()
+                .all(|char| char.is_ascii_alphanumeric() || "-_.".contains(char))
+        );
+
+        let wrapped = format!("{}\n{}", &code[..20], &code[20..]);
+        assert_eq!(decode_payload(&wrapped).unwrap(), payload);
+        assert_eq!(
+            payload.category_name.as_deref(),
+            Some(category.name.as_str())
+        );
+        assert_eq!(payload.strategies.len(), category.strategies.len());
+
+        let mut damaged = code.clone().into_bytes();
+        let last = damaged.len() - 1;
+        damaged[last] = if damaged[last] == b'A' { b'B' } else { b'A' };
+        assert!(decode_payload(&String::from_utf8(damaged).unwrap()).is_err());
+        assert!(decode_payload(&code.replacen("zs1.", "zs2.", 1)).is_err());
+        assert!(decode_payload("hello").is_err());
+    }
+
+    #[test]
+    fn import_remaps_placeholders_and_assigns_fresh_ids() {
+        let mut config = AppConfig::default();
+        let local = config.placeholders[0].clone();
+        let payload = SharePayload {
+            category_name: None,
+            strategies: vec![SharedStrategy {
+                name: "Shared".to_string(),
+                content: format!(
+                    "--hostlist={{{{THEIR_LIST}}}}\n--ipset={{{{{}}}}}\n--dpi-desync-fake-tls={{{{TLS_NEW}}}}\n--hostlist={{{{MINE}}}}",
+                    local.name
+                ),
+                metadata: StrategyMetadata::default(),
+            }],
+            placeholders: vec![
+                SharedPlaceholder {
+                    name: "THEIR_LIST".to_string(),
+                    path: local.value.clone(),
+                    kind: PlaceholderKind::Path,
+                },
+                SharedPlaceholder {
+                    name: "TLS_NEW".to_string(),
+                    path: "@resources/fake/new.bin".to_string(),
+                    kind: PlaceholderKind::Path,
+                },
+                SharedPlaceholder {
+                    name: "MINE".to_string(),
+                    path: "C:/Users/someone/list.txt".to_string(),
+                    kind: PlaceholderKind::Path,
+                },
+            ],
+        };
+        let category_id = config.categories[0].id.clone();
+        let strategy_count = config.categories[0].strategies.len();
+
+        let (imported_category, strategy_ids, remap) =
+            import_payload(&mut config, &payload, Some(&category_id)).unwrap();
+        assert_eq!(imported_category, category_id);
+        let category = &config.categories[0];
+        assert_eq!(category.strategies.len(), strategy_count + 1);
+        let imported = category.strategies.last().unwrap();
+        assert_eq!(imported.id, strategy_ids[0]);
+        assert!(Uuid::parse_str(&imported.id).is_ok());
+        assert!(!imported.system && !imported.active);
+        assert!(
+            imported
+                .content
+                .starts_with(&format!("--hostlist={{{{{}}}}}", local.name))
+        );
+        assert_eq!(remap.added, vec!["TLS_NEW"]);
+        assert_eq!(remap.unresolved, vec!["MINE"]);
+        assert!(
+            import_payload(&mut config, &payload, None).is_err(),
+            "strategy codes need a target category"
+        );
+    }
+}
@@ -0,0 +1,1671 @@
+use super::config::{
+    AppConfig, AppState, DEFAULT_INSTANCE_GROUP, GlobalPorts, LIST_MODE_TOKEN,
+    PROFILE_SEPARATOR_ARG, apply_placeholders, current_config, instance_config, instance_groups,
+};
+use super::ports::{
+    canonical_port_list, format_ranges, normalize_ranges, parse_port_list, subtract_ranges,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+const L7_PROTOCOLS: &[&str] = &[
+    "http",
+    "tls",
+    "quic",
+    "wireguard",
+    "dht",
+    "discord",
+    "stun",
+    "xmpp",
+    "dns",
+    "mtproto",
+    "unknown",
+];
+const DESYNC_MODES: &[&str] = &[
+    "fake",
+    "fakeknown",
+    "rst",
+    "rstack",
+    "synack",
+    "syndata",
+    "hopbyhop",
+    "destopt",
+    "ipfrag1",
+    "multisplit",
+    "multidisorder",
+    "fakedsplit",
+    "fakeddisorder",
+    "hostfakesplit",
+    "split",
+    "split2",
+    "disorder",
+    "disorder2",
+    "ipfrag2",
+    "udplen",
+    "tamper",
+];
+const SPLIT_DESYNC_MODES: &[&str] = &[
+    "multisplit",
+    "multidisorder",
+    "fakedsplit",
+    "fakeddisorder",
+    "hostfakesplit",
+    "split",
+    "split2",
+    "disorder",
+    "disorder2",
+];
+const FAKE_DESYNC_MODES: &[&str] = &[
+    "fake",
+    "fakeknown",
+    "fakedsplit",
+    "fakeddisorder",
+    "hostfakesplit",
+];
+const FOOLING_MODES: &[&str] = &[
+    "none",
+    "md5sig",
+    "ts",
+    "badseq",
+    "badsum",
+    "datanoack",
+    "hopbyhop",
+    "hopbyhop2",
+];
+const SPLIT_MARKERS: &[&str] = &[
+    "method", "host", "endhost", "sld", "midsld", "endsld", "sniext",
+];
+const FAKE_TLS_MODS: &[&str] = &["none", "rnd", "rndsni", "dupsid", "padencap"];
+const IP_ID_MODES: &[&str] = &["seq", "seqgroup", "rnd", "zero"];
+const SYNACK_SPLIT_MODES: &[&str] = &["syn", "synack", "acksyn"];
+const L3_PROTOCOLS: &[&str] = &["ipv4", "ipv6"];
+const GLOBAL_ONLY_OPTIONS: &[&str] = &[
+    "--wf-tcp",
+    "--wf-udp",
+    "--wf-raw",
+    "--wf-raw-part",
+    "--wf-l3",
+    "--wf-iface",
+    "--debug",
+    "--dry-run",
+    "--daemon",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueGrammar {
+    Flag,
+    Integer { min: i64, max: i64 },
+    Ports,
+    List(&'static [&'static str]),
+    OneOf(&'static [&'static str]),
+    SplitPositions,
+    SplitPosition,
+    Blob,
+    Path,
+    AutoTtl,
+    Cutoff,
+    FakeTlsMod,
+    HostFakeSplitMod,
+    Text,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OptionSpec {
+    name: &'static str,
+    grammar: ValueGrammar,
+    value_optional: bool,
+    repeatable: bool,
+}
+
+const fn option(name: &'static str, grammar: ValueGrammar) -> OptionSpec {
+    OptionSpec {
+        name,
+        grammar,
+        value_optional: false,
+        repeatable: false,
+    }
+}
+
+const fn optional_value(name: &'static str, grammar: ValueGrammar) -> OptionSpec {
+    OptionSpec {
+        name,
+        grammar,
+        value_optional: true,
+        repeatable: false,
+    }
+}
+
+const fn repeatable(name: &'static str, grammar: ValueGrammar) -> OptionSpec {
+    OptionSpec {
+        name,
+        grammar,
+        value_optional: false,
+        repeatable: true,
+    }
+}
+
+const TTL: ValueGrammar = ValueGrammar::Integer { min: 0, max: 255 };
+const SWITCH: ValueGrammar = ValueGrammar::Integer { min: 0, max: 1 };
+const COUNT: ValueGrammar = ValueGrammar::Integer { min: 1, max: 1_000 };
+const INCREMENT: ValueGrammar = ValueGrammar::Integer {
+    min: i32::MIN as i64,
+    max: u32::MAX as i64,
+};
+
+const OPTION_SPECS: &[OptionSpec] = &[
+    option("--filter-tcp", ValueGrammar::Ports),
+    option("--filter-udp", ValueGrammar::Ports),
+    option("--filter-l7", ValueGrammar::List(L7_PROTOCOLS)),
+    option("--filter-l3", ValueGrammar::OneOf(L3_PROTOCOLS)),
+    option("--filter-ssid", ValueGrammar::Text),
+    repeatable("--hostlist", ValueGrammar::Path),
+    repeatable("--hostlist-exclude", ValueGrammar::Path),
+    repeatable("--hostlist-domains", ValueGrammar::Text),
+    repeatable("--hostlist-exclude-domains", ValueGrammar::Text),
+    option("--hostlist-auto", ValueGrammar::Path),
+    option(
+        "--hostlist-auto-fail-threshold",
+        ValueGrammar::Integer { min: 1, max: 20 },
+    ),
+    option(
+        "--hostlist-auto-fail-time",
+        ValueGrammar::Integer {
+            min: 1,
+            max: 86_400,
+        },
+    ),
+    option(
+        "--hostlist-auto-retrans-threshold",
+        ValueGrammar::Integer { min: 2, max: 10 },
+    ),
+    repeatable("--ipset", ValueGrammar::Path),
+    repeatable("--ipset-exclude", ValueGrammar::Path),
+    repeatable("--ipset-ip", ValueGrammar::Text),
+    repeatable("--ipset-exclude-ip", ValueGrammar::Text),
+    option("--dpi-desync", ValueGrammar::List(DESYNC_MODES)),
+    option("--dpi-desync-split-pos", ValueGrammar::SplitPositions),
+    option("--dpi-desync-split-seqovl", ValueGrammar::SplitPosition),
+    option("--dpi-desync-split-seqovl-pattern", ValueGrammar::Blob),
+    option("--dpi-desync-fakedsplit-pattern", ValueGrammar::Blob),
+    option("--dpi-desync-fakedsplit-mod", ValueGrammar::Text),
+    option(
+        "--dpi-desync-hostfakesplit-midhost",
+        ValueGrammar::SplitPosition,
+    ),
+    option(
+        "--dpi-desync-hostfakesplit-mod",
+        ValueGrammar::HostFakeSplitMod,
+    ),
+    option("--dpi-desync-fooling", ValueGrammar::List(FOOLING_MODES)),
+    option("--dpi-desync-ttl", TTL),
+    option("--dpi-desync-ttl6", TTL),
+    optional_value("--dpi-desync-autottl", ValueGrammar::AutoTtl),
+    optional_value("--dpi-desync-autottl6", ValueGrammar::AutoTtl),
+    option("--dpi-desync-repeats", COUNT),
+    option("--dpi-desync-badseq-increment", INCREMENT),
+    option("--dpi-desync-badack-increment", INCREMENT),
+    option("--dpi-desync-ts-increment", INCREMENT),
+    optional_value("--dpi-desync-any-protocol", SWITCH),
+    optional_value("--dpi-desync-skip-nosni", SWITCH),
+    option("--dpi-desync-cutoff", ValueGrammar::Cutoff),
+    option("--dpi-desync-start", ValueGrammar::Cutoff),
+    option("--dpi-desync-fwmark", INCREMENT),
+    option(
+        "--dpi-desync-ipfrag-pos-tcp",
+        ValueGrammar::Integer {
+            min: 8,
+            max: 65_535,
+        },
+    ),
+    option(
+        "--dpi-desync-ipfrag-pos-udp",
+        ValueGrammar::Integer {
+            min: 8,
+            max: 65_535,
+        },
+    ),
+    option("--dpi-desync-udplen-increment", INCREMENT),
+    option("--dpi-desync-udplen-pattern", ValueGrammar::Blob),
+    repeatable("--dpi-desync-fake-tls", ValueGrammar::Blob),
+    repeatable("--dpi-desync-fake-tls-mod", ValueGrammar::FakeTlsMod),
+    repeatable("--dpi-desync-fake-http", ValueGrammar::Blob),
+    repeatable("--dpi-desync-fake-quic", ValueGrammar::Blob),
+    repeatable("--dpi-desync-fake-unknown", ValueGrammar::Blob),
+    repeatable("--dpi-desync-fake-unknown-udp", ValueGrammar::Blob),
+    repeatable("--dpi-desync-fake-syndata", ValueGrammar::Blob),
+    repeatable("--dpi-desync-fake-wireguard", ValueGrammar::Blob),
+    repeatable("--dpi-desync-fake-dht", ValueGrammar::Blob),
+    repeatable("--dpi-desync-fake-discord", ValueGrammar::Blob),
+    repeatable("--dpi-desync-fake-stun", ValueGrammar::Blob),
+    option("--dpi-desync-fake-tcp-mod", ValueGrammar::Text),
+    option("--dup", COUNT),
+    option("--dup-cutoff", ValueGrammar::Cutoff),
+    option("--dup-start", ValueGrammar::Cutoff),
+    option("--dup-fooling", ValueGrammar::List(FOOLING_MODES)),
+    option("--dup-ttl", TTL),
+    optional_value("--dup-autottl", ValueGrammar::AutoTtl),
+    optional_value("--dup-replace", SWITCH),
+    option("--dup-ip-id", ValueGrammar::OneOf(IP_ID_MODES)),
+    option("--orig-ttl", TTL),
+    optional_value("--orig-autottl", ValueGrammar::AutoTtl),
+    option("--orig-mod-start", ValueGrammar::Cutoff),
+    option("--orig-mod-cutoff", ValueGrammar::Cutoff),
+    option("--ip-id", ValueGrammar::OneOf(IP_ID_MODES)),
+    option("--wssize", ValueGrammar::Text),
+    option("--wssize-cutoff", ValueGrammar::Cutoff),
+    optional_value("--synack-split", ValueGrammar::OneOf(SYNACK_SPLIT_MODES)),
+    option(
+        "--mss",
+        ValueGrammar::Integer {
+            min: 88,
+            max: 32_767,
+        },
+    ),
+    option("--hostspell", ValueGrammar::Text),
+    option("--methodeol", ValueGrammar::Flag),
+    option("--hostcase", ValueGrammar::Flag),
+    option("--hostnospace", ValueGrammar::Flag),
+    option("--domcase", ValueGrammar::Flag),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionValue {
+    Flag,
+    Integer(i64),
+    Ports(Vec<RangeInclusive<u16>>),
+    List(Vec<String>),
+    Text(String),
+    Placeholder(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct StrategyOption {
+    pub line: usize,
+    pub name: String,
+    pub raw_value: Option<String>,
+    pub value: Result<OptionValue, String>,
+    spec: Option<&'static OptionSpec>,
+}
+
+impl StrategyOption {
+    pub fn is_known(&self) -> bool {
+        self.spec.is_some()
+    }
+
+    pub fn list_values(&self) -> &[String] {
+        match &self.value {
+            Ok(OptionValue::List(values)) => values,
+            _ => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum StrategyLine {
+    Option(StrategyOption),
+    ListMode,
+    Invalid { line: usize, text: String },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticCode {
+    NotAnOption,
+    UnknownOption,
+    GlobalOption,
+    ProfileSeparator,
+    MissingValue,
+    UnexpectedValue,
+    InvalidValue,
+    DuplicateOption,
+    MissingDependency,
+    NonCanonicalPorts,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyDiagnostic {
+    pub line: usize,
+    pub severity: DiagnosticSeverity,
+    pub code: DiagnosticCode,
+    pub option: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyLintReport {
+    pub category_id: String,
+    pub category_name: String,
+    pub strategy_id: String,
+    pub strategy_name: String,
+    pub diagnostics: Vec<StrategyDiagnostic>,
+}
+
+fn find_spec(name: &str) -> Option<&'static OptionSpec> {
+    OPTION_SPECS.iter().find(|spec| spec.name == name)
+}
+
+pub(crate) fn is_global_only_option(name: &str) -> bool {
+    GLOBAL_ONLY_OPTIONS.contains(&name)
+}
+
+/// Options whose value may name a hostlist, ipset or fake payload file.
+pub(crate) fn option_takes_file(name: &str) -> bool {
+    find_spec(name)
+        .is_some_and(|spec| matches!(spec.grammar, ValueGrammar::Path | ValueGrammar::Blob))
+}
+
+fn is_placeholder_token(value: &str) -> bool {
+    value.starts_with("{{") && value.ends_with("}}") && value.len() > 4
+}
+
+fn contains_placeholder_token(value: &str) -> bool {
+    value
+        .find("{{")
+        .is_some_and(|start| value[start..].contains("}}"))
+}
+
+fn parse_list(value: &str, allowed: &[&str], what: &str) -> Result<Vec<String>, String> {
+    let mut items = Vec::new();
+    for item in value.split(',') {
+        let item = item.trim();
+        if !allowed.contains(&item) {
+            return Err(format!("неизвестный {what} «{item}»"));
+        }
+        items.push(item.to_string());
+    }
+    Ok(items)
+}
+
+fn parse_integer(value: &str, min: i64, max: i64) -> Result<i64, String> {
+    let number = value
+        .parse::<i64>()
+        .map_err(|_| format!("ожидалось целое число, получено «{value}»"))?;
+    if number < min || number > max {
+        return Err(format!("значение {number} вне диапазона {min}..{max}"));
+    }
+    Ok(number)
+}
+
+fn is_valid_split_position(value: &str) -> bool {
+    if value.parse::<i64>().is_ok() {
+        return true;
+    }
+
+    let marker_end = value.find(['+', '-']).unwrap_or(value.len());
+    let (marker, offset) = value.split_at(marker_end);
+    SPLIT_MARKERS.contains(&marker) && (offset.is_empty() || offset[1..].parse::<u32>().is_ok())
+}
+
+fn is_valid_hex_blob(value: &str) -> bool {
+    value.strip_prefix("0x").is_some_and(|hex| {
+        !hex.is_empty() && hex.len() % 2 == 0 && hex.chars().all(|char| char.is_ascii_hexdigit())
+    })
+}
+
+fn is_valid_auto_ttl(value: &str) -> bool {
+    let (delta, bounds) = value.split_once(':').unwrap_or((value, ""));
+    if delta.parse::<i32>().is_err() {
+        return false;
+    }
+    if bounds.is_empty() {
+        return !value.ends_with(':');
+    }
+    let (min, max) = bounds.split_once('-').unwrap_or((bounds, bounds));
+    matches!((min.parse::<u8>(), max.parse::<u8>()), (Ok(min), Ok(max)) if min <= max)
+}
+
+fn is_valid_cutoff(value: &str) -> bool {
+    let digits = value.strip_prefix(['n', 'd', 's']).unwrap_or(value);
+    !digits.is_empty() && digits.chars().all(|char| char.is_ascii_digit())
+}
+
+fn is_valid_domain(value: &str) -> bool {
+    !value.is_empty()
+        && value.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+        })
+}
+
+fn parse_value(grammar: ValueGrammar, value: &str) -> Result<OptionValue, String> {
+    if is_placeholder_token(value) {
+        return Ok(OptionValue::Placeholder(value.to_string()));
+    }
+
+    match grammar {
+        ValueGrammar::Flag => Ok(OptionValue::Flag),
+        ValueGrammar::Integer { min, max } => {
+            parse_integer(value, min, max).map(OptionValue::Integer)
+        }
+        ValueGrammar::Ports => parse_port_list(value).map(OptionValue::Ports),
+        ValueGrammar::List(allowed) => parse_list(value, allowed, "режим").map(OptionValue::List),
+        ValueGrammar::OneOf(allowed) => {
+            if allowed.contains(&value) {
+                Ok(OptionValue::Text(value.to_string()))
+            } else {
+                Err(format!(
+                    "ожидалось одно из: {}, получено «{value}»",
+                    allowed.join(", ")
+                ))
+            }
+        }
+        ValueGrammar::SplitPositions => {
+            let positions = value.split(',').map(str::trim).collect::<Vec<_>>();
+            match positions
+                .iter()
+                .find(|position| !is_valid_split_position(position))
+            {
+                Some(position) => Err(format!("некорректная позиция разреза «{position}»")),
+                None => Ok(OptionValue::List(
+                    positions.into_iter().map(str::to_string).collect(),
+                )),
+            }
+        }
+        ValueGrammar::SplitPosition => {
+            if is_valid_split_position(value) {
+                Ok(OptionValue::Text(value.to_string()))
+            } else {
+                Err(format!("некорректная позиция разреза «{value}»"))
+            }
+        }
+        ValueGrammar::Blob => {
+            if value.starts_with("0x") && !is_valid_hex_blob(value) {
+                Err(format!("некорректная hex-строка «{value}»"))
+            } else {
+                Ok(OptionValue::Text(value.to_string()))
+            }
+        }
+        ValueGrammar::Path | ValueGrammar::Text => Ok(OptionValue::Text(value.to_string())),
+        ValueGrammar::AutoTtl => {
+            if is_valid_auto_ttl(value) {
+                Ok(OptionValue::Text(value.to_string()))
+            } else {
+                Err(format!(
+                    "ожидался формат delta[:min[-max]], получено «{value}»"
+                ))
+            }
+        }
+        ValueGrammar::Cutoff => {
+            if is_valid_cutoff(value) {
+                Ok(OptionValue::Text(value.to_string()))
+            } else {
+                Err(format!("ожидался формат [n|d|s]N, получено «{value}»"))
+            }
+        }
+        ValueGrammar::FakeTlsMod => {
+            let mut mods = Vec::new();
+            for item in value.split(',') {
+                let valid = match item.strip_prefix("sni=") {
+                    Some(sni) => is_valid_domain(sni),
+                    None => FAKE_TLS_MODS.contains(&item),
+                };
+                if !valid {
+                    return Err(format!("неизвестный модификатор fake-tls «{item}»"));
+                }
+                mods.push(item.to_string());
+            }
+            Ok(OptionValue::List(mods))
+        }
+        ValueGrammar::HostFakeSplitMod => {
+            let mut mods = Vec::new();
+            for item in value.split(',') {
+                let valid = match item.split_once('=') {
+                    Some(("host", host)) => is_valid_domain(host),
+                    Some(("altorder", order)) => parse_integer(order, 0, 2).is_ok(),
+                    _ => false,
+                };
+                if !valid {
+                    return Err(format!("неизвестный модификатор hostfakesplit «{item}»"));
+                }
+                mods.push(item.to_string());
+            }
+            Ok(OptionValue::List(mods))
+        }
+    }
+}
+
+fn parse_option(line: usize, text: &str) -> StrategyOption {
+    let (name, raw_value) = match text.split_once('=') {
+        Some((name, value)) => (name, Some(value.to_string())),
+        None => (text, None),
+    };
+    let spec = find_spec(name);
+    let value = match (spec, raw_value.as_deref()) {
+        (None, _) => Err(format!("неизвестная опция {name}")),
+        (Some(spec), None) if spec.grammar == ValueGrammar::Flag || spec.value_optional => {
+            Ok(OptionValue::Flag)
+        }
+        (Some(_), None) => Err(format!("опция {name} требует значение")),
+        (Some(spec), Some(_)) if spec.grammar == ValueGrammar::Flag => {
+            Err(format!("опция {name} не принимает значение"))
+        }
+        (Some(_), Some("")) => Err(format!("опция {name} требует значение")),
+        (Some(_), Some(value))
+            if contains_placeholder_token(value) && !is_placeholder_token(value) =>
+        {
+            Ok(OptionValue::Text(value.to_string()))
+        }
+        (Some(spec), Some(value)) => parse_value(spec.grammar, value),
+    };
+
+    StrategyOption {
+        line,
+        name: name.to_string(),
+        raw_value,
+        value,
+        spec,
+    }
+}
+
+pub fn parse_strategy(content: &str) -> Vec<StrategyLine> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line_number = index + 1;
+            let text = line.trim();
+            if text.is_empty() {
+                None
+            } else if text == LIST_MODE_TOKEN {
+                Some(StrategyLine::ListMode)
+            } else if text.starts_with("--") {
+                Some(StrategyLine::Option(parse_option(line_number, text)))
+            } else {
+                Some(StrategyLine::Invalid {
+                    line: line_number,
+                    text: text.to_string(),
+                })
+            }
+        })
+        .collect()
+}
+
+pub fn strategy_options(lines: &[StrategyLine]) -> impl Iterator<Item = &StrategyOption> {
+    lines.iter().filter_map(|line| match line {
+        StrategyLine::Option(option) => Some(option),
+        _ => None,
+    })
+}
+
+fn diagnostic(
+    line: usize,
+    severity: DiagnosticSeverity,
+    code: DiagnosticCode,
+    option: Option<&str>,
+    message: String,
+) -> StrategyDiagnostic {
+    StrategyDiagnostic {
+        line,
+        severity,
+        code,
+        option: option.map(str::to_string),
+        message,
+    }
+}
+
+fn lint_dependencies(options: &[&StrategyOption], diagnostics: &mut Vec<StrategyDiagnostic>) {
+    let first = |name: &str| options.iter().find(|option| option.name == name);
+    let desync_modes = first("--dpi-desync")
+        .map(|option| option.list_values().to_vec())
+        .unwrap_or_default();
+    let has_desync = first("--dpi-desync").is_some();
+    let has_mode = |modes: &[&str]| {
+        desync_modes
+            .iter()
+            .any(|mode| modes.contains(&mode.as_str()))
+    };
+    let fooling = first("--dpi-desync-fooling")
+        .map(|option| option.list_values().to_vec())
+        .unwrap_or_default();
+
+    for option in options.iter().filter(|option| option.is_known()) {
+        let name = option.name.as_str();
+        let requirement = if name == "--dpi-desync-split-seqovl-pattern" {
+            first("--dpi-desync-split-seqovl")
+                .is_none()
+                .then_some("--dpi-desync-split-seqovl")
+        } else if name.starts_with("--dup-") {
+            first("--dup").is_none().then_some("--dup")
+        } else if !name.starts_with("--dpi-desync-") {
+            None
+        } else if !has_desync {
+            Some("--dpi-desync")
+        } else if name == "--dpi-desync-badseq-increment"
+            && !fooling.iter().any(|mode| mode == "badseq")
+        {
+            Some("--dpi-desync-fooling=badseq")
+        } else if (name == "--dpi-desync-split-pos" || name == "--dpi-desync-split-seqovl")
+            && !desync_modes.is_empty()
+            && !has_mode(SPLIT_DESYNC_MODES)
+        {
+            Some("--dpi-desync с режимом разреза")
+        } else if name.starts_with("--dpi-desync-fake-")
+            && !desync_modes.is_empty()
+            && !has_mode(FAKE_DESYNC_MODES)
+        {
+            Some("--dpi-desync с режимом fake")
+        } else if name.starts_with("--dpi-desync-fakedsplit-")
+            && !desync_modes.is_empty()
+            && !has_mode(&["fakedsplit", "fakeddisorder"])
+        {
+            Some("--dpi-desync=fakedsplit или fakeddisorder")
+        } else if name.starts_with("--dpi-desync-hostfakesplit-")
+            && !desync_modes.is_empty()
+            && !has_mode(&["hostfakesplit"])
+        {
+            Some("--dpi-desync=hostfakesplit")
+        } else {
+            None
+        };
+
+        if let Some(requirement) = requirement {
+            diagnostics.push(diagnostic(
+                option.line,
+                DiagnosticSeverity::Warning,
+                DiagnosticCode::MissingDependency,
+                Some(name),
+                format!("{name} не действует без {requirement}"),
+            ));
+        }
+    }
+}
+
+pub fn lint_strategy_content(content: &str) -> Vec<StrategyDiagnostic> {
+    let lines = parse_strategy(content);
+    let mut diagnostics = Vec::new();
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+
+    for line in &lines {
+        let option = match line {
+            StrategyLine::Option(option) => option,
+            StrategyLine::ListMode => continue,
+            StrategyLine::Invalid { line, text } => {
+                diagnostics.push(diagnostic(
+                    *line,
+                    DiagnosticSeverity::Error,
+                    DiagnosticCode::NotAnOption,
+                    None,
+                    format!("строка «{text}» не является опцией winws"),
+                ));
+                continue;
+            }
+        };
+        let name = option.name.as_str();
+
+        if name == PROFILE_SEPARATOR_ARG {
+            diagnostics.push(diagnostic(
+                option.line,
+                DiagnosticSeverity::Warning,
+                DiagnosticCode::ProfileSeparator,
+                Some(name),
+                "--new внутри стратегии разбивает её на несколько профилей".to_string(),
+            ));
+            continue;
+        }
+
+        if GLOBAL_ONLY_OPTIONS.contains(&name) {
+            diagnostics.push(diagnostic(
+                option.line,
+                DiagnosticSeverity::Error,
+                DiagnosticCode::GlobalOption,
+                Some(name),
+                format!("{name} задаётся глобально и недопустим внутри стратегии"),
+            ));
+            continue;
+        }
+
+        let Some(spec) = option.spec else {
+            diagnostics.push(diagnostic(
+                option.line,
+                DiagnosticSeverity::Error,
+                DiagnosticCode::UnknownOption,
+                Some(name),
+                format!("неизвестная опция {name}"),
+            ));
+            continue;
+        };
+
+        if let Err(error) = &option.value {
+            let code = match (option.raw_value.as_deref(), spec.grammar) {
+                (Some(_), ValueGrammar::Flag) => DiagnosticCode::UnexpectedValue,
+                (None, _) | (Some(""), _) => DiagnosticCode::MissingValue,
+                _ => DiagnosticCode::InvalidValue,
+            };
+            diagnostics.push(diagnostic(
+                option.line,
+                DiagnosticSeverity::Error,
+                code,
+                Some(name),
+                format!("{name}: {error}"),
+            ));
+        } else if let (Ok(OptionValue::Ports(_)), Some(raw)) =
+            (&option.value, option.raw_value.as_deref())
+            && raw.trim_start_matches('~') != "*"
+            && let Ok(canonical) = canonical_port_list(raw)
+            && canonical != raw
+        {
+            diagnostics.push(diagnostic(
+                option.line,
+                DiagnosticSeverity::Warning,
+                DiagnosticCode::NonCanonicalPorts,
+                Some(name),
+                format!(
+                    "{name}: диапазоны портов пересекаются или не упорядочены, короче: {canonical}"
+                ),
+            ));
+        }
+
+        if let Some(first_line) = seen.insert(name, option.line)
+            && !spec.repeatable
+        {
+            diagnostics.push(diagnostic(
+                option.line,
+                DiagnosticSeverity::Warning,
+                DiagnosticCode::DuplicateOption,
+                Some(name),
+                format!(
+                    "{name} уже указан в строке {first_line}, будет использовано последнее значение"
+                ),
+            ));
+        }
+    }
+
+    let options = strategy_options(&lines).collect::<Vec<_>>();
+    lint_dependencies(&options, &mut diagnostics);
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+    diagnostics
+}
+
+/// Options that limit a profile to listed hosts or addresses. Exclude lists
+/// only carve a few hosts out of an otherwise broad profile, so they do not
+/// count as a restriction.
+const HOST_FILTER_OPTIONS: &[&str] = &[
+    "--hostlist",
+    "--hostlist-domains",
+    "--hostlist-auto",
+    "--ipset",
+    "--ipset-ip",
+];
+
+/// The traffic a single winws profile (one active strategy) applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProfileMatch {
+    l3: Option<Vec<String>>,
+    tcp: Vec<RangeInclusive<u16>>,
+    udp: Vec<RangeInclusive<u16>>,
+    explicit_tcp: bool,
+    explicit_udp: bool,
+    l7: Option<Vec<String>>,
+    /// Host and ip list options; an empty list matches every host.
+    hosts: Vec<String>,
+    ssid: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProfileIssueKind {
+    Shadowed,
+    Duplicate,
+    UncoveredPorts,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSource {
+    /// 1-based number within the winws.exe of `instance_group`.
+    pub position: usize,
+    pub instance_group: String,
+    pub category_id: String,
+    pub category_name: String,
+    pub strategy_id: String,
+    pub strategy_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileIssue {
+    pub kind: ProfileIssueKind,
+    pub profile: ProfileSource,
+    pub shadowed_by: Option<ProfileSource>,
+    pub message: String,
+}
+
+fn profile_ports(option: &StrategyOption) -> Option<Vec<RangeInclusive<u16>>> {
+    let Ok(OptionValue::Ports(ranges)) = &option.value else {
+        return None;
+    };
+    if option
+        .raw_value
+        .as_deref()
+        .is_some_and(|value| value.starts_with('~'))
+    {
+        return Some(subtract_ranges(&[1..=u16::MAX], ranges));
+    }
+    Some(normalize_ranges(ranges.clone()))
+}
+
+/// Returns `None` when a filter value cannot be reasoned about, e.g. a
+/// placeholder in place of a port list.
+fn profile_match(lines: &[StrategyLine]) -> Option<ProfileMatch> {
+    let mut tcp = None;
+    let mut udp = None;
+    let mut l3 = None;
+    let mut l7 = None;
+    let mut ssid = None;
+    let mut hosts = Vec::new();
+
+    for line in lines {
+        let option = match line {
+            StrategyLine::Option(option) => option,
+            // `active_profiles` substitutes the configured list mode.
+            StrategyLine::ListMode | StrategyLine::Invalid { .. } => continue,
+        };
+        match option.name.as_str() {
+            "--filter-tcp" => tcp = Some(profile_ports(option)?),
+            "--filter-udp" => udp = Some(profile_ports(option)?),
+            "--filter-l3" => l3 = Some(vec![option.raw_value.clone()?]),
+            "--filter-l7" => {
+                let mut protocols = option.list_values().to_vec();
+                if protocols.is_empty() {
+                    return None;
+                }
+                protocols.sort();
+                l7 = Some(protocols);
+            }
+            "--filter-ssid" => ssid = Some(option.raw_value.clone()?),
+            name if HOST_FILTER_OPTIONS.contains(&name) => hosts.push(format!(
+                "{name}={}",
+                option.raw_value.as_deref().unwrap_or_default()
+            )),
+            _ => {}
+        }
+    }
+
+    hosts.sort();
+    let explicit_tcp = tcp.is_some();
+    let explicit_udp = udp.is_some();
+    let (tcp, udp) = match (tcp, udp) {
+        (None, None) => (vec![1..=u16::MAX], vec![1..=u16::MAX]),
+        (tcp, udp) => (tcp.unwrap_or_default(), udp.unwrap_or_default()),
+    };
+
+    Some(ProfileMatch {
+        l3,
+        tcp,
+        udp,
+        explicit_tcp,
+        explicit_udp,
+        l7,
+        hosts,
+        ssid,
+    })
+}
+
+fn is_subset(inner: &Option<Vec<String>>, outer: &Option<Vec<String>>) -> bool {
+    match (inner, outer) {
+        (_, None) => true,
+        (None, Some(_)) => false,
+        (Some(inner), Some(outer)) => inner.iter().all(|value| outer.contains(value)),
+    }
+}
+
+/// Whether every packet matched by `later` is already taken by `earlier`.
+fn profile_covers(earlier: &ProfileMatch, later: &ProfileMatch) -> bool {
+    is_subset(&later.l3, &earlier.l3)
+        && is_subset(&later.l7, &earlier.l7)
+        && subtract_ranges(&later.tcp, &earlier.tcp).is_empty()
+        && subtract_ranges(&later.udp, &earlier.udp).is_empty()
+        && (earlier.hosts.is_empty() || earlier.hosts == later.hosts)
+        && (earlier.ssid.is_none() || earlier.ssid == later.ssid)
+}
+
+pub(crate) fn describe_source(source: &ProfileSource) -> String {
+    let group = if source.instance_group == DEFAULT_INSTANCE_GROUP {
+        String::new()
+    } else {
+        format!(" в группе «{}»", source.instance_group)
+    };
+    format!(
+        "профиль #{} «{} / {}»{group}",
+        source.position, source.category_name, source.strategy_name
+    )
+}
+
+/// Parses every active strategy that yields a winws profile, numbered per
+/// instance group in the order its winws.exe receives them.
+pub(crate) fn active_profiles(
+    config: &AppConfig,
+    parse: impl Fn(&str) -> Vec<StrategyLine>,
+) -> Vec<(ProfileSource, Vec<StrategyLine>)> {
+    let mut profiles = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for category in &config.categories {
+        for strategy in category
+            .strategies
+            .iter()
+            .filter(|strategy| strategy.active)
+        {
+            let lines = parse(
+                &strategy
+                    .content
+                    .replace(LIST_MODE_TOKEN, config.list_mode.strategy_arg()),
+            );
+            if lines.is_empty() {
+                continue;
+            }
+            let position = positions.entry(category.instance_group()).or_default();
+            *position += 1;
+            let source = ProfileSource {
+                position: *position,
+                instance_group: category.instance_group().to_string(),
+                category_id: category.id.clone(),
+                category_name: category.name.clone(),
+                strategy_id: strategy.id.clone(),
+                strategy_name: strategy.name.clone(),
+            };
+            profiles.push((source, lines));
+        }
+    }
+    profiles
+}
+
+/// Checks active strategies in the order winws receives them: the first
+/// profile that matches a packet wins, so broad early profiles hide later ones.
+/// Every instance group is its own winws.exe and is checked separately.
+pub fn analyze_profiles(config: &AppConfig) -> Vec<ProfileIssue> {
+    instance_groups(config)
+        .iter()
+        .flat_map(|group| analyze_instance_profiles(&instance_config(config, group)))
+        .collect()
+}
+
+fn analyze_instance_profiles(config: &AppConfig) -> Vec<ProfileIssue> {
+    let global_ports = effective_global_ports(config);
+    let global_tcp = parse_port_list(&global_ports.tcp).ok();
+    let global_udp = parse_port_list(&global_ports.udp).ok();
+    let profiles: Vec<(ProfileSource, Option<ProfileMatch>)> =
+        active_profiles(config, parse_strategy)
+            .into_iter()
+            .map(|(source, lines)| {
+                let matched = profile_match(&lines);
+                (source, matched)
+            })
+            .collect();
+    let mut issues = Vec::new();
+
+    for (index, (source, matched)) in profiles.iter().enumerate() {
+        let Some(matched) = matched else {
+            continue;
+        };
+
+        let coverer = profiles[..index]
+            .iter()
+            .find_map(|(earlier_source, earlier)| {
+                earlier
+                    .as_ref()
+                    .filter(|earlier| profile_covers(earlier, matched))
+                    .map(|earlier| (earlier_source, profile_covers(matched, earlier)))
+            });
+        if let Some((earlier_source, identical)) = coverer {
+            let same_category = earlier_source.category_id == source.category_id;
+            let (kind, message) = if identical {
+                (
+                    ProfileIssueKind::Duplicate,
+                    format!(
+                        "{} совпадает по фильтрам с {}{}",
+                        describe_source(source),
+                        describe_source(earlier_source),
+                        if same_category {
+                            ", в категории активны две стратегии"
+                        } else {
+                            ""
+                        }
+                    ),
+                )
+            } else {
+                (
+                    ProfileIssueKind::Shadowed,
+                    format!(
+                        "{} никогда не сработает: весь его трафик забирает более широкий {}",
+                        describe_source(source),
+                        describe_source(earlier_source)
+                    ),
+                )
+            };
+            issues.push(ProfileIssue {
+                kind,
+                profile: source.clone(),
+                shadowed_by: Some(earlier_source.clone()),
+                message,
+            });
+        }
+
+        for (protocol, explicit, ports, global) in [
+            ("tcp", matched.explicit_tcp, &matched.tcp, &global_tcp),
+            ("udp", matched.explicit_udp, &matched.udp, &global_udp),
+        ] {
+            let Some(global) = global.as_ref().filter(|_| explicit) else {
+                continue;
+            };
+            let uncovered = subtract_ranges(ports, global);
+            if !uncovered.is_empty() {
+                issues.push(ProfileIssue {
+                    kind: ProfileIssueKind::UncoveredPorts,
+                    profile: source.clone(),
+                    shadowed_by: None,
+                    message: format!(
+                        "{}: порты {protocol} {} не перехватываются, их нет в глобальных портах --wf-{protocol}",
+                        describe_source(source),
+                        format_ranges(&uncovered)
+                    ),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// WinDivert port sets computed from the active strategies. `wide_profiles`
+/// are the profiles that forced a full port range.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivedPorts {
+    pub tcp: String,
+    pub udp: String,
+    pub wide_profiles: Vec<ProfileSource>,
+}
+
+/// Transports an `--filter-l7` protocol runs over as `(tcp, udp)`.
+fn l7_transports(protocol: &str) -> (bool, bool) {
+    match protocol {
+        "http" | "tls" | "xmpp" | "mtproto" => (true, false),
+        "quic" | "wireguard" | "dht" | "discord" | "stun" => (false, true),
+        _ => (true, true),
+    }
+}
+
+/// Computes the smallest `--wf-tcp`/`--wf-udp` sets that still deliver every
+/// packet the active profiles can match. winws recognizes an `--filter-l7`
+/// protocol on any port, so a profile without port filters gets the whole
+/// range of the transports its protocols use.
+pub fn derive_global_ports(config: &AppConfig) -> DerivedPorts {
+    let parse = |content: &str| parse_strategy(&apply_placeholders(content, &config.placeholders));
+    let mut tcp = Vec::new();
+    let mut udp = Vec::new();
+    let mut wide_profiles = Vec::new();
+
+    for (source, lines) in active_profiles(config, parse) {
+        let (profile_tcp, profile_udp) = match profile_match(&lines) {
+            Some(matched) if matched.explicit_tcp || matched.explicit_udp => {
+                (matched.tcp, matched.udp)
+            }
+            Some(ProfileMatch {
+                l7: Some(protocols),
+                ..
+            }) => {
+                let (uses_tcp, uses_udp) = protocols
+                    .iter()
+                    .map(|protocol| l7_transports(protocol))
+                    .fold(
+                        (false, false),
+                        |(tcp, udp), (protocol_tcp, protocol_udp)| {
+                            (tcp || protocol_tcp, udp || protocol_udp)
+                        },
+                    );
+                let range = |used: bool| if used { vec![1..=u16::MAX] } else { Vec::new() };
+                (range(uses_tcp), range(uses_udp))
+            }
+            _ => (vec![1..=u16::MAX], vec![1..=u16::MAX]),
+        };
+        let is_full =
+            |ranges: &[RangeInclusive<u16>]| normalize_ranges(ranges.to_vec()) == [1..=u16::MAX];
+        if is_full(&profile_tcp) || is_full(&profile_udp) {
+            wide_profiles.push(source);
+        }
+        tcp.extend(profile_tcp);
+        udp.extend(profile_udp);
+    }
+
+    DerivedPorts {
+        tcp: format_ranges(&normalize_ranges(tcp)),
+        udp: format_ranges(&normalize_ranges(udp)),
+        wide_profiles,
+    }
+}
+
+/// The port sets winws is started with: derived in auto mode, otherwise the
+/// manual ones. An empty set means the protocol is not intercepted at all.
+pub(crate) fn effective_global_ports(config: &AppConfig) -> GlobalPorts {
+    if !config.global_ports.auto {
+        return config.global_ports.clone();
+    }
+
+    let derived = derive_global_ports(config);
+    GlobalPorts {
+        tcp: derived.tcp,
+        udp: derived.udp,
+        auto: true,
+    }
+}
+
+/// A region where the user and the builtin update changed the same options
+/// differently. Lines are given as they appear in each version.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeConflict {
+    pub base: Vec<String>,
+    pub user: Vec<String>,
+    pub builtin: Vec<String>,
+}
+
+/// On conflict `content` keeps the user's lines for the conflicting regions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentMerge {
+    pub content: String,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// For every line of `base`, the index of the matching line in `other`
+/// according to their longest common subsequence.
+fn match_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut lengths = vec![vec![0usize; other.len() + 1]; base.len() + 1];
+    for i in (0..base.len()).rev() {
+        for j in (0..other.len()).rev() {
+            lengths[i][j] = if base[i] == other[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut matches = vec![None; base.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < base.len() && j < other.len() {
+        if base[i] == other[j] {
+            matches[i] = Some(j);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+fn option_key(line: &str) -> &str {
+    line.split_once('=').map_or(line, |(name, _)| name)
+}
+
+/// Resolves a chunk changed on both sides by option name, which works as long
+/// as each side touched different options and no option is repeated.
+fn merge_chunk_by_option(base: &[&str], user: &[&str], builtin: &[&str]) -> Option<Vec<String>> {
+    let unique = |lines: &[&str]| {
+        let mut keys = lines
+            .iter()
+            .map(|line| option_key(line))
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.windows(2).all(|pair| pair[0] != pair[1])
+    };
+    if !unique(base) || !unique(user) || !unique(builtin) {
+        return None;
+    }
+
+    let find = |lines: &[&str], key: &str| {
+        lines
+            .iter()
+            .find(|line| option_key(line) == key)
+            .map(|line| line.to_string())
+    };
+    let mut keys = builtin
+        .iter()
+        .map(|line| option_key(line))
+        .collect::<Vec<_>>();
+    for line in user.iter().chain(base) {
+        if !keys.contains(&option_key(line)) {
+            keys.push(option_key(line));
+        }
+    }
+
+    let mut merged = Vec::new();
+    for key in keys {
+        let (original, ours, theirs) = (find(base, key), find(user, key), find(builtin, key));
+        let resolved = if ours == original {
+            theirs
+        } else if theirs == original || ours == theirs {
+            ours
+        } else {
+            return None;
+        };
+        merged.extend(resolved);
+    }
+    Some(merged)
+}
+
+/// Three-way merge of a builtin strategy: `base` is the builtin content the
+/// user started from, `builtin` is the current builtin content.
+pub(crate) fn merge_strategy_content(base: &str, user: &str, builtin: &str) -> ContentMerge {
+    if user == base || user == builtin {
+        return ContentMerge {
+            content: builtin.to_string(),
+            conflicts: Vec::new(),
+        };
+    }
+    if builtin == base {
+        return ContentMerge {
+            content: user.to_string(),
+            conflicts: Vec::new(),
+        };
+    }
+
+    let base_lines = base.lines().collect::<Vec<_>>();
+    let user_lines = user.lines().collect::<Vec<_>>();
+    let builtin_lines = builtin.lines().collect::<Vec<_>>();
+    let user_matches = match_lines(&base_lines, &user_lines);
+    let builtin_matches = match_lines(&base_lines, &builtin_lines);
+
+    let mut merged = Vec::new();
+    let mut conflicts = Vec::new();
+    let (mut base_index, mut user_index, mut builtin_index) = (0, 0, 0);
+    loop {
+        let stable = (base_index..base_lines.len()).find_map(|index| {
+            match (user_matches[index], builtin_matches[index]) {
+                (Some(user), Some(builtin)) if user >= user_index && builtin >= builtin_index => {
+                    Some((index, user, builtin))
+                }
+                _ => None,
+            }
+        });
+        let (base_end, user_end, builtin_end) =
+            stable.unwrap_or((base_lines.len(), user_lines.len(), builtin_lines.len()));
+
+        let original = &base_lines[base_index..base_end];
+        let ours = &user_lines[user_index..user_end];
+        let theirs = &builtin_lines[builtin_index..builtin_end];
+        let resolved = if ours == original {
+            Some(theirs.iter().map(|line| line.to_string()).collect())
+        } else if theirs == original || ours == theirs {
+            Some(ours.iter().map(|line| line.to_string()).collect())
+        } else {
+            merge_chunk_by_option(original, ours, theirs)
+        };
+        match resolved {
+            Some(lines) => merged.extend(lines),
+            None => {
+                merged.extend(ours.iter().map(|line| line.to_string()));
+                let to_owned = |lines: &[&str]| lines.iter().map(|line| line.to_string()).collect();
+                conflicts.push(MergeConflict {
+                    base: to_owned(original),
+                    user: to_owned(ours),
+                    builtin: to_owned(theirs),
+                });
+            }
+        }
+
+        let Some((base_end, user_end, builtin_end)) = stable else {
+            break;
+        };
+        merged.push(base_lines[base_end].to_string());
+        base_index = base_end + 1;
+        user_index = user_end + 1;
+        builtin_index = builtin_end + 1;
+    }
+
+    ContentMerge {
+        content: merged.join("\n"),
+        conflicts,
+    }
+}
+
+#[tauri::command]
+pub fn lint_strategy(content: String) -> Vec<StrategyDiagnostic> {
+    lint_strategy_content(&content)
+}
+
+#[tauri::command]
+pub fn lint_active_strategies(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<StrategyLintReport>, String> {
+    let config = current_config(&state)?;
+    let mut reports = Vec::new();
+
+    for category in &config.categories {
+        for strategy in category
+            .strategies
+            .iter()
+            .filter(|strategy| strategy.active)
+        {
+            let diagnostics = lint_strategy_content(&strategy.content);
+            if diagnostics.is_empty() {
+                continue;
+            }
+            reports.push(StrategyLintReport {
+                category_id: category.id.clone(),
+                category_name: category.name.clone(),
+                strategy_id: strategy.id.clone(),
+                strategy_name: strategy.name.clone(),
+                diagnostics,
+            });
+        }
+    }
+
+    Ok(reports)
+}
+
+#[tauri::command]
+pub fn get_derived_ports(state: tauri::State<'_, AppState>) -> Result<DerivedPorts, String> {
+    Ok(derive_global_ports(&current_config(&state)?))
+}
+
+#[tauri::command]
+pub fn analyze_active_profiles(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ProfileIssue>, String> {
+    Ok(analyze_profiles(&current_config(&state)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::config::ListMode;
+
+    fn codes(content: &str) -> Vec<(usize, DiagnosticCode)> {
+        lint_strategy_content(content)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.code))
+            .collect()
+    }
+
+    #[test]
+    fn builtin_strategies_lint_without_errors() {
+        let config = crate::commands::config::AppConfig::default();
+        for category in &config.categories {
+            for strategy in &category.strategies {
+                let errors = lint_strategy_content(&strategy.content)
+                    .into_iter()
+                    .filter(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error)
+                    .collect::<Vec<_>>();
+                assert!(
+                    errors.is_empty(),
+                    "{} / {}: {errors:?}",
+                    category.id,
+                    strategy.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reports_unknown_options_and_bad_values_with_lines() {
+        assert_eq!(
+            codes(
+                "--filter-l7=tls\n\n--dpi-desync=fake,multisplt\n--dpi-desync-fooling=md5sig\n--dpi-desynk-ttl=4"
+            ),
+            vec![
+                (3, DiagnosticCode::InvalidValue),
+                (5, DiagnosticCode::UnknownOption),
+            ]
+        );
+        assert_eq!(
+            codes("--filter-tcp=80,70000\n--dpi-desync=fake\n--dpi-desync-split-pos\nhostlist.txt"),
+            vec![
+                (1, DiagnosticCode::InvalidValue),
+                (3, DiagnosticCode::MissingValue),
+                (3, DiagnosticCode::MissingDependency),
+                (4, DiagnosticCode::NotAnOption),
+            ]
+        );
+    }
+
+    #[test]
+    fn suggests_canonical_port_lists() {
+        assert_eq!(
+            codes("--filter-tcp=443,80,80-90\n--filter-udp=~*\n--dpi-desync=fake"),
+            vec![(1, DiagnosticCode::NonCanonicalPorts)]
+        );
+        assert!(codes("--filter-tcp=80-90,443\n--dpi-desync=fake").is_empty());
+    }
+
+    #[test]
+    fn reports_duplicates_but_allows_repeatable_options() {
+        assert_eq!(
+            codes(
+                "--dpi-desync=fake\n--dpi-desync-fake-tls=0x00\n--dpi-desync-fake-tls=!\n--dpi-desync-repeats=2\n--dpi-desync-repeats=6"
+            ),
+            vec![(5, DiagnosticCode::DuplicateOption)]
+        );
+    }
+
+    #[test]
+    fn reports_missing_required_pairs() {
+        assert_eq!(
+            codes(
+                "--filter-l7=tls\n--dpi-desync-split-seqovl-pattern={{TLS}}\n--dpi-desync-repeats=2"
+            ),
+            vec![
+                (2, DiagnosticCode::MissingDependency),
+                (3, DiagnosticCode::MissingDependency),
+            ]
+        );
+        assert_eq!(
+            codes(
+                "--dpi-desync=fake\n--dpi-desync-fooling=md5sig\n--dpi-desync-badseq-increment=0"
+            ),
+            vec![(3, DiagnosticCode::MissingDependency)]
+        );
+    }
+
+    #[test]
+    fn accepts_placeholders_and_list_mode_marker() {
+        assert!(codes("--filter-l7=http\n<LIST_MODE>\n--dpi-desync=fake\n--dpi-desync-ttl={{TTL}}\n--hostlist={{HOSTS}}").is_empty());
+    }
+
+    fn config_with_profiles(profiles: &[(&str, &str)]) -> AppConfig {
+        let mut config = AppConfig::default();
+        let template = config.categories[0].clone();
+        config.categories = profiles
+            .iter()
+            .enumerate()
+            .map(|(index, (category_id, content))| {
+                let mut category = template.clone();
+                category.id = category_id.to_string();
+                category.name = category_id.to_string();
+                let mut strategy = template.strategies[0].clone();
+                strategy.id = format!("s{index}");
+                strategy.name = format!("s{index}");
+                strategy.content = content.to_string();
+                strategy.active = true;
+                category.strategies = vec![strategy];
+                category
+            })
+            .collect();
+        config
+    }
+
+    fn issue_kinds(config: &AppConfig) -> Vec<(usize, ProfileIssueKind)> {
+        analyze_profiles(config)
+            .into_iter()
+            .map(|issue| (issue.profile.position, issue.kind))
+            .collect()
+    }
+
+    #[test]
+    fn reports_shadowed_and_duplicate_profiles() {
+        let config = config_with_profiles(&[
+            ("tls", "--filter-l7=tls\n--dpi-desync=fake"),
+            (
+                "youtube",
+                "--filter-l7=tls\n--hostlist={{HOSTS_GOOGLE}}\n--dpi-desync=multisplit",
+            ),
+            ("quic", "--filter-l7=quic\n--dpi-desync=fake"),
+            (
+                "quic",
+                "--filter-l7=quic\n--dpi-desync=fake\n--dpi-desync-repeats=6",
+            ),
+        ]);
+        assert_eq!(
+            issue_kinds(&config),
+            vec![
+                (2, ProfileIssueKind::Shadowed),
+                (4, ProfileIssueKind::Duplicate),
+            ]
+        );
+
+        let reordered = config_with_profiles(&[
+            (
+                "youtube",
+                "--filter-l7=tls\n--hostlist={{HOSTS_GOOGLE}}\n--dpi-desync=multisplit",
+            ),
+            ("tls", "--filter-l7=tls\n--dpi-desync=fake"),
+        ]);
+        assert!(issue_kinds(&reordered).is_empty());
+
+        // A profile in another instance group runs in its own winws.exe.
+        let mut grouped = config.clone();
+        grouped.categories[1].instance_group = Some("youtube".to_string());
+        let issues = analyze_profiles(&grouped);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ProfileIssueKind::Duplicate);
+        assert_eq!(issues[0].profile.position, 3);
+    }
+
+    #[test]
+    fn list_mode_narrows_profiles_only_in_ipset_mode() {
+        let mut config = config_with_profiles(&[
+            ("tls", "--filter-l7=tls\n<LIST_MODE>\n--dpi-desync=fake"),
+            (
+                "youtube",
+                "--filter-l7=tls\n--hostlist={{HOSTS_GOOGLE}}\n--dpi-desync=multisplit",
+            ),
+        ]);
+        assert!(issue_kinds(&config).is_empty());
+
+        config.list_mode = ListMode::Exclude;
+        assert_eq!(issue_kinds(&config), vec![(2, ProfileIssueKind::Shadowed)]);
+    }
+
+    #[test]
+    fn port_filters_narrow_profiles_and_must_fit_global_ports() {
+        let mut config = config_with_profiles(&[
+            ("udp", "--filter-udp=443\n--dpi-desync=fake"),
+            ("tcp", "--filter-tcp=80,443\n--dpi-desync=fake"),
+            ("other", "--filter-udp=~443\n--dpi-desync=fake"),
+        ]);
+        config.global_ports.tcp = "80".to_string();
+        config.global_ports.udp = "1-65535".to_string();
+
+        let issues = analyze_profiles(&config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ProfileIssueKind::UncoveredPorts);
+        assert_eq!(issues[0].profile.position, 2);
+        assert!(issues[0].message.contains("tcp 443"));
+        assert_eq!(
+            subtract_ranges(&[1..=100], &[10..=20, 50..=100]),
+            vec![1..=9, 21..=49]
+        );
+    }
+
+    #[test]
+    fn auto_ports_are_derived_from_active_profiles() {
+        let mut config = config_with_profiles(&[
+            (
+                "tls",
+                "--filter-tcp=80,443\n--filter-l7=tls,http\n--dpi-desync=fake",
+            ),
+            (
+                "quic",
+                "--filter-udp=443\n--filter-l7=quic\n--dpi-desync=fake",
+            ),
+            ("game", "--filter-udp=27000-27100,27050\n--dpi-desync=fake"),
+            ("mixed", "--filter-tcp=2053,8443\n--dpi-desync=fake"),
+        ]);
+        config.global_ports.auto = true;
+
+        let derived = derive_global_ports(&config);
+        assert_eq!(derived.tcp, "80,443,2053,8443");
+        assert_eq!(derived.udp, "443,27000-27100");
+        assert!(derived.wide_profiles.is_empty());
+        assert_eq!(effective_global_ports(&config).tcp, derived.tcp);
+        assert!(analyze_profiles(&config).is_empty());
+
+        // QUIC on a non-standard port is still QUIC, so an L7 filter alone
+        // needs every port of its transport.
+        config.categories[1].strategies[0].content =
+            "--filter-l7=quic\n--dpi-desync=fake".to_string();
+        let derived = derive_global_ports(&config);
+        assert_eq!(
+            (derived.tcp.as_str(), derived.udp.as_str()),
+            ("80,443,2053,8443", "1-65535")
+        );
+        assert_eq!(derived.wide_profiles[0].category_id, "quic");
+
+        config.categories[1].strategies[0].content = "--dpi-desync=fake".to_string();
+        let derived = derive_global_ports(&config);
+        assert_eq!(
+            (derived.tcp.as_str(), derived.udp.as_str()),
+            ("1-65535", "1-65535")
+        );
+        assert_eq!(derived.wide_profiles[0].category_id, "quic");
+
+        config.global_ports.auto = false;
+        assert_eq!(effective_global_ports(&config), config.global_ports);
+    }
+
+    #[test]
+    fn merges_builtin_updates_into_user_edits() {
+        let base = "--filter-tcp=443\n--dpi-desync=fake\n--dpi-desync-repeats=6\n--new";
+        let user = "--filter-tcp=443\n--dpi-desync=fake\n--dpi-desync-repeats=11\n--new";
+        let builtin = "--filter-tcp=443\n--dpi-desync=fake\n--dpi-desync-fooling=ts\n--dpi-desync-repeats=6\n--new";
+        let merge = merge_strategy_content(base, user, builtin);
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(
+            merge.content,
+            "--filter-tcp=443\n--dpi-desync=fake\n--dpi-desync-fooling=ts\n--dpi-desync-repeats=11\n--new"
+        );
+
+        let adjacent = merge_strategy_content(
+            "--dpi-desync=fake\n--dpi-desync-repeats=6",
+            "--dpi-desync=fake,multisplit\n--dpi-desync-repeats=6",
+            "--dpi-desync=fake\n--dpi-desync-repeats=8",
+        );
+        assert!(adjacent.conflicts.is_empty());
+        assert_eq!(
+            adjacent.content,
+            "--dpi-desync=fake,multisplit\n--dpi-desync-repeats=8"
+        );
+
+        let conflict = merge_strategy_content(
+            "--dpi-desync=fake\n--dpi-desync-repeats=6",
+            "--dpi-desync=fake\n--dpi-desync-repeats=11",
+            "--dpi-desync=fake\n--dpi-desync-repeats=8",
+        );
+        assert_eq!(
+            conflict.conflicts,
+            vec![MergeConflict {
+                base: vec!["--dpi-desync-repeats=6".to_string()],
+                user: vec!["--dpi-desync-repeats=11".to_string()],
+                builtin: vec!["--dpi-desync-repeats=8".to_string()],
+            }]
+        );
+        assert_eq!(
+            conflict.content,
+            "--dpi-desync=fake\n--dpi-desync-repeats=11"
+        );
+    }
+}
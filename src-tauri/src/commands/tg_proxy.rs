@@ -157,25 +157,25 @@ fn terminate_process_by_pid(pid: u32) -> Result<(), String> {
                         return Ok(());
                     } else if wait_result == WAIT_TIMEOUT {
                         errors.push(format!(
-                            "Process {pid} did not exit within 5000 ms after WinAPI termination"
+                            "Процесс {pid} не завершился за 5000 мс после завершения через WinAPI"
                         ));
                     } else if wait_result == WAIT_FAILED {
                         errors.push(format!(
-                            "Failed to wait for process {pid} termination after WinAPI kill"
+                            "Не удалось дождаться завершения процесса {pid} после завершения через WinAPI"
                         ));
                     } else {
                         errors.push(format!(
-                            "Unexpected wait result while terminating process {pid}: {:?}",
+                            "Неожиданный результат ожидания при завершении процесса {pid}: {:?}",
                             wait_result
                         ));
                     }
                 }
                 Err(error) => {
                     let _ = CloseHandle(handle);
-                    errors.push(format!("Failed to terminate process {pid}: {error}"));
+                    errors.push(format!("Не удалось завершить процесс {pid}: {error}"));
                 }
             },
-            Err(error) => errors.push(format!("Failed to open process {pid}: {error}")),
+            Err(error) => errors.push(format!("Не удалось открыть процесс {pid}: {error}")),
         }
     }
 
@@ -188,10 +188,10 @@ fn terminate_process_by_pid(pid: u32) -> Result<(), String> {
             std::thread::sleep(Duration::from_millis(100));
         }
         errors.push(format!(
-            "Process {pid} did not exit within 5000 ms after sysinfo kill"
+            "Процесс {pid} не завершился за 5000 мс после завершения через sysinfo"
         ));
     } else {
-        errors.push(format!("sysinfo failed to kill process {pid}"));
+        errors.push(format!("sysinfo не смог завершить процесс {pid}"));
     }
 
     match kill_process_by_pid_taskkill(pid) {
@@ -204,7 +204,7 @@ fn terminate_process_by_pid(pid: u32) -> Result<(), String> {
                 std::thread::sleep(Duration::from_millis(100));
             }
             errors.push(format!(
-                "Process {pid} did not exit within 5000 ms after taskkill fallback"
+                "Процесс {pid} не завершился за 5000 мс после taskkill"
             ));
         }
         Err(error) => errors.push(error),
@@ -313,7 +313,7 @@ fn kill_process_by_pid_taskkill(pid: u32) -> Result<(), String> {
     let output = std::process::Command::new("taskkill")
         .args(["/PID", &pid.to_string(), "/T", "/F"])
         .output()
-        .map_err(|error| format!("Failed to launch taskkill for process {pid}: {error}"))?;
+        .map_err(|error| format!("Не удалось запустить taskkill для процесса {pid}: {error}"))?;
 
     if output.status.success() {
         Ok(())
@@ -325,9 +325,11 @@ fn kill_process_by_pid_taskkill(pid: u32) -> Result<(), String> {
         } else if !stdout.is_empty() {
             stdout
         } else {
-            format!("exit code {}", output.status)
+            format!("статус {}", output.status)
         };
-        Err(format!("taskkill failed for process {pid}: {detail}"))
+        Err(format!(
+            "taskkill не смог завершить процесс {pid}: {detail}"
+        ))
     }
 }
 
@@ -425,13 +427,15 @@ fn stop_tg_ws_proxy_inner() -> Result<TgWsProxyStatus, String> {
         match handle.try_wait() {
             Ok(None) => {
                 if let Err(error) = handle.kill() {
-                    stop_errors.push(format!("Failed to kill tg-ws-proxy: {error}"));
+                    stop_errors.push(format!("Не удалось завершить tg-ws-proxy: {error}"));
                 }
                 let _ = handle.wait_timeout(TG_WS_PROXY_SHUTDOWN_TIMEOUT);
             }
             Ok(Some(_)) => {}
             Err(error) => {
-                stop_errors.push(format!("Failed to inspect tg-ws-proxy state: {error}"));
+                stop_errors.push(format!(
+                    "Не удалось проверить состояние tg-ws-proxy: {error}"
+                ));
             }
         }
     }
@@ -441,7 +445,7 @@ fn stop_tg_ws_proxy_inner() -> Result<TgWsProxyStatus, String> {
         for pid in collect_tg_ws_proxy_pid_candidates() {
             if let Err(error) = terminate_process_by_pid(pid) {
                 stop_errors.push(format!(
-                    "Failed to terminate tg-ws-proxy process {pid}: {error}"
+                    "Не удалось завершить процесс tg-ws-proxy {pid}: {error}"
                 ));
             }
         }
@@ -502,13 +506,13 @@ fn spawn_tg_ws_proxy_once(port: u16, secret: &str) -> Result<TgWsProxyStatus, St
     let handle = configure_expression(cmd(binary_path.to_string_lossy().into_owned(), args))
         .dir(tg_ws_proxy_module_dir())
         .start()
-        .map_err(|error| format!("Failed to start tg-ws-proxy.exe: {error}"))?;
+        .map_err(|error| format!("Не удалось запустить tg-ws-proxy.exe: {error}"))?;
 
     let pid = handle
         .pids()
         .into_iter()
         .next()
-        .ok_or_else(|| "Failed to get tg-ws-proxy PID from duct handle".to_string())?;
+        .ok_or_else(|| "Не удалось получить PID tg-ws-proxy".to_string())?;
 
     {
         let mut running_handle = TG_WS_PROXY_HANDLE
@@ -529,7 +533,9 @@ fn spawn_tg_ws_proxy_once(port: u16, secret: &str) -> Result<TgWsProxyStatus, St
                 Some(handle) => {
                     let exit_message = handle
                         .try_wait()
-                        .map_err(|error| format!("Failed to inspect tg-ws-proxy startup: {error}"))?
+                        .map_err(|error| {
+                            format!("Не удалось проверить запуск tg-ws-proxy: {error}")
+                        })?
                         .map(|status| {
                             format!("tg-ws-proxy завершился сразу после запуска ({status:?})")
                         });
@@ -568,7 +574,7 @@ fn start_tg_ws_proxy_inner(port: u16, secret: String) -> Result<TgWsProxyStatus,
     ensure_tg_ws_proxy_runtime_dir()?;
     if let Err(error) = stop_tg_ws_proxy_inner() {
         return Err(format!(
-            "Failed to stop existing tg-ws-proxy before restart: {error}"
+            "Не удалось остановить запущенный tg-ws-proxy перед перезапуском: {error}"
         ));
     }
 
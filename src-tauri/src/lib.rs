@@ -1,6 +1,9 @@
 mod commands;
 
-use commands::{admin, binaries, config, discord_presence, dns, process, tg_proxy};
+use commands::{
+    admin, backup, batch, binaries, config, debug_log, discord_presence, dns, history, pack, ports,
+    process, profile, share, strategy, tg_proxy,
+};
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use tauri::{
@@ -18,6 +21,7 @@ use windows::{
 };
 
 static CONNECTED: AtomicBool = AtomicBool::new(false);
+const PROFILE_MENU_ID_PREFIX: &str = "profile:";
 
 pub(crate) fn sync_list_mode_ui(
     app: &tauri::AppHandle,
@@ -37,6 +41,47 @@ pub(crate) fn sync_list_mode_ui(
     Ok(())
 }
 
+pub(crate) fn sync_profiles_ui(
+    app: &tauri::AppHandle,
+    config: &config::AppConfig,
+) -> Result<(), String> {
+    let submenu = &app.state::<ProfileMenu>().0;
+    while submenu.remove_at(0).map_err(|e| e.to_string())?.is_some() {}
+
+    if config.profiles.is_empty() {
+        let placeholder =
+            MenuItem::with_id(app, "profile-none", "Нет профилей", false, None::<&str>)
+                .map_err(|e| e.to_string())?;
+        return submenu.append(&placeholder).map_err(|e| e.to_string());
+    }
+
+    for profile in &config.profiles {
+        let item = CheckMenuItem::with_id(
+            app,
+            format!("{PROFILE_MENU_ID_PREFIX}{}", profile.id),
+            &profile.name,
+            true,
+            config.active_profile_id.as_deref() == Some(profile.id.as_str()),
+            None::<&str>,
+        )
+        .map_err(|e| e.to_string())?;
+        submenu.append(&item).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn apply_profile(app: &tauri::AppHandle, id: &str) {
+    if let Err(error) =
+        profile::switch_profile(app.clone(), id.to_string(), app.state::<config::AppState>())
+    {
+        eprintln!("Failed to switch profile from tray: {error}");
+        if let Ok(config) = config::current_config(&app.state::<config::AppState>()) {
+            let _ = sync_profiles_ui(app, &config);
+        }
+        let _ = app.emit("profile-switch-error", error);
+    }
+}
+
 fn apply_list_mode(app: &tauri::AppHandle, mode: config::ListMode) {
     if CONNECTED.load(Ordering::SeqCst) {
         return;
@@ -145,11 +190,12 @@ pub fn run() {
             }
 
             let app_state = config::AppState::new()?;
-            let list_mode = app_state
+            let initial_config = app_state
                 .config
                 .lock()
-                .map(|cfg| cfg.list_mode)
-                .unwrap_or_default();
+                .map(|cfg| cfg.clone())
+                .map_err(|e| e.to_string())?;
+            let list_mode = initial_config.list_mode;
             app.manage(app_state);
 
             let connect_item =
@@ -177,9 +223,19 @@ pub fn run() {
             let listmode_submenu =
                 Submenu::with_items(app, "Режим списков", true, &[&ipset_item, &exclude_item])?;
 
+            let profiles_submenu = Submenu::with_items(app, "Профили", true, &[])?;
+            app.manage(ProfileMenu(profiles_submenu.clone()));
+            sync_profiles_ui(app.handle(), &initial_config)?;
+
             let menu = Menu::with_items(
                 app,
-                &[&connect_item, &listmode_submenu, &show_item, &quit_item],
+                &[
+                    &connect_item,
+                    &listmode_submenu,
+                    &profiles_submenu,
+                    &show_item,
+                    &quit_item,
+                ],
             )?;
 
             let icon_bytes = include_bytes!("../icons/32x32.png") as &[u8];
@@ -210,7 +266,11 @@ pub fn run() {
                     "quit" => {
                         app.exit(0);
                     }
-                    _ => {}
+                    id => {
+                        if let Some(profile_id) = id.strip_prefix(PROFILE_MENU_ID_PREFIX) {
+                            apply_profile(app, profile_id);
+                        }
+                    }
                 })
                 .on_tray_icon_event(|tray, event| {
                     if let tauri::tray::TrayIconEvent::Click { button, .. } = event
@@ -259,6 +319,7 @@ pub fn run() {
             });
 
             binaries::start_files_watcher(app.handle().clone())?;
+            process::start_winws_supervisor(app.handle().clone());
 
             Ok(())
         })
@@ -267,12 +328,45 @@ pub fn run() {
             config::ensure_config_dir,
             config::load_config,
             config::get_builtin_config,
+            config::get_config_schema,
             config::save_config,
             config::reset_config,
             config::get_resources_directory,
             config::config_exists,
-            config::resolve_placeholders,
+            config::get_placeholder_report,
+            config::get_placeholder_statuses,
+            config::get_dynamic_placeholders,
+            config::get_winws_args,
             config::update_list_mode,
+            config::get_builtin_merge_conflicts,
+            config::keep_user_strategy_content,
+            pack::add_pack_subscription,
+            pack::remove_pack_subscription,
+            pack::refresh_pack_subscriptions,
+            strategy::lint_strategy,
+            strategy::lint_active_strategies,
+            strategy::analyze_active_profiles,
+            strategy::get_derived_ports,
+            ports::normalize_port_list,
+            debug_log::get_debug_stats,
+            debug_log::reset_debug_stats,
+            backup::create_backup,
+            backup::list_backups,
+            backup::preview_backup_restore,
+            backup::restore_backup,
+            backup::open_backups_directory,
+            history::list_config_history,
+            history::diff_config_history,
+            history::restore_config_history,
+            profile::switch_profile,
+            profile::create_profile,
+            profile::rename_profile,
+            profile::delete_profile,
+            share::encode_share_code,
+            share::decode_share_code,
+            share::import_share_code,
+            batch::import_batch_preset,
+            batch::export_winws_script,
             binaries::verify_binaries,
             binaries::get_missing_critical_files,
             binaries::get_app_health_snapshot,
@@ -300,7 +394,11 @@ pub fn run() {
             tg_proxy::start_tg_ws_proxy,
             tg_proxy::stop_tg_ws_proxy,
             process::start_winws,
+            process::preflight_winws,
             process::stop_winws,
+            process::reload_winws,
+            process::restart_winws_instance,
+            process::get_winws_instances,
             process::is_winws_running,
             process::kill_windivert_service,
             process::get_running_pid,
@@ -324,8 +422,14 @@ struct ListModeItems {
     submenu: Submenu<tauri::Wry>,
 }
 
+struct ProfileMenu(Submenu<tauri::Wry>);
+
 #[tauri::command]
 fn set_connected_state(app: tauri::AppHandle, connected: bool) -> Result<(), String> {
+    sync_connected_ui(&app, connected)
+}
+
+pub(crate) fn sync_connected_ui(app: &tauri::AppHandle, connected: bool) -> Result<(), String> {
     let text = if connected {
         "Отключиться"
     } else {
@@ -0,0 +1,171 @@
+import type { BackupFileInfo, BackupRestorePreview, RestoreAction } from '@/lib/types'
+import { Archive, FolderOpen, Loader2, Plus, RotateCcw } from 'lucide-react'
+import { useState } from 'react'
+import { toast } from 'sonner'
+import { MODULE_PAGE_CARD_CLASS, ModuleSectionHeader } from '@/components/features/module-ui'
+import {
+  AlertDialog,
+  AlertDialogAction,
+  AlertDialogCancel,
+  AlertDialogContent,
+  AlertDialogDescription,
+  AlertDialogFooter,
+  AlertDialogHeader,
+  AlertDialogTitle,
+} from '@/components/ui/alert-dialog'
+import { Button } from '@/components/ui/button'
+import { Card, CardContent } from '@/components/ui/card'
+import { useMountEffect } from '@/hooks/use-mount-effect'
+import * as tauri from '@/lib/tauri'
+import { useConfigStore } from '@/stores/config.store'
+import { useConnectionStore } from '@/stores/connection.store'
+
+const RESTORE_ACTION_LABELS: Record<RestoreAction, string> = {
+  create: 'будет создан',
+  replace: 'будет заменён',
+  unchanged: 'без изменений',
+}
+
+function formatBackupDate(timestamp: number) {
+  return new Date(timestamp).toLocaleString('ru-RU')
+}
+
+function formatBackupSize(size: number) {
+  return size < 1024 ? `${size} Б` : `${(size / 1024).toFixed(1)} КБ`
+}
+
+export function BackupSection() {
+  const [backups, setBackups] = useState<BackupFileInfo[]>([])
+  const [busy, setBusy] = useState(false)
+  const [preview, setPreview] = useState<BackupRestorePreview | null>(null)
+  const reload = useConfigStore(state => state.reload)
+  const addConfigLog = useConnectionStore(state => state.addConfigLog)
+
+  const refreshBackups = async () => {
+    try {
+      setBackups(await tauri.listBackups())
+    }
+    catch (e) {
+      toast.error(`Не удалось получить список резервных копий: ${e}`)
+    }
+  }
+
+  useMountEffect(() => {
+    void refreshBackups()
+  })
+
+  const handleCreate = async () => {
+    setBusy(true)
+    try {
+      const backup = await tauri.createBackup()
+      addConfigLog(`создана резервная копия ${backup.fileName}`)
+      toast.success('Резервная копия создана')
+      await refreshBackups()
+    }
+    catch (e) {
+      toast.error(`Ошибка создания резервной копии: ${e}`)
+    }
+    finally {
+      setBusy(false)
+    }
+  }
+
+  const handlePreview = async (backup: BackupFileInfo) => {
+    setBusy(true)
+    try {
+      setPreview(await tauri.previewBackupRestore(backup.path))
+    }
+    catch (e) {
+      toast.error(`Резервная копия повреждена: ${e}`)
+    }
+    finally {
+      setBusy(false)
+    }
+  }
+
+  const handleRestore = async () => {
+    if (!preview) {
+      return
+    }
+
+    setBusy(true)
+    try {
+      await tauri.restoreBackup(preview.path)
+      await reload()
+      addConfigLog(`восстановлена резервная копия от ${formatBackupDate(preview.manifest.createdAt)}`)
+      toast.success('Резервная копия восстановлена')
+      setPreview(null)
+    }
+    catch (e) {
+      toast.error(`Ошибка восстановления: ${e}`)
+    }
+    finally {
+      setBusy(false)
+    }
+  }
+
+  return (
+    <Card className={MODULE_PAGE_CARD_CLASS}>
+      <ModuleSectionHeader
+        icon={Archive}
+        title="Резервные копии"
+        description="Конфигурация, пользовательские списки, фильтры и fake-файлы в одном архиве"
+        action={(
+          <div className="flex gap-2">
+            <Button variant="outline" size="sm" onClick={() => void tauri.openBackupsDirectory()}>
+              <FolderOpen className="size-4" />
+              Папка
+            </Button>
+            <Button size="sm" disabled={busy} onClick={handleCreate}>
+              {busy ? <Loader2 className="size-4 animate-spin" /> : <Plus className="size-4" />}
+              Создать
+            </Button>
+          </div>
+        )}
+      />
+      <CardContent className="space-y-2 p-4!">
+        {backups.length === 0
+          ? <p className="text-xs text-muted-foreground">Резервных копий пока нет</p>
+          : backups.map(backup => (
+              <div key={backup.path} className="flex items-center justify-between gap-4">
+                <div className="min-w-0">
+                  <p className="truncate text-sm">{formatBackupDate(backup.modifiedAt)}</p>
+                  <p className="truncate text-xs text-muted-foreground">
+                    {`${backup.fileName}, ${formatBackupSize(backup.size)}`}
+                  </p>
+                </div>
+                <Button variant="outline" size="sm" disabled={busy} onClick={() => void handlePreview(backup)}>
+                  <RotateCcw className="size-4" />
+                  Восстановить
+                </Button>
+              </div>
+            ))}
+      </CardContent>
+
+      <AlertDialog open={!!preview} onOpenChange={open => !open && setPreview(null)}>
+        <AlertDialogContent>
+          <AlertDialogHeader>
+            <AlertDialogTitle>Восстановить резервную копию?</AlertDialogTitle>
+            <AlertDialogDescription>
+              {preview && `Копия от ${formatBackupDate(preview.manifest.createdAt)}, версия приложения ${preview.manifest.appVersion}. Текущие файлы будут заменены:`}
+            </AlertDialogDescription>
+          </AlertDialogHeader>
+          <ul className="max-h-64 space-y-1 overflow-auto text-xs">
+            {preview?.entries.map(entry => (
+              <li key={entry.path} className="flex justify-between gap-4">
+                <span className="truncate font-mono">{entry.path}</span>
+                <span className="shrink-0 text-muted-foreground">{RESTORE_ACTION_LABELS[entry.action]}</span>
+              </li>
+            ))}
+          </ul>
+          <AlertDialogFooter>
+            <AlertDialogCancel>Отмена</AlertDialogCancel>
+            <AlertDialogAction disabled={busy} onClick={handleRestore}>
+              Восстановить
+            </AlertDialogAction>
+          </AlertDialogFooter>
+        </AlertDialogContent>
+      </AlertDialog>
+    </Card>
+  )
+}
@@ -19,7 +19,7 @@ import {
 } from '@dnd-kit/sortable'
 import { CSS } from '@dnd-kit/utilities'
 import { useNavigate } from '@tanstack/react-router'
-import { BrushCleaning, ChevronRight, FilePenLine, GripVertical, Loader2, Package, Pencil, Plus, RefreshCcw, RotateCcw, Trash2, UserRoundPlus } from 'lucide-react'
+import { BrushCleaning, ChevronRight, FileCode, FileDown, FilePenLine, GripVertical, Loader2, Package, Pencil, Plus, RefreshCcw, RotateCcw, Trash2, UserRoundPlus } from 'lucide-react'
 import { memo, useCallback, useRef, useState } from 'react'
 import { toast } from 'sonner'
 import {
@@ -46,6 +46,7 @@ import { Input } from '@/components/ui/input'
 import { LenisScrollArea } from '@/components/ui/lenis-scroll-area'
 import { Tooltip, TooltipContent, TooltipTrigger } from '@/components/ui/tooltip'
 import { useMountEffect } from '@/hooks/use-mount-effect'
+import * as tauri from '@/lib/tauri'
 import { buildRestoredCategory, getBuiltinCategory, isSystemCategory, isSystemCategoryModified, isSystemCategoryUpdateAvailable } from '@/lib/system-config'
 import { useConfigStore } from '@/stores/config.store'
 import { useConnectionStore } from '@/stores/connection.store'
@@ -307,16 +308,19 @@ const SortableCategoryItem = memo(({ category, config, builtinConfig, onClearAct
 })
 
 export function CategoriesListPage() {
+  const navigate = useNavigate()
   const [newCategoryOpen, setNewCategoryOpen] = useState(false)
   const [newCategoryName, setNewCategoryName] = useState('')
   const [renameDialogOpen, setRenameDialogOpen] = useState(false)
   const categoryToRenameRef = useRef<Category | null>(null)
   const [newCategoryNameDraft, setNewCategoryNameDraft] = useState('')
   const [systemCategoryTarget, setSystemCategoryTarget] = useState<Category | null>(null)
+  const batchFileInputRef = useRef<HTMLInputElement | null>(null)
   const config = useConfigStore(state => state.config)
   const builtinConfig = useConfigStore(state => state.builtinConfig)
   const loading = useConfigStore(state => state.loading)
   const load = useConfigStore(state => state.load)
+  const reload = useConfigStore(state => state.reload)
   const saveNow = useConfigStore(state => state.saveNow)
   const addCategory = useConfigStore(state => state.addCategory)
   const revertTo = useConfigStore(state => state.revertTo)
@@ -344,6 +348,46 @@ export function CategoriesListPage() {
     void load()
   })
 
+  const handleImportBatchFile = async (file: File) => {
+    try {
+      await saveNow()
+      const result = await tauri.importBatchPreset(file.name, await file.text())
+      await reload()
+      addConfigLog(`импортирован скрипт "${file.name}": ${result.strategyIds.length} стратегий`)
+      if (result.wfTcp !== null || result.wfUdp !== null) {
+        addConfigLog(`порты из скрипта не применены: tcp ${result.wfTcp ?? '—'}, udp ${result.wfUdp ?? '—'}`)
+      }
+      if (result.skippedOptions.length > 0) {
+        addConfigLog(`пропущены глобальные параметры: ${result.skippedOptions.join(' ')}`)
+      }
+      for (const reference of result.unmappedFiles) {
+        addConfigLog(`профиль ${reference.profile}: файл ${reference.option}=${reference.value} не сопоставлен с плейсхолдером`)
+      }
+      if (result.unmappedFiles.length > 0) {
+        toast.warning(`Импортировано, но ${result.unmappedFiles.length} файлов не сопоставлено с плейсхолдерами. Подробности в журнале`)
+      }
+      else {
+        toast.success('Скрипт импортирован')
+      }
+      void navigate({ to: '/strategies/$categoryId', params: { categoryId: result.categoryId } })
+    }
+    catch (e) {
+      toast.error(`Ошибка импорта скрипта: ${e instanceof Error ? e.message : String(e)}`)
+    }
+  }
+
+  const handleExportScript = async () => {
+    try {
+      await saveNow()
+      const path = await tauri.exportWinwsScript()
+      addConfigLog(`текущая конфигурация выгружена в ${path}`)
+      toast.success('Скрипт сохранён')
+    }
+    catch (e) {
+      toast.error(`Ошибка экспорта скрипта: ${e instanceof Error ? e.message : String(e)}`)
+    }
+  }
+
   const handleAddCategory = useCallback(async () => {
     if (!newCategoryName.trim()) {
       return
@@ -552,10 +596,33 @@ export function CategoriesListPage() {
               Категоризированное управление стратегиями
             </p>
           </div>
-          <Button onClick={() => setNewCategoryOpen(true)}>
-            <Plus className="size-4" />
-            Новая категория
-          </Button>
+          <div className="flex items-center gap-2">
+            <input
+              ref={batchFileInputRef}
+              type="file"
+              accept=".bat,.cmd"
+              className="hidden"
+              onChange={(e) => {
+                const file = e.target.files?.[0]
+                e.target.value = ''
+                if (file) {
+                  void handleImportBatchFile(file)
+                }
+              }}
+            />
+            <Button variant="outline" onClick={() => batchFileInputRef.current?.click()}>
+              <FileCode className="size-4" />
+              Импорт .bat
+            </Button>
+            <Button variant="outline" onClick={() => void handleExportScript()}>
+              <FileDown className="size-4" />
+              Экспорт .cmd
+            </Button>
+            <Button onClick={() => setNewCategoryOpen(true)}>
+              <Plus className="size-4" />
+              Новая категория
+            </Button>
+          </div>
         </div>
 
         <div className="space-y-3">
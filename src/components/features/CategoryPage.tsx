@@ -1,6 +1,6 @@
-import type { Strategy } from '@/lib/types'
+import type { MergeConflict, Strategy, StrategyDiagnostic, StrategyMetadata } from '@/lib/types'
 import { Link, useNavigate, useParams } from '@tanstack/react-router'
-import { ArrowLeft, BrushCleaning, Check, FilePenLine, Loader2, Package, Pencil, Plus, RefreshCcw, RotateCcw, Trash2, UserRoundPlus } from 'lucide-react'
+import { ArrowLeft, BrushCleaning, Check, ClipboardPaste, FilePenLine, Loader2, Package, Pencil, Plus, RefreshCcw, RotateCcw, Share2, Trash2, UserRoundPlus } from 'lucide-react'
 import { memo, useCallback, useRef, useState } from 'react'
 import { toast } from 'sonner'
 import {
@@ -13,10 +13,12 @@ import {
   AlertDialogTitle,
   AlertDialogTrigger,
 } from '@/components/ui/alert-dialog'
+import { Badge } from '@/components/ui/badge'
 import { Button } from '@/components/ui/button'
 import {
   Dialog,
   DialogContent,
+  DialogDescription,
   DialogFooter,
   DialogHeader,
   DialogTitle,
@@ -29,13 +31,17 @@ import { LenisScrollArea } from '@/components/ui/lenis-scroll-area'
 import { ScrollTopButton } from '@/components/ui/scroll-top-button'
 import { Tooltip, TooltipContent, TooltipTrigger } from '@/components/ui/tooltip'
 import { useMountEffect } from '@/hooks/use-mount-effect'
+import { useStrategyLint } from '@/hooks/use-strategy-lint'
 import { autosizeTextarea } from '@/lib/editor-scroll'
+import * as tauri from '@/lib/tauri'
 import { buildRestoredCategory, buildRestoredStrategy, getBuiltinCategory, getBuiltinStrategy, isSystemCategory, isSystemCategoryModified, isSystemCategoryUpdateAvailable, isSystemStrategy, isSystemStrategyModified, isSystemStrategyUpdateAvailable } from '@/lib/system-config'
 import { cn } from '@/lib/utils'
 import { useConfigStore } from '@/stores/config.store'
 import { useConnectionStore } from '@/stores/connection.store'
 
 const CRLF_REGEX = /\r\n/g
+const DEFAULT_INSTANCE_GROUP = 'main'
+const INSTANCE_GROUP_PATTERN = /^[\w-]{1,32}$/
 
 function normalizeStrategyText(value: string) {
   return value.replace(CRLF_REGEX, '\n').trim()
@@ -76,6 +82,25 @@ function formatActiveStrategiesSrText(activeCount: number) {
   return `${activeCount} активных стратегий`
 }
 
+function StrategyDiagnosticsList({ diagnostics }: { diagnostics: StrategyDiagnostic[] }) {
+  if (diagnostics.length === 0) {
+    return null
+  }
+
+  return (
+    <ul className="space-y-1 text-xs">
+      {diagnostics.map(diagnostic => (
+        <li
+          key={`${diagnostic.line}-${diagnostic.code}-${diagnostic.option ?? ''}`}
+          className={cn(diagnostic.severity === 'error' ? 'text-destructive' : 'text-amber-600 dark:text-amber-400')}
+        >
+          {`Строка ${diagnostic.line}: ${diagnostic.message}`}
+        </li>
+      ))}
+    </ul>
+  )
+}
+
 function getStrategyDuplicateError(
   strategies: Strategy[],
   name: string,
@@ -105,9 +130,65 @@ interface StrategyCardProps {
   handleEditStrategy: (strategy: Strategy) => void
   handleClearActive: (id: string) => void
   handleDeleteStrategy: (id: string) => void
+  handleShareStrategy: (strategy: Strategy) => void
   onSystemActionClick: (strategyId: string, name: string, updateAvailable: boolean) => void
 }
 
+function StrategyMetadataLine({ strategy }: { strategy: Strategy }) {
+  const labels = [...(strategy.targets ?? []), ...(strategy.tags ?? [])]
+  const details = [
+    strategy.author && `автор: ${strategy.author}`,
+    strategy.lastVerified && `проверена: ${strategy.lastVerified}`,
+  ].filter(Boolean).join(', ')
+
+  if (!strategy.description && labels.length === 0 && !details) {
+    return null
+  }
+
+  return (
+    <div className="space-y-1.5 text-xs text-muted-foreground">
+      {strategy.description && <p>{strategy.description}</p>}
+      {labels.length > 0 && (
+        <div className="flex flex-wrap gap-1">
+          {strategy.targets?.map(target => (
+            <Badge key={`target-${target}`} variant="secondary" className="rounded-[4px] px-1.5 py-0 text-[10px]">
+              {target}
+            </Badge>
+          ))}
+          {strategy.tags?.map(tag => (
+            <Badge key={`tag-${tag}`} variant="outline" className="rounded-[4px] px-1.5 py-0 text-[10px]">
+              {tag}
+            </Badge>
+          ))}
+        </div>
+      )}
+      {details && <p>{details}</p>}
+    </div>
+  )
+}
+
+interface StrategyMetadataForm {
+  description: string
+  tags: string
+  targets: string
+  author: string
+  lastVerified: string
+}
+
+function splitMetadataList(value: string) {
+  return [...new Set(value.split(',').map(item => item.trim()).filter(Boolean))]
+}
+
+function buildStrategyMetadata(form: StrategyMetadataForm): StrategyMetadata {
+  return {
+    description: form.description.trim() || undefined,
+    tags: splitMetadataList(form.tags),
+    targets: splitMetadataList(form.targets),
+    author: form.author.trim() || undefined,
+    lastVerified: form.lastVerified || undefined,
+  }
+}
+
 const StrategyCard = memo(({
   strategy,
   isSystem,
@@ -117,6 +198,7 @@ const StrategyCard = memo(({
   handleEditStrategy,
   handleClearActive,
   handleDeleteStrategy,
+  handleShareStrategy,
   onSystemActionClick,
 }: StrategyCardProps) => {
   const canRestore = isSystem && (isModified || updateAvailable)
@@ -205,6 +287,19 @@ const StrategyCard = memo(({
               <TooltipContent>Деактивировать</TooltipContent>
             </Tooltip>
           )}
+          <Tooltip>
+            <TooltipTrigger asChild>
+              <Button
+                variant="outline"
+                size="icon"
+                onClick={() => handleShareStrategy(strategy)}
+                aria-label={`Скопировать код стратегии ${strategy.name}`}
+              >
+                <Share2 className="size-4" />
+              </Button>
+            </TooltipTrigger>
+            <TooltipContent>Скопировать код для обмена</TooltipContent>
+          </Tooltip>
           <Tooltip>
             <TooltipTrigger asChild>
               <Button
@@ -231,13 +326,14 @@ const StrategyCard = memo(({
       >
         {strategy.content}
       </pre>
+      <StrategyMetadataLine strategy={strategy} />
     </div>
   )
 })
 
 type SystemActionTarget
   = | { type: 'category', title: string, description: string }
-    | { type: 'strategy', strategyId: string, title: string, description: string }
+    | { type: 'strategy', strategyId: string, title: string, description: string, conflicts: MergeConflict[] }
 
 export function CategoryPage() {
   const { categoryId } = useParams({ from: '/strategies/$categoryId' })
@@ -249,12 +345,19 @@ export function CategoryPage() {
   const [newStrategyContent, setNewStrategyContent] = useState('')
   const [editingName, setEditingName] = useState('')
   const [editingContent, setEditingContent] = useState('')
+  const [editingMetadata, setEditingMetadata] = useState<StrategyMetadataForm>({ description: '', tags: '', targets: '', author: '', lastVerified: '' })
   const [deleteDialogOpen, setDeleteDialogOpen] = useState(false)
   const [renameDialogOpen, setRenameDialogOpen] = useState(false)
   const [newCategoryName, setNewCategoryName] = useState('')
+  const [newInstanceGroup, setNewInstanceGroup] = useState('')
   const [systemActionTarget, setSystemActionTarget] = useState<SystemActionTarget | null>(null)
+  const [importDialogOpen, setImportDialogOpen] = useState(false)
+  const [importCode, setImportCode] = useState('')
+  const [importing, setImporting] = useState(false)
   const newStrategyContentTextareaRef = useRef<HTMLTextAreaElement | null>(null)
   const editStrategyContentTextareaRef = useRef<HTMLTextAreaElement | null>(null)
+  const { diagnostics: newStrategyDiagnostics, lint: lintNewStrategy, reset: resetNewStrategyLint } = useStrategyLint()
+  const { diagnostics: editStrategyDiagnostics, lint: lintEditStrategy } = useStrategyLint()
   const scrollAreaRef = useRef<HTMLDivElement | null>(null)
   const config = useConfigStore(state => state.config)
   const builtinConfig = useConfigStore(state => state.builtinConfig)
@@ -264,6 +367,7 @@ export function CategoryPage() {
   const saveNow = useConfigStore(state => state.saveNow)
   const revertTo = useConfigStore(state => state.revertTo)
   const updateCategory = useConfigStore(state => state.updateCategory)
+  const setCategoryInstanceGroup = useConfigStore(state => state.setCategoryInstanceGroup)
   const restoreBuiltinCategory = useConfigStore(state => state.restoreBuiltinCategory)
   const deleteCategory = useConfigStore(state => state.deleteCategory)
   const addStrategy = useConfigStore(state => state.addStrategy)
@@ -339,6 +443,7 @@ export function CategoryPage() {
       }
       setNewStrategyName('')
       setNewStrategyContent('')
+      resetNewStrategyLint()
       setNewStrategyOpen(false)
       toast.success('Стратегия добавлена')
     }
@@ -346,14 +451,22 @@ export function CategoryPage() {
       revertTo(previousConfig)
       toast.error(`Ошибка сохранения стратегии: ${e instanceof Error ? e.message : String(e)}`)
     }
-  }, [newStrategyName, newStrategyContent, categoryId, addStrategy, saveNow, addConfigLog, revertTo])
+  }, [newStrategyName, newStrategyContent, categoryId, addStrategy, saveNow, addConfigLog, revertTo, resetNewStrategyLint])
 
   const handleEditStrategy = useCallback((strategy: Strategy) => {
     setEditingStrategy(strategy)
     setEditingName(strategy.name)
     setEditingContent(strategy.content)
+    setEditingMetadata({
+      description: strategy.description ?? '',
+      tags: (strategy.tags ?? []).join(', '),
+      targets: (strategy.targets ?? []).join(', '),
+      author: strategy.author ?? '',
+      lastVerified: strategy.lastVerified ?? '',
+    })
+    lintEditStrategy(strategy.content)
     requestAnimationFrame(() => autosizeTextarea(editStrategyContentTextareaRef.current))
-  }, [])
+  }, [lintEditStrategy])
 
   const handleSaveEdit = useCallback(async () => {
     if (!editingStrategy || !categoryId) {
@@ -377,6 +490,7 @@ export function CategoryPage() {
     updateStrategy(categoryId, editingStrategy.id, {
       name: nextName,
       content: editingContent,
+      ...buildStrategyMetadata(editingMetadata),
     })
     try {
       await saveNow()
@@ -396,7 +510,7 @@ export function CategoryPage() {
       revertTo(previousConfig)
       toast.error(`Ошибка сохранения стратегии: ${e instanceof Error ? e.message : String(e)}`)
     }
-  }, [editingStrategy, categoryId, editingName, editingContent, updateStrategy, saveNow, addConfigLog, revertTo])
+  }, [editingStrategy, categoryId, editingName, editingContent, editingMetadata, updateStrategy, saveNow, addConfigLog, revertTo])
 
   const handleSetActive = useCallback(async (strategyId: string) => {
     if (!categoryId)
@@ -549,18 +663,34 @@ export function CategoryPage() {
     }
   }, [categoryId, deleteStrategy, saveNow, addConfigLog, restartIfConnected, revertTo])
 
-  const onSystemActionClick = useCallback((strategyId: string, name: string, updateAvailable: boolean) => {
+  const onSystemActionClick = useCallback(async (strategyId: string, name: string, updateAvailable: boolean) => {
+    // Non-conflicting builtin updates are merged on load, so a pending update
+    // usually means the user's edits clash with it.
+    let conflicts: MergeConflict[] = []
+    if (updateAvailable) {
+      try {
+        const reports = await tauri.getBuiltinMergeConflicts()
+        conflicts = reports.find(report => report.categoryId === categoryId && report.strategyId === strategyId)?.conflicts ?? []
+      }
+      catch (error) {
+        console.error('Failed to get builtin merge conflicts:', error)
+      }
+    }
+
     setSystemActionTarget({
       type: 'strategy',
       strategyId,
       title: updateAvailable
         ? 'Обновить системную стратегию?'
         : 'Откатить стратегию к системному значению?',
-      description: updateAvailable
-        ? `Стратегия «${name}» будет обновлена до актуальной системной версии.`
-        : `Стратегия «${name}» будет возвращена к системному значению.`,
+      description: conflicts.length > 0
+        ? `Изменения в стратегии «${name}» конфликтуют с новой системной версией. Можно взять системную версию целиком или оставить свою.`
+        : updateAvailable
+          ? `Стратегия «${name}» будет обновлена до актуальной системной версии.`
+          : `Стратегия «${name}» будет возвращена к системному значению.`,
+      conflicts,
     })
-  }, [])
+  }, [categoryId])
 
   const handleDeleteCategory = async () => {
     if (categoryId) {
@@ -607,17 +737,37 @@ export function CategoryPage() {
       return
     }
 
+    // Mirrors the backend normalization: the default group is stored as no
+    // group at all so that such categories keep running in winws.exe itself.
+    const nextGroup = newInstanceGroup.trim().toLowerCase()
+    if (nextGroup && !INSTANCE_GROUP_PATTERN.test(nextGroup)) {
+      toast.error('Группа может содержать только латинские буквы, цифры, "-" и "_" (до 32 символов)')
+      return
+    }
+
     const previousConfig = structuredClone(currentConfig)
     const previousName = category?.name
     const nextName = newCategoryName.trim()
+    const previousGroup = category?.instanceGroup ?? null
+    const nextInstanceGroup = nextGroup && nextGroup !== DEFAULT_INSTANCE_GROUP ? nextGroup : null
+    const groupChanged = previousGroup !== nextInstanceGroup
     updateCategory(categoryId, nextName)
+    if (groupChanged) {
+      setCategoryInstanceGroup(categoryId, nextInstanceGroup)
+    }
     try {
       await saveNow()
-      if (previousName) {
+      if (previousName && previousName !== nextName) {
         addConfigLog(`категория "${previousName}" переименована в "${nextName}"`)
       }
+      if (groupChanged) {
+        addConfigLog(`категория "${nextName}" перенесена в группу winws "${nextInstanceGroup ?? DEFAULT_INSTANCE_GROUP}"`)
+      }
       setRenameDialogOpen(false)
-      toast.success('Категория переименована')
+      if (groupChanged && activeCount > 0) {
+        await restartIfConnected()
+      }
+      toast.success('Категория сохранена')
     }
     catch (e) {
       revertTo(previousConfig)
@@ -625,9 +775,69 @@ export function CategoryPage() {
     }
   }
 
+  const copyShareCode = useCallback(async (strategyId?: string) => {
+    if (!categoryId) {
+      return
+    }
+
+    try {
+      await saveNow()
+      await navigator.clipboard.writeText(await tauri.encodeShareCode(categoryId, strategyId))
+      toast.success('Код скопирован')
+    }
+    catch (error) {
+      toast.error(`Не удалось создать код: ${error instanceof Error ? error.message : String(error)}`)
+    }
+  }, [categoryId, saveNow])
+
+  const handleShareStrategy = useCallback((strategy: Strategy) => {
+    void copyShareCode(strategy.id)
+  }, [copyShareCode])
+
+  const handleImportShareCode = async () => {
+    if (!category || !importCode.trim()) {
+      return
+    }
+
+    setImporting(true)
+    try {
+      await saveNow()
+      const payload = await tauri.decodeShareCode(importCode)
+      const result = await tauri.importShareCode(importCode, category.id)
+      await reload()
+      if (payload.categoryName) {
+        addConfigLog(`импортирована категория "${payload.categoryName}" (${result.strategyIds.length} стратегий)`)
+      }
+      else {
+        addConfigLog(`импортирована стратегия "${payload.strategies[0]?.name}" в категорию "${category.name}"`)
+      }
+      if (result.addedPlaceholders.length > 0) {
+        addConfigLog(`добавлены плейсхолдеры: ${result.addedPlaceholders.join(', ')}`)
+      }
+      if (result.unresolvedPlaceholders.length > 0) {
+        toast.warning(`Импортировано, но нужно задать плейсхолдеры: ${result.unresolvedPlaceholders.join(', ')}`)
+      }
+      else {
+        toast.success('Импортировано')
+      }
+      setImportDialogOpen(false)
+      setImportCode('')
+      if (result.categoryId !== category.id) {
+        void navigate({ to: '/strategies/$categoryId', params: { categoryId: result.categoryId } })
+      }
+    }
+    catch (error) {
+      toast.error(`Ошибка импорта: ${error instanceof Error ? error.message : String(error)}`)
+    }
+    finally {
+      setImporting(false)
+    }
+  }
+
   const openRenameDialog = () => {
     if (category) {
       setNewCategoryName(category.name)
+      setNewInstanceGroup(category.instanceGroup ?? '')
       setRenameDialogOpen(true)
     }
   }
@@ -666,6 +876,26 @@ export function CategoryPage() {
     }
   }
 
+  const handleKeepUserStrategy = async (strategyId: string) => {
+    if (!category) {
+      return
+    }
+
+    const strategy = category.strategies.find(item => item.id === strategyId)
+    try {
+      await saveNow()
+      await tauri.keepUserStrategyContent(category.id, strategyId)
+      await reload()
+      addConfigLog(`в стратегии "${strategy?.name ?? strategyId}" оставлена пользовательская версия вместо системного обновления`)
+    }
+    catch (error) {
+      toast.error(`Не удалось сохранить выбор: ${error instanceof Error ? error.message : String(error)}`)
+    }
+    finally {
+      setSystemActionTarget(null)
+    }
+  }
+
   const handleRestoreStrategy = async (strategyId: string) => {
     if (!category || !builtinCategory) {
       return
@@ -836,11 +1066,27 @@ export function CategoryPage() {
               </Tooltip>
               <Tooltip>
                 <TooltipTrigger asChild>
-                  <Button variant="outline" size="icon" onClick={openRenameDialog} aria-label="Переименовать категорию">
+                  <Button variant="outline" size="icon" onClick={() => setImportDialogOpen(true)} aria-label="Импорт по коду">
+                    <ClipboardPaste className="size-4" />
+                  </Button>
+                </TooltipTrigger>
+                <TooltipContent>Импорт по коду</TooltipContent>
+              </Tooltip>
+              <Tooltip>
+                <TooltipTrigger asChild>
+                  <Button variant="outline" size="icon" onClick={() => void copyShareCode()} aria-label="Скопировать код категории">
+                    <Share2 className="size-4" />
+                  </Button>
+                </TooltipTrigger>
+                <TooltipContent>Скопировать код категории</TooltipContent>
+              </Tooltip>
+              <Tooltip>
+                <TooltipTrigger asChild>
+                  <Button variant="outline" size="icon" onClick={openRenameDialog} aria-label="Параметры категории">
                     <Pencil className="size-4" />
                   </Button>
                 </TooltipTrigger>
-                <TooltipContent>Переименовать категорию</TooltipContent>
+                <TooltipContent>Параметры категории</TooltipContent>
               </Tooltip>
               {category.strategies.some(s => s.active) && (
                 <Tooltip>
@@ -922,6 +1168,7 @@ export function CategoryPage() {
                         handleEditStrategy={handleEditStrategy}
                         handleClearActive={handleClearActive}
                         handleDeleteStrategy={handleDeleteStrategy}
+                        handleShareStrategy={handleShareStrategy}
                         onSystemActionClick={onSystemActionClick}
                       />
                     )
@@ -935,8 +1182,32 @@ export function CategoryPage() {
                 <AlertDialogTitle>{systemActionTarget?.title}</AlertDialogTitle>
                 <AlertDialogDescription>{systemActionTarget?.description}</AlertDialogDescription>
               </AlertDialogHeader>
+              {systemActionTarget?.type === 'strategy' && systemActionTarget.conflicts.length > 0 && (
+                <div className="max-h-64 space-y-3 overflow-auto font-mono text-xs">
+                  {systemActionTarget.conflicts.map(conflict => (
+                    <div key={[...conflict.base, ...conflict.user, ...conflict.builtin].join('\n')} className="grid grid-cols-2 gap-2">
+                      <div className="space-y-1">
+                        <p className="font-sans font-medium">Ваша версия</p>
+                        <pre className="whitespace-pre-wrap break-all text-muted-foreground">{conflict.user.join('\n') || '—'}</pre>
+                      </div>
+                      <div className="space-y-1">
+                        <p className="font-sans font-medium">Системная версия</p>
+                        <pre className="whitespace-pre-wrap break-all text-muted-foreground">{conflict.builtin.join('\n') || '—'}</pre>
+                      </div>
+                    </div>
+                  ))}
+                </div>
+              )}
               <AlertDialogFooter>
                 <AlertDialogCancel>Отмена</AlertDialogCancel>
+                {systemActionTarget?.type === 'strategy' && systemActionTarget.conflicts.length > 0 && (
+                  <Button
+                    variant="outline"
+                    onClick={() => void handleKeepUserStrategy(systemActionTarget.strategyId)}
+                  >
+                    Оставить мою
+                  </Button>
+                )}
                 <Button
                   onClick={async () => {
                     if (!systemActionTarget) {
@@ -951,7 +1222,7 @@ export function CategoryPage() {
                     await handleRestoreStrategy(systemActionTarget.strategyId)
                   }}
                 >
-                  Обновить
+                  {systemActionTarget?.type === 'strategy' && systemActionTarget.conflicts.length > 0 ? 'Взять системную' : 'Обновить'}
                 </Button>
               </AlertDialogFooter>
             </AlertDialogContent>
@@ -981,10 +1252,12 @@ export function CategoryPage() {
                     value={newStrategyContent}
                     onChange={(e) => {
                       setNewStrategyContent(e.target.value)
+                      lintNewStrategy(e.target.value)
                       autosizeTextarea(e.currentTarget)
                     }}
                     rows={10}
                   />
+                  <StrategyDiagnosticsList diagnostics={newStrategyDiagnostics} />
                   <p className="text-xs text-muted-foreground">
                     <code className="rounded bg-muted px-1 py-0.5 font-mono text-[0.72rem] text-foreground">
                       {'<LIST_MODE>'}
@@ -1027,10 +1300,12 @@ export function CategoryPage() {
                     value={editingContent}
                     onChange={(e) => {
                       setEditingContent(e.target.value)
+                      lintEditStrategy(e.target.value)
                       autosizeTextarea(e.currentTarget)
                     }}
                     rows={10}
                   />
+                  <StrategyDiagnosticsList diagnostics={editStrategyDiagnostics} />
                   <p className="text-xs text-muted-foreground">
                     <code className="rounded bg-muted px-1 py-0.5 font-mono text-[0.72rem] text-foreground">
                       {'<LIST_MODE>'}
@@ -1039,6 +1314,52 @@ export function CategoryPage() {
                     автоматически заменяется на текущий режим списков: список исключений или список заблокированных адресов.
                   </p>
                 </div>
+                <div className="space-y-2">
+                  <Label htmlFor="edit-strategy-description">Описание</Label>
+                  <Input
+                    id="edit-strategy-description"
+                    placeholder="Для чего подходит стратегия"
+                    value={editingMetadata.description}
+                    onChange={e => setEditingMetadata(current => ({ ...current, description: e.target.value }))}
+                  />
+                </div>
+                <div className="grid grid-cols-2 gap-4">
+                  <div className="space-y-2">
+                    <Label htmlFor="edit-strategy-targets">Сервисы и домены</Label>
+                    <Input
+                      id="edit-strategy-targets"
+                      placeholder="YouTube, discord.com"
+                      value={editingMetadata.targets}
+                      onChange={e => setEditingMetadata(current => ({ ...current, targets: e.target.value }))}
+                    />
+                  </div>
+                  <div className="space-y-2">
+                    <Label htmlFor="edit-strategy-tags">Теги</Label>
+                    <Input
+                      id="edit-strategy-tags"
+                      placeholder="fake, multisplit"
+                      value={editingMetadata.tags}
+                      onChange={e => setEditingMetadata(current => ({ ...current, tags: e.target.value }))}
+                    />
+                  </div>
+                  <div className="space-y-2">
+                    <Label htmlFor="edit-strategy-author">Автор</Label>
+                    <Input
+                      id="edit-strategy-author"
+                      value={editingMetadata.author}
+                      onChange={e => setEditingMetadata(current => ({ ...current, author: e.target.value }))}
+                    />
+                  </div>
+                  <div className="space-y-2">
+                    <Label htmlFor="edit-strategy-last-verified">Последняя проверка</Label>
+                    <Input
+                      id="edit-strategy-last-verified"
+                      type="date"
+                      value={editingMetadata.lastVerified}
+                      onChange={e => setEditingMetadata(current => ({ ...current, lastVerified: e.target.value }))}
+                    />
+                  </div>
+                </div>
               </div>
               <DialogFooter>
                 <Button variant="outline" onClick={() => setEditingStrategy(null)}>
@@ -1052,9 +1373,9 @@ export function CategoryPage() {
           <Dialog open={renameDialogOpen} onOpenChange={setRenameDialogOpen}>
             <DialogContent>
               <DialogHeader>
-                <DialogTitle>Переименовать категорию</DialogTitle>
+                <DialogTitle>Параметры категории</DialogTitle>
               </DialogHeader>
-              <div className="py-4">
+              <div className="space-y-4 py-4">
                 <div className="space-y-2">
                   <Label htmlFor="category-name">Название категории</Label>
                   <Input
@@ -1065,6 +1386,19 @@ export function CategoryPage() {
                     onKeyDown={e => e.key === 'Enter' && handleRenameCategory()}
                   />
                 </div>
+                <div className="space-y-2">
+                  <Label htmlFor="category-instance-group">Группа winws</Label>
+                  <Input
+                    id="category-instance-group"
+                    placeholder={DEFAULT_INSTANCE_GROUP}
+                    value={newInstanceGroup}
+                    onChange={e => setNewInstanceGroup(e.target.value)}
+                    onKeyDown={e => e.key === 'Enter' && handleRenameCategory()}
+                  />
+                  <p className="text-xs text-muted-foreground">
+                    Категории с одинаковой группой работают в отдельном процессе winws.exe со своими портами. Падение или перезапуск одной группы не затрагивает остальные.
+                  </p>
+                </div>
               </div>
               <DialogFooter>
                 <Button variant="outline" onClick={() => setRenameDialogOpen(false)}>
@@ -1074,6 +1408,35 @@ export function CategoryPage() {
               </DialogFooter>
             </DialogContent>
           </Dialog>
+
+          <Dialog open={importDialogOpen} onOpenChange={setImportDialogOpen}>
+            <DialogContent>
+              <DialogHeader>
+                <DialogTitle>Импорт по коду</DialogTitle>
+                <DialogDescription>
+                  Код стратегии добавит её в эту категорию, код категории создаст новую категорию
+                </DialogDescription>
+              </DialogHeader>
+              <div className="space-y-2 py-4">
+                <Label htmlFor="share-code">Код</Label>
+                <Input
+                  id="share-code"
+                  placeholder="zs1...."
+                  value={importCode}
+                  onChange={e => setImportCode(e.target.value)}
+                  onKeyDown={e => e.key === 'Enter' && void handleImportShareCode()}
+                />
+              </div>
+              <DialogFooter>
+                <Button variant="outline" onClick={() => setImportDialogOpen(false)}>
+                  Отмена
+                </Button>
+                <Button disabled={importing || !importCode.trim()} onClick={() => void handleImportShareCode()}>
+                  Импортировать
+                </Button>
+              </DialogFooter>
+            </DialogContent>
+          </Dialog>
         </div>
       </LenisScrollArea>
       <ScrollTopButton scrollAreaRef={scrollAreaRef} resetKeys={[categoryId, loading]} />
@@ -0,0 +1,172 @@
+import type { ConfigChangeKind, ConfigHistoryDiff, ConfigHistoryEntry, ConfigItemChange } from '@/lib/types'
+import { History, RefreshCw, RotateCcw } from 'lucide-react'
+import { useState } from 'react'
+import { toast } from 'sonner'
+import { MODULE_PAGE_CARD_CLASS, ModuleSectionHeader } from '@/components/features/module-ui'
+import {
+  AlertDialog,
+  AlertDialogAction,
+  AlertDialogCancel,
+  AlertDialogContent,
+  AlertDialogDescription,
+  AlertDialogFooter,
+  AlertDialogHeader,
+  AlertDialogTitle,
+} from '@/components/ui/alert-dialog'
+import { Button } from '@/components/ui/button'
+import { Card, CardContent } from '@/components/ui/card'
+import { useMountEffect } from '@/hooks/use-mount-effect'
+import * as tauri from '@/lib/tauri'
+import { useConfigStore } from '@/stores/config.store'
+import { useConnectionStore } from '@/stores/connection.store'
+
+const VISIBLE_ENTRIES = 10
+
+// The diff describes changes made since the snapshot, so restoring reverts them.
+const REVERT_LABELS: Record<ConfigChangeKind, string> = {
+  added: 'будет удалено',
+  removed: 'будет возвращено',
+  modified: 'будет изменено',
+}
+
+const DIFF_SECTIONS: { key: keyof ConfigHistoryDiff, title: string }[] = [
+  { key: 'categories', title: 'Категории' },
+  { key: 'strategies', title: 'Стратегии' },
+  { key: 'placeholders', title: 'Плейсхолдеры' },
+  { key: 'filters', title: 'Фильтры' },
+]
+
+function formatHistoryDate(timestamp: number) {
+  return new Date(timestamp).toLocaleString('ru-RU')
+}
+
+function describeChange(change: ConfigItemChange) {
+  const fields = change.fields.length > 0 ? ` (${change.fields.join(', ')})` : ''
+  return `${REVERT_LABELS[change.change]}${fields}`
+}
+
+interface HistoryPreview {
+  entry: ConfigHistoryEntry
+  diff: ConfigHistoryDiff
+}
+
+export function ConfigHistorySection() {
+  const [entries, setEntries] = useState<ConfigHistoryEntry[]>([])
+  const [busy, setBusy] = useState(false)
+  const [preview, setPreview] = useState<HistoryPreview | null>(null)
+  const reload = useConfigStore(state => state.reload)
+  const addConfigLog = useConnectionStore(state => state.addConfigLog)
+
+  const refreshEntries = async () => {
+    try {
+      setEntries(await tauri.listConfigHistory())
+    }
+    catch (e) {
+      toast.error(`Не удалось получить историю конфигурации: ${e}`)
+    }
+  }
+
+  useMountEffect(() => {
+    void refreshEntries()
+  })
+
+  const handlePreview = async (entry: ConfigHistoryEntry) => {
+    setBusy(true)
+    try {
+      setPreview({ entry, diff: await tauri.diffConfigHistory(entry.id) })
+    }
+    catch (e) {
+      toast.error(`Не удалось прочитать снимок: ${e}`)
+    }
+    finally {
+      setBusy(false)
+    }
+  }
+
+  const handleRestore = async () => {
+    if (!preview) {
+      return
+    }
+
+    setBusy(true)
+    try {
+      await tauri.restoreConfigHistory(preview.entry.id)
+      await reload()
+      addConfigLog(`восстановлена конфигурация от ${formatHistoryDate(preview.entry.createdAt)}`)
+      toast.success('Конфигурация восстановлена')
+      setPreview(null)
+      await refreshEntries()
+    }
+    catch (e) {
+      toast.error(`Ошибка восстановления: ${e}`)
+    }
+    finally {
+      setBusy(false)
+    }
+  }
+
+  const hasChanges = !!preview && DIFF_SECTIONS.some(section => preview.diff[section.key].length > 0)
+
+  return (
+    <Card className={MODULE_PAGE_CARD_CLASS}>
+      <ModuleSectionHeader
+        icon={History}
+        title="История конфигурации"
+        description="Предыдущие версии config.json сохраняются автоматически при каждом изменении"
+        action={(
+          <Button variant="outline" size="sm" disabled={busy} onClick={() => void refreshEntries()}>
+            <RefreshCw className="size-4" />
+            Обновить
+          </Button>
+        )}
+      />
+      <CardContent className="space-y-2 p-4!">
+        {entries.length === 0
+          ? <p className="text-xs text-muted-foreground">Сохранённых версий пока нет</p>
+          : entries.slice(0, VISIBLE_ENTRIES).map(entry => (
+              <div key={entry.id} className="flex items-center justify-between gap-4">
+                <p className="truncate text-sm">{formatHistoryDate(entry.createdAt)}</p>
+                <Button variant="outline" size="sm" disabled={busy} onClick={() => void handlePreview(entry)}>
+                  <RotateCcw className="size-4" />
+                  Сравнить
+                </Button>
+              </div>
+            ))}
+      </CardContent>
+
+      <AlertDialog open={!!preview} onOpenChange={open => !open && setPreview(null)}>
+        <AlertDialogContent>
+          <AlertDialogHeader>
+            <AlertDialogTitle>Вернуть конфигурацию?</AlertDialogTitle>
+            <AlertDialogDescription>
+              {preview && (hasChanges
+                ? `Версия от ${formatHistoryDate(preview.entry.createdAt)}. При восстановлении:`
+                : `Версия от ${formatHistoryDate(preview.entry.createdAt)} не отличается от текущей по категориям, стратегиям, плейсхолдерам и фильтрам.`)}
+            </AlertDialogDescription>
+          </AlertDialogHeader>
+          <div className="max-h-64 space-y-3 overflow-auto text-xs">
+            {preview && DIFF_SECTIONS.filter(section => preview.diff[section.key].length > 0).map(section => (
+              <div key={section.key} className="space-y-1">
+                <p className="font-medium">{section.title}</p>
+                <ul className="space-y-1">
+                  {preview.diff[section.key].map(change => (
+                    <li key={change.id} className="flex justify-between gap-4">
+                      <span className="truncate">{change.name}</span>
+                      <span className="shrink-0 text-muted-foreground">{describeChange(change)}</span>
+                    </li>
+                  ))}
+                </ul>
+              </div>
+            ))}
+          </div>
+          <AlertDialogFooter>
+            <AlertDialogCancel>Отмена</AlertDialogCancel>
+            <AlertDialogAction disabled={busy} onClick={handleRestore}>
+              Восстановить
+            </AlertDialogAction>
+          </AlertDialogFooter>
+        </AlertDialogContent>
+      </AlertDialog>
+    </Card>
+  )
+}
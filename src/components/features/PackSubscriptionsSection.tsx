@@ -0,0 +1,193 @@
+import type { PackSubscription } from '@/lib/types'
+import { Package, Plus, RefreshCw, Trash2 } from 'lucide-react'
+import { useState } from 'react'
+import { toast } from 'sonner'
+import { MODULE_PAGE_CARD_CLASS, ModuleSectionHeader } from '@/components/features/module-ui'
+import { Button } from '@/components/ui/button'
+import { Card, CardContent } from '@/components/ui/card'
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog'
+import { Input } from '@/components/ui/input'
+import { Label } from '@/components/ui/label'
+import * as tauri from '@/lib/tauri'
+import { useConfigStore } from '@/stores/config.store'
+import { useConnectionStore } from '@/stores/connection.store'
+
+interface AddPackDialogState {
+  url: string
+  publicKey: string
+}
+
+function describeSubscription(subscription: PackSubscription) {
+  if (subscription.lastError) {
+    return `Ошибка обновления: ${subscription.lastError}`
+  }
+  const version = subscription.version ? `версия ${subscription.version}` : 'без версии'
+  const verification = subscription.publicKey ? 'подпись minisign' : 'SHA-256'
+  return `${version}, ${verification}`
+}
+
+export function PackSubscriptionsSection() {
+  const config = useConfigStore(state => state.config)
+  const reload = useConfigStore(state => state.reload)
+  const saveNow = useConfigStore(state => state.saveNow)
+  const addConfigLog = useConnectionStore(state => state.addConfigLog)
+  const [busy, setBusy] = useState(false)
+  const [addDialog, setAddDialog] = useState<AddPackDialogState | null>(null)
+
+  const subscriptions = config?.packSubscriptions ?? []
+
+  const runPackAction = async <T,>(action: () => Promise<T>, errorPrefix: string) => {
+    setBusy(true)
+    try {
+      // Packs are merged into the persisted config, so flush pending edits first.
+      await saveNow()
+      const result = await action()
+      await reload()
+      return result
+    }
+    catch (e) {
+      toast.error(`${errorPrefix}: ${e}`)
+      return null
+    }
+    finally {
+      setBusy(false)
+    }
+  }
+
+  const handleAdd = async () => {
+    if (!addDialog) {
+      return
+    }
+
+    const url = addDialog.url.trim()
+    if (!url.startsWith('https://')) {
+      toast.error('Адрес пакета должен начинаться с https://')
+      return
+    }
+
+    const publicKey = addDialog.publicKey.trim() || null
+    const config = await runPackAction(() => tauri.addPackSubscription(url, publicKey), 'Не удалось подписаться на пакет')
+    if (config) {
+      const name = config.packSubscriptions?.find(subscription => subscription.url === url)?.name ?? url
+      addConfigLog(`добавлен пакет стратегий "${name}"`)
+      setAddDialog(null)
+    }
+  }
+
+  const handleRefresh = async () => {
+    const results = await runPackAction(() => tauri.refreshPackSubscriptions(true), 'Не удалось обновить пакеты')
+    if (!results) {
+      return
+    }
+
+    const failed = results.filter(result => result.error)
+    for (const result of results) {
+      addConfigLog(result.error
+        ? `не удалось обновить пакет "${result.name}": ${result.error}`
+        : `пакет стратегий "${result.name}" ${result.updated ? 'обновлён' : 'без изменений'}`)
+    }
+    if (failed.length > 0) {
+      toast.error(`Не удалось обновить пакетов: ${failed.length}`)
+    }
+    else {
+      toast.success('Пакеты стратегий проверены')
+    }
+  }
+
+  const handleRemove = async (subscription: PackSubscription) => {
+    if (await runPackAction(() => tauri.removePackSubscription(subscription.id), 'Не удалось удалить подписку')) {
+      addConfigLog(`удалён пакет стратегий "${subscription.name}"`)
+    }
+  }
+
+  return (
+    <Card className={MODULE_PAGE_CARD_CLASS}>
+      <ModuleSectionHeader
+        icon={Package}
+        title="Пакеты стратегий"
+        description="Подписки на внешние наборы категорий и стратегий. Изменённые вами стратегии не перезаписываются"
+        action={(
+          <div className="flex gap-2">
+            {subscriptions.length > 0 && (
+              <Button variant="outline" size="sm" disabled={busy} onClick={() => void handleRefresh()}>
+                <RefreshCw className="size-4" />
+                Обновить
+              </Button>
+            )}
+            <Button size="sm" disabled={busy || !config} onClick={() => setAddDialog({ url: '', publicKey: '' })}>
+              <Plus className="size-4" />
+              Подписаться
+            </Button>
+          </div>
+        )}
+      />
+      <CardContent className="space-y-2 p-4!">
+        {subscriptions.length === 0
+          ? <p className="text-xs text-muted-foreground">Подписок пока нет. Пакеты обновляются автоматически раз в 12 часов</p>
+          : subscriptions.map(subscription => (
+              <div key={subscription.id} className="flex items-center justify-between gap-4">
+                <div className="min-w-0">
+                  <p className="truncate text-sm">{subscription.name || subscription.url}</p>
+                  <p className={`truncate text-xs ${subscription.lastError ? 'text-destructive' : 'text-muted-foreground'}`}>
+                    {describeSubscription(subscription)}
+                  </p>
+                </div>
+                <Button
+                  variant="ghost"
+                  size="icon-sm"
+                  disabled={busy}
+                  aria-label="Удалить подписку"
+                  onClick={() => void handleRemove(subscription)}
+                >
+                  <Trash2 className="size-4" />
+                </Button>
+              </div>
+            ))}
+      </CardContent>
+
+      <Dialog open={!!addDialog} onOpenChange={open => !open && setAddDialog(null)}>
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>Подписка на пакет стратегий</DialogTitle>
+            <DialogDescription>
+              Без ключа рядом с пакетом должен лежать файл .sha256, с ключом — подпись .minisig
+            </DialogDescription>
+          </DialogHeader>
+          <div className="space-y-4 py-4">
+            <div className="space-y-2">
+              <Label htmlFor="pack-url">Адрес пакета</Label>
+              <Input
+                id="pack-url"
+                placeholder="https://example.com/pack.json"
+                value={addDialog?.url ?? ''}
+                onChange={e => setAddDialog(current => current && { ...current, url: e.target.value })}
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="pack-public-key">Публичный ключ minisign (необязательно)</Label>
+              <Input
+                id="pack-public-key"
+                placeholder="RWQ..."
+                value={addDialog?.publicKey ?? ''}
+                onChange={e => setAddDialog(current => current && { ...current, publicKey: e.target.value })}
+              />
+            </div>
+          </div>
+          <DialogFooter>
+            <Button variant="outline" onClick={() => setAddDialog(null)}>
+              Отмена
+            </Button>
+            <Button disabled={busy} onClick={() => void handleAdd()}>Подписаться</Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    </Card>
+  )
+}
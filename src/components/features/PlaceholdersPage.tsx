@@ -1,5 +1,5 @@
-import type { Placeholder } from '@/lib/types'
-import { FileCode, FilePenLine, FolderOpen, Loader2, Package, Pencil, Plus, RefreshCcw, RotateCcw, Trash2, UserRoundPlus } from 'lucide-react'
+import type { Placeholder, PlaceholderIssueKind, PlaceholderKind, PlaceholderStatus } from '@/lib/types'
+import { Braces, FileCode, FilePenLine, FolderCog, FolderOpen, Loader2, Lock, Package, Pencil, Plus, RefreshCcw, RotateCcw, Trash2, TriangleAlert, UserRoundPlus } from 'lucide-react'
 import { useRef, useState } from 'react'
 import { toast } from 'sonner'
 import {
@@ -25,6 +25,13 @@ import { Input } from '@/components/ui/input'
 import { Label } from '@/components/ui/label'
 import { LenisScrollArea } from '@/components/ui/lenis-scroll-area'
 import { ScrollTopButton } from '@/components/ui/scroll-top-button'
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select'
 import { useMountEffect } from '@/hooks/use-mount-effect'
 import { buildRestoredPlaceholder, getBuiltinPlaceholder, isSystemPlaceholder, isSystemPlaceholderModified, isSystemPlaceholderUpdateAvailable } from '@/lib/system-config'
 import * as tauri from '@/lib/tauri'
@@ -36,6 +43,25 @@ const LEADING_RESOURCE_SEPARATORS = /^[/\\]+/
 const PATH_SEGMENT_SEPARATOR = /[/\\]+/g
 const TRAILING_SLASHES_RE = /[/\\]+$/
 
+const PLACEHOLDER_KIND_OPTIONS: { value: PlaceholderKind, label: string, hint: string }[] = [
+  { value: 'path', label: 'Путь к файлу', hint: 'Путь к файлу' },
+  { value: 'string', label: 'Строка', hint: 'Значение без пробелов, например SNI' },
+  { value: 'integer', label: 'Целое число', hint: 'Например, TTL или позиция разреза' },
+  { value: 'hex', label: 'Hex-блоб', hint: 'Например, 0x1603' },
+  { value: 'domainList', label: 'Список доменов', hint: 'Домены через запятую' },
+]
+
+const PLACEHOLDER_ISSUE_LABELS: Record<PlaceholderIssueKind, string> = {
+  unknown: 'Плейсхолдер не определён',
+  missingTarget: 'Файл не найден',
+  emptyTarget: 'Файл пуст',
+  invalidValue: 'Значение не подходит под тип',
+}
+
+function getKindOption(kind: PlaceholderKind | undefined) {
+  return PLACEHOLDER_KIND_OPTIONS.find(option => option.value === (kind ?? 'path')) ?? PLACEHOLDER_KIND_OPTIONS[0]
+}
+
 function isResourcesAliasPath(path: string) {
   const lowerCasePath = path.toLowerCase()
   if (!lowerCasePath.startsWith(RESOURCES_ALIAS_PREFIX)) {
@@ -46,13 +72,32 @@ function isResourcesAliasPath(path: string) {
   return nextCharacter === undefined || nextCharacter === '/' || nextCharacter === '\\'
 }
 
+function PlaceholderKindSelect({ id, value, onChange }: { id: string, value: PlaceholderKind, onChange: (kind: PlaceholderKind) => void }) {
+  return (
+    <Select value={value} onValueChange={kind => onChange(kind as PlaceholderKind)}>
+      <SelectTrigger id={id} className="w-full cursor-pointer">
+        <SelectValue>{getKindOption(value).label}</SelectValue>
+      </SelectTrigger>
+      <SelectContent>
+        {PLACEHOLDER_KIND_OPTIONS.map(option => (
+          <SelectItem key={option.value} value={option.value}>{option.label}</SelectItem>
+        ))}
+      </SelectContent>
+    </Select>
+  )
+}
+
 export function PlaceholdersPage() {
   const [editingIndex, setEditingIndex] = useState<number | null>(null)
   const [editName, setEditName] = useState('')
   const [editPath, setEditPath] = useState('')
+  const [editKind, setEditKind] = useState<PlaceholderKind>('path')
   const [addOpen, setAddOpen] = useState(false)
   const [newName, setNewName] = useState('')
   const [newPath, setNewPath] = useState('')
+  const [newKind, setNewKind] = useState<PlaceholderKind>('path')
+  const [statuses, setStatuses] = useState<PlaceholderStatus[]>([])
+  const [dynamicPlaceholders, setDynamicPlaceholders] = useState<PlaceholderStatus[]>([])
   const [resourcesDir, setResourcesDir] = useState('')
   const [systemPlaceholderTarget, setSystemPlaceholderTarget] = useState<Placeholder | null>(null)
   const isSavingRef = useRef(false)
@@ -69,8 +114,22 @@ export function PlaceholdersPage() {
   const replacePlaceholdersState = useConfigStore(state => state.replacePlaceholdersState)
   const addConfigLog = useConnectionStore(state => state.addConfigLog)
 
+  const refreshStatuses = () => {
+    void tauri.getPlaceholderStatuses()
+      .then(setStatuses)
+      .catch((error) => {
+        console.error('Failed to check placeholders:', error)
+      })
+  }
+
   useMountEffect(() => {
     void load().catch(console.error)
+    refreshStatuses()
+    void tauri.getDynamicPlaceholders()
+      .then(setDynamicPlaceholders)
+      .catch((error) => {
+        console.error('Failed to get dynamic placeholders:', error)
+      })
     void tauri.getResourcesDirectory()
       .then(setResourcesDir)
       .catch((error) => {
@@ -138,17 +197,27 @@ export function PlaceholdersPage() {
     return relativePath ? `${RESOURCES_ALIAS_PREFIX}/${relativePath}` : RESOURCES_ALIAS_PREFIX
   }
 
-  const validatePlaceholder = (name: string, path: string, excludedIndex?: number) => {
+  const toStoredPlaceholderValue = (value: string, kind: PlaceholderKind) => {
+    return kind === 'path' ? toStoredPlaceholderPath(value) : value.trim()
+  }
+
+  const validatePlaceholder = (name: string, path: string, kind: PlaceholderKind, excludedIndex?: number) => {
     const normalizedName = name.trim().toLocaleLowerCase()
     const normalizedPath = toStoredPlaceholderPath(path).trim().toLocaleLowerCase()
     const placeholders = useConfigStore.getState().config?.placeholders ?? []
 
+    if (dynamicPlaceholders.some(placeholder => placeholder.name.toLocaleLowerCase() === normalizedName)) {
+      toast.error('Это имя зарезервировано встроенным плейсхолдером')
+      return false
+    }
+
     if (placeholders.some((placeholder, index) => index !== excludedIndex && placeholder.name.trim().toLocaleLowerCase() === normalizedName)) {
       toast.error('Плейсхолдер с таким названием уже существует')
       return false
     }
 
-    if (placeholders.some((placeholder, index) => index !== excludedIndex && placeholder.path.trim().toLocaleLowerCase() === normalizedPath)) {
+    // Typed values such as a TTL may legitimately repeat, only files must be unique.
+    if (kind === 'path' && placeholders.some((placeholder, index) => index !== excludedIndex && (placeholder.kind ?? 'path') === 'path' && placeholder.path.trim().toLocaleLowerCase() === normalizedPath)) {
       toast.error('Плейсхолдер с таким путём уже существует')
       return false
     }
@@ -173,17 +242,19 @@ export function PlaceholdersPage() {
 
     const previousConfig = structuredClone(currentConfig)
     const placeholderName = newName.trim()
-    const placeholderPath = toStoredPlaceholderPath(newPath.trim())
-    if (!validatePlaceholder(placeholderName, placeholderPath)) {
+    const placeholderPath = toStoredPlaceholderValue(newPath, newKind)
+    if (!validatePlaceholder(placeholderName, placeholderPath, newKind)) {
       return
     }
-    addPlaceholder(placeholderName, placeholderPath)
+    addPlaceholder(placeholderName, placeholderPath, newKind)
     isSavingRef.current = true
     try {
       await saveNow()
+      refreshStatuses()
       addConfigLog(`добавлен плейсхолдер "{{${placeholderName}}}" -> ${placeholderPath}`)
       setNewName('')
       setNewPath('')
+      setNewKind('path')
       setAddOpen(false)
       toast.success('Плейсхолдер добавлен')
     }
@@ -199,7 +270,8 @@ export function PlaceholdersPage() {
   const handleEdit = (index: number, placeholder: Placeholder) => {
     setEditingIndex(index)
     setEditName(placeholder.name)
-    setEditPath(resolvePlaceholderPath(placeholder.path))
+    setEditKind(placeholder.kind ?? 'path')
+    setEditPath(placeholder.kind && placeholder.kind !== 'path' ? placeholder.path : resolvePlaceholderPath(placeholder.path))
   }
 
   const handleOpenAppDirectory = async () => {
@@ -223,7 +295,7 @@ export function PlaceholdersPage() {
     }
 
     const trimmedName = editName.trim()
-    const trimmedPath = toStoredPlaceholderPath(editPath)
+    const trimmedPath = toStoredPlaceholderValue(editPath, editKind)
     if (!trimmedName || !trimmedPath) {
       return
     }
@@ -235,18 +307,19 @@ export function PlaceholdersPage() {
 
     const previousConfig = structuredClone(currentConfig)
     const previousPlaceholder = previousConfig.placeholders[editingIndex]
-    if (!validatePlaceholder(trimmedName, trimmedPath, editingIndex)) {
+    if (!validatePlaceholder(trimmedName, trimmedPath, editKind, editingIndex)) {
       return
     }
-    updatePlaceholder(editingIndex, trimmedName, trimmedPath)
+    updatePlaceholder(editingIndex, trimmedName, trimmedPath, editKind)
     isSavingRef.current = true
     try {
       await saveNow()
+      refreshStatuses()
       if (previousPlaceholder) {
         addConfigLog(
           previousPlaceholder.name !== trimmedName
             ? `обновлён плейсхолдер "{{${previousPlaceholder.name}}}" -> "{{${trimmedName}}}"`
-            : `путь плейсхолдера "{{${trimmedName}}}" изменён на ${trimmedPath}`,
+            : `значение плейсхолдера "{{${trimmedName}}}" изменено на ${trimmedPath}`,
         )
       }
       setEditingIndex(null)
@@ -275,6 +348,7 @@ export function PlaceholdersPage() {
     isSavingRef.current = true
     try {
       await saveNow()
+      refreshStatuses()
       if (deletedPlaceholder) {
         addConfigLog(`удалён плейсхолдер "{{${deletedPlaceholder.name}}}"`)
       }
@@ -316,6 +390,7 @@ export function PlaceholdersPage() {
     isSavingRef.current = true
     try {
       await saveNow()
+      refreshStatuses()
       addConfigLog(`плейсхолдер "{{${systemPlaceholderTarget.name}}}" обновлён до системного значения`)
       toast.success('Плейсхолдер обновлён')
     }
@@ -345,7 +420,7 @@ export function PlaceholdersPage() {
             <div className="min-w-0 flex-1">
               <h1 className="text-2xl font-medium">Плейсхолдеры</h1>
               <p className="mt-1 text-sm text-muted-foreground">
-                Замена плейсхолдеров на пути к бинарным файлам
+                Замена плейсхолдеров на пути к файлам и типизированные значения
               </p>
             </div>
             <div className="ml-4 flex shrink-0 items-center gap-1">
@@ -378,6 +453,9 @@ export function PlaceholdersPage() {
                     const isSystem = isSystemPlaceholder(placeholder)
                     const isModified = isSystemPlaceholderModified(placeholder)
                     const hasUpdate = isSystemPlaceholderUpdateAvailable(placeholder, builtin)
+                    const isPath = (placeholder.kind ?? 'path') === 'path'
+                    const displayValue = isPath ? resolvePlaceholderPath(placeholder.path) : placeholder.path
+                    const issue = statuses.find(status => status.name === placeholder.name)?.issue
 
                     return (
                       <div
@@ -386,7 +464,7 @@ export function PlaceholdersPage() {
                       >
                         <div className="flex min-w-0 w-0 flex-1 items-center gap-3 overflow-hidden">
                           <div className="text-muted-foreground flex size-9 shrink-0 items-center justify-center rounded-md border border-border/70 bg-muted/25">
-                            <FileCode className="size-4" />
+                            {isPath ? <FileCode className="size-4" /> : <Braces className="size-4" />}
                           </div>
                           <div className="min-w-0 w-0 flex-1 overflow-hidden space-y-1">
                             <div className="flex items-center gap-1 truncate text-sm font-normal text-foreground">
@@ -400,6 +478,9 @@ export function PlaceholdersPage() {
                                 {isModified && (
                                   <InlineMarker icon={FilePenLine} label="Системный плейсхолдер изменён пользователем" className="text-warning" />
                                 )}
+                                {issue && (
+                                  <InlineMarker icon={TriangleAlert} label={PLACEHOLDER_ISSUE_LABELS[issue]} className="text-destructive" />
+                                )}
                                 {isSystem && (isModified || hasUpdate) && (
                                   <InlineMarker
                                     icon={hasUpdate ? RefreshCcw : RotateCcw}
@@ -412,8 +493,8 @@ export function PlaceholdersPage() {
                                 )}
                               </div>
                             </div>
-                            <div className="truncate overflow-hidden text-xs text-muted-foreground/90" title={resolvePlaceholderPath(placeholder.path)}>
-                              {resolvePlaceholderPath(placeholder.path)}
+                            <div className="truncate overflow-hidden text-xs text-muted-foreground/90" title={displayValue}>
+                              {isPath ? displayValue : `${getKindOption(placeholder.kind).label}: ${displayValue}`}
                             </div>
                           </div>
                         </div>
@@ -442,6 +523,36 @@ export function PlaceholdersPage() {
                 )}
           </div>
 
+          {dynamicPlaceholders.length > 0 && (
+            <div className="min-w-0 space-y-3">
+              <div>
+                <h2 className="text-sm font-medium">Встроенные</h2>
+                <p className="text-xs text-muted-foreground">
+                  Вычисляются при запуске и не зависят от машины. Также поддерживаются переменные окружения вида %APPDATA%
+                </p>
+              </div>
+              {dynamicPlaceholders.map(placeholder => (
+                <div
+                  key={placeholder.name}
+                  className="bg-card flex min-h-[4.5rem] items-center gap-3 overflow-hidden rounded-lg border px-4 py-3"
+                >
+                  <div className="text-muted-foreground flex size-9 shrink-0 items-center justify-center rounded-md border border-border/70 bg-muted/25">
+                    <FolderCog className="size-4" />
+                  </div>
+                  <div className="min-w-0 w-0 flex-1 overflow-hidden space-y-1">
+                    <div className="flex items-center gap-1 truncate text-sm font-normal text-foreground">
+                      {`{{${placeholder.name}}}`}
+                      <InlineMarker icon={Lock} label="Встроенный плейсхолдер, изменить нельзя" className="text-muted-foreground" />
+                    </div>
+                    <div className="truncate text-xs text-muted-foreground/90" title={placeholder.resolved}>
+                      {placeholder.resolved}
+                    </div>
+                  </div>
+                </div>
+              ))}
+            </div>
+          )}
+
           <Dialog open={addOpen} onOpenChange={setAddOpen}>
             <DialogContent>
               <DialogHeader>
@@ -454,13 +565,14 @@ export function PlaceholdersPage() {
                   value={newName}
                   onChange={e => setNewName(e.target.value)}
                 />
+                <PlaceholderKindSelect id="new-placeholder-kind" value={newKind} onChange={setNewKind} />
                 <Input
-                  aria-label="Путь плейсхолдера"
-                  placeholder="Путь к файлу"
+                  aria-label="Значение плейсхолдера"
+                  placeholder={getKindOption(newKind).hint}
                   value={newPath}
                   onChange={e => setNewPath(e.target.value)}
                 />
-                {newPath.trim() && (
+                {newKind === 'path' && newPath.trim() && (
                   <p className="text-xs text-muted-foreground break-all">
                     {resolvePlaceholderPath(toStoredPlaceholderPath(newPath))}
                   </p>
@@ -492,11 +604,15 @@ export function PlaceholdersPage() {
                   />
                 </div>
                 <div className="space-y-2">
-                  <Label htmlFor="edit-placeholder-path">Путь</Label>
+                  <Label htmlFor="edit-placeholder-kind">Тип</Label>
+                  <PlaceholderKindSelect id="edit-placeholder-kind" value={editKind} onChange={setEditKind} />
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="edit-placeholder-path">Значение</Label>
                   <Input
                     id="edit-placeholder-path"
-                    aria-label="Путь плейсхолдера"
-                    placeholder="Путь к файлу"
+                    aria-label="Значение плейсхолдера"
+                    placeholder={getKindOption(editKind).hint}
                     value={editPath}
                     onChange={e => setEditPath(e.target.value)}
                   />
@@ -0,0 +1,188 @@
+import type { StrategyProfile } from '@/lib/types'
+import { Check, Layers, Pencil, Plus, Trash2 } from 'lucide-react'
+import { useState } from 'react'
+import { toast } from 'sonner'
+import { MODULE_PAGE_CARD_CLASS, ModuleSectionHeader } from '@/components/features/module-ui'
+import { Button } from '@/components/ui/button'
+import { Card, CardContent } from '@/components/ui/card'
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog'
+import { Input } from '@/components/ui/input'
+import { Label } from '@/components/ui/label'
+import * as tauri from '@/lib/tauri'
+import { useConfigStore } from '@/stores/config.store'
+import { useConnectionStore } from '@/stores/connection.store'
+
+interface ProfileNameDialogState {
+  profile: StrategyProfile | null
+  name: string
+}
+
+export function ProfilesSection() {
+  const config = useConfigStore(state => state.config)
+  const reload = useConfigStore(state => state.reload)
+  const saveNow = useConfigStore(state => state.saveNow)
+  const addConfigLog = useConnectionStore(state => state.addConfigLog)
+  const [busy, setBusy] = useState(false)
+  const [nameDialog, setNameDialog] = useState<ProfileNameDialogState | null>(null)
+
+  const profiles = config?.profiles ?? []
+  const activeProfileId = config?.activeProfileId
+
+  const runProfileAction = async (action: () => Promise<unknown>, errorPrefix: string) => {
+    setBusy(true)
+    try {
+      // Profiles are captured from the persisted config, so flush pending edits first.
+      await saveNow()
+      await action()
+      await reload()
+      return true
+    }
+    catch (e) {
+      toast.error(`${errorPrefix}: ${e}`)
+      return false
+    }
+    finally {
+      setBusy(false)
+    }
+  }
+
+  const handleSwitch = async (profile: StrategyProfile) => {
+    setBusy(true)
+    try {
+      await saveNow()
+      // Reload and reconnect are driven by the profile-changed event.
+      await tauri.switchProfile(profile.id)
+    }
+    catch (e) {
+      toast.error(`Не удалось переключить профиль: ${e}`)
+    }
+    finally {
+      setBusy(false)
+    }
+  }
+
+  const handleSubmitName = async () => {
+    if (!nameDialog) {
+      return
+    }
+
+    const name = nameDialog.name.trim()
+    if (!name) {
+      toast.error('Введите название профиля')
+      return
+    }
+
+    const { profile } = nameDialog
+    const saved = profile
+      ? await runProfileAction(() => tauri.renameProfile(profile.id, name), 'Не удалось переименовать профиль')
+      : await runProfileAction(() => tauri.createProfile(name), 'Не удалось создать профиль')
+    if (saved) {
+      addConfigLog(profile ? `профиль "${profile.name}" переименован в "${name}"` : `создан профиль "${name}"`)
+      setNameDialog(null)
+    }
+  }
+
+  const handleDelete = async (profile: StrategyProfile) => {
+    if (await runProfileAction(() => tauri.deleteProfile(profile.id), 'Не удалось удалить профиль')) {
+      addConfigLog(`удалён профиль "${profile.name}"`)
+    }
+  }
+
+  return (
+    <Card className={MODULE_PAGE_CARD_CLASS}>
+      <ModuleSectionHeader
+        icon={Layers}
+        title="Профили"
+        description="Наборы активных стратегий, фильтров, режима списков и портов. Переключаются также из трея"
+        action={(
+          <Button size="sm" disabled={busy || !config} onClick={() => setNameDialog({ profile: null, name: '' })}>
+            <Plus className="size-4" />
+            Из текущих
+          </Button>
+        )}
+      />
+      <CardContent className="space-y-2 p-4!">
+        {profiles.length === 0
+          ? <p className="text-xs text-muted-foreground">Профилей пока нет. Текущие настройки можно сохранить как профиль</p>
+          : profiles.map(profile => (
+              <div key={profile.id} className="flex items-center justify-between gap-4">
+                <div className="min-w-0">
+                  <p className="truncate text-sm">{profile.name}</p>
+                  <p className="truncate text-xs text-muted-foreground">
+                    {`${profile.activeStrategies.length} стратегий, ${profile.activeFilterIds.length} фильтров, ${profile.listMode === 'ipset' ? 'только заблокированные' : 'исключения'}`}
+                  </p>
+                </div>
+                <div className="flex shrink-0 gap-2">
+                  {profile.id === activeProfileId
+                    ? (
+                        <Button variant="secondary" size="sm" disabled>
+                          <Check className="size-4" />
+                          Активен
+                        </Button>
+                      )
+                    : (
+                        <Button variant="outline" size="sm" disabled={busy} onClick={() => void handleSwitch(profile)}>
+                          Включить
+                        </Button>
+                      )}
+                  <Button
+                    variant="ghost"
+                    size="icon-sm"
+                    disabled={busy}
+                    aria-label="Переименовать профиль"
+                    onClick={() => setNameDialog({ profile, name: profile.name })}
+                  >
+                    <Pencil className="size-4" />
+                  </Button>
+                  <Button
+                    variant="ghost"
+                    size="icon-sm"
+                    disabled={busy}
+                    aria-label="Удалить профиль"
+                    onClick={() => void handleDelete(profile)}
+                  >
+                    <Trash2 className="size-4" />
+                  </Button>
+                </div>
+              </div>
+            ))}
+      </CardContent>
+
+      <Dialog open={!!nameDialog} onOpenChange={open => !open && setNameDialog(null)}>
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>{nameDialog?.profile ? 'Переименовать профиль' : 'Новый профиль'}</DialogTitle>
+            {!nameDialog?.profile && (
+              <DialogDescription>
+                В профиль попадут текущие активные стратегии, фильтры, режим списков и порты
+              </DialogDescription>
+            )}
+          </DialogHeader>
+          <div className="space-y-2 py-4">
+            <Label htmlFor="profile-name">Название профиля</Label>
+            <Input
+              id="profile-name"
+              placeholder="Например, Домашний провайдер"
+              value={nameDialog?.name ?? ''}
+              onChange={e => setNameDialog(current => current && { ...current, name: e.target.value })}
+              onKeyDown={e => e.key === 'Enter' && void handleSubmitName()}
+            />
+          </div>
+          <DialogFooter>
+            <Button variant="outline" onClick={() => setNameDialog(null)}>
+              Отмена
+            </Button>
+            <Button disabled={busy} onClick={() => void handleSubmitName()}>Сохранить</Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    </Card>
+  )
+}
@@ -1,9 +1,10 @@
-import type { DiscordPresenceActivityType } from '@/lib/types'
+import type { DerivedPorts, DiscordPresenceActivityType } from '@/lib/types'
 import type { Theme } from '@/stores/theme.store'
 import {
   AppWindow,
   ArrowLeftRight,
   BellRing,
+  Bug,
   CircleOff,
   Clapperboard,
   Download,
@@ -22,9 +23,14 @@ import {
   Router,
   SunMedium,
   Trophy,
+  Wand2,
 } from 'lucide-react'
 import { useEffect, useRef, useState } from 'react'
 import { toast } from 'sonner'
+import { BackupSection } from '@/components/features/BackupSection'
+import { ConfigHistorySection } from '@/components/features/ConfigHistorySection'
+import { PackSubscriptionsSection } from '@/components/features/PackSubscriptionsSection'
+import { ProfilesSection } from '@/components/features/ProfilesSection'
 import { MODULE_PAGE_CARD_CLASS, ModuleSectionHeader, ModuleSettingLabel } from '@/components/features/module-ui'
 import {
   AlertDialog,
@@ -57,8 +63,6 @@ import { useConfigStore } from '@/stores/config.store'
 import { useConnectionStore } from '@/stores/connection.store'
 import { useThemeStore } from '@/stores/theme.store'
 
-const RANGE_RE = /^\d+-\d+$/
-const PORT_RE = /^\d+$/
 const THEME_OPTIONS: { value: Theme, label: string, icon: React.ComponentType<{ className?: string }> }[] = [
   { value: 'system', label: 'Системная', icon: Laptop },
   { value: 'light', label: 'Светлая', icon: SunMedium },
@@ -76,27 +80,13 @@ const DISCORD_PRESENCE_SELECT_OPTIONS: { value: 'none' | DiscordPresenceActivity
   ...DISCORD_PRESENCE_ACTIVITY_OPTIONS,
 ]
 
-function isValidPortRange(value: string): boolean {
-  if (!value.trim())
-    return true
-  const parts = value.split(',').map(p => p.trim())
-  for (const part of parts) {
-    if (RANGE_RE.test(part)) {
-      const [start, end] = part.split('-').map(p => Number.parseInt(p, 10))
-      if (start < 1 || end > 65535 || start > end)
-        return false
-    }
-    else if (PORT_RE.test(part)) {
-      const port = Number.parseInt(part, 10)
-      if (port < 1 || port > 65535)
-        return false
-    }
-    else {
-      return false
-    }
-  }
-  return true
-}
+const WINWS_RESTART_LIMIT_OPTIONS: { value: number, label: string }[] = [
+  { value: 0, label: 'Не перезапускать' },
+  { value: 1, label: '1 попытка' },
+  { value: 3, label: '3 попытки' },
+  { value: 5, label: '5 попыток' },
+  { value: 10, label: '10 попыток' },
+]
 
 export function SettingsPage() {
   const [resetDialogOpen, setResetDialogOpen] = useState(false)
@@ -108,6 +98,7 @@ export function SettingsPage() {
   const prevGlobalPortsRef = useRef<string | undefined>(undefined)
   const tcpFocusedRef = useRef(false)
   const udpFocusedRef = useRef(false)
+  const [derivedPorts, setDerivedPorts] = useState<DerivedPorts | null>(null)
 
   const config = useConfigStore(state => state.config)
   const loading = useConfigStore(state => state.loading)
@@ -116,6 +107,8 @@ export function SettingsPage() {
   const scheduleSave = useConfigStore(state => state.scheduleSave)
   const setGlobalPorts = useConfigStore(state => state.setGlobalPorts)
   const setCoreFileUpdatePromptsEnabled = useConfigStore(state => state.setCoreFileUpdatePromptsEnabled)
+  const setWinwsRestartLimit = useConfigStore(state => state.setWinwsRestartLimit)
+  const setWinwsDebug = useConfigStore(state => state.setWinwsDebug)
   const setAppAutoUpdatesEnabled = useConfigStore(state => state.setAppAutoUpdatesEnabled)
   const setDiscordPresenceEnabled = useConfigStore(state => state.setDiscordPresenceEnabled)
   const setDiscordPresenceActivityType = useConfigStore(state => state.setDiscordPresenceActivityType)
@@ -189,6 +182,34 @@ export function SettingsPage() {
     }
   }, [config?.global_ports])
 
+  useEffect(() => {
+    if (!config?.global_ports.auto) {
+      return
+    }
+    void tauri.getDerivedPorts()
+      .then(setDerivedPorts)
+      .catch((error) => {
+        console.error('Failed to derive ports:', error)
+      })
+  }, [config])
+
+  const handleAutoPortsChange = async (auto: boolean) => {
+    const latestGlobalPorts = useConfigStore.getState().config?.global_ports
+    if (!latestGlobalPorts) {
+      return
+    }
+    setGlobalPorts({ ...latestGlobalPorts, auto })
+    try {
+      await saveNow()
+      addConfigLog(auto ? 'порты WinDivert вычисляются по активным стратегиям' : 'порты WinDivert заданы вручную')
+      await restartIfConnected()
+    }
+    catch (err) {
+      console.error('Failed to apply port mode change:', err)
+      toast.error('Не удалось изменить режим портов')
+    }
+  }
+
   const handleReset = async () => {
     try {
       await reset()
@@ -241,6 +262,29 @@ export function SettingsPage() {
       : 'сворачивание в трей при закрытии отключено')
   }
 
+  const handleWinwsRestartLimitChange = (limit: number) => {
+    setWinwsRestartLimit(limit)
+    scheduleSave('winws-restart-limit')
+    addConfigLog(limit > 0
+      ? `перезапуск winws.exe при сбое: до ${limit} попыток`
+      : 'перезапуск winws.exe при сбое отключён')
+  }
+
+  const handleWinwsDebugChange = async (checked: boolean) => {
+    setWinwsDebug(checked)
+    try {
+      await saveNow()
+      addConfigLog(checked
+        ? 'отладочный вывод winws.exe включён'
+        : 'отладочный вывод winws.exe отключён')
+      await restartIfConnected()
+    }
+    catch (err) {
+      console.error('Failed to apply winws debug change:', err)
+      toast.error('Не удалось изменить отладочный вывод winws')
+    }
+  }
+
   const handleCoreFileUpdatePromptsChange = (checked: boolean) => {
     setCoreFileUpdatePromptsEnabled(checked)
     scheduleSave('core-file-update-prompts')
@@ -475,6 +519,48 @@ export function SettingsPage() {
               />
             </div>
 
+            <div className="flex items-center justify-between gap-4">
+              <ModuleSettingLabel
+                htmlFor="winws-restart-limit"
+                icon={RefreshCw}
+                description="Сколько раз подряд перезапускать winws.exe после аварийного завершения"
+              >
+                Перезапуск при сбое
+              </ModuleSettingLabel>
+              <div className="w-[10.5rem]">
+                <Select
+                  value={String(config.winwsRestartLimit ?? 3)}
+                  onValueChange={value => handleWinwsRestartLimitChange(Number(value))}
+                >
+                  <SelectTrigger id="winws-restart-limit" className="w-full cursor-pointer">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    {WINWS_RESTART_LIMIT_OPTIONS.map(option => (
+                      <SelectItem key={option.value} value={String(option.value)}>
+                        {option.label}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </div>
+            </div>
+
+            <div className="flex items-center justify-between gap-4">
+              <ModuleSettingLabel
+                htmlFor="winws-debug"
+                icon={Bug}
+                description="Запускает winws.exe с --debug и собирает статистику: какой профиль обрабатывает каждый хост"
+              >
+                Отладочный вывод winws
+              </ModuleSettingLabel>
+              <Switch
+                id="winws-debug"
+                checked={config.winwsDebug ?? false}
+                onCheckedChange={checked => void handleWinwsDebugChange(checked)}
+              />
+            </div>
+
             <div className="flex items-center justify-between gap-4">
               <ModuleSettingLabel
                 htmlFor="discord-presence"
@@ -519,6 +605,31 @@ export function SettingsPage() {
             description="Глобальные порты для фильтрации трафика"
           />
           <CardContent className="space-y-4 p-4!">
+            <div className="flex items-center justify-between gap-4">
+              <ModuleSettingLabel
+                htmlFor="auto-ports"
+                icon={Wand2}
+                description="Перехватывать только порты, нужные активным стратегиям. Ручные значения ниже сохраняются"
+              >
+                Вычислять порты автоматически
+              </ModuleSettingLabel>
+              <Switch
+                id="auto-ports"
+                checked={config.global_ports.auto ?? false}
+                onCheckedChange={auto => void handleAutoPortsChange(auto)}
+              />
+            </div>
+            {config.global_ports.auto && derivedPorts && (
+              <div className="space-y-1 rounded-md border border-border/70 bg-muted/25 px-3 py-2 text-xs text-muted-foreground">
+                <p>{`TCP: ${derivedPorts.tcp || 'не перехватывается'}`}</p>
+                <p>{`UDP: ${derivedPorts.udp || 'не перехватывается'}`}</p>
+                {derivedPorts.wideProfiles.length > 0 && (
+                  <p className="text-warning">
+                    {`Весь диапазон нужен стратегиям: ${derivedPorts.wideProfiles.map(profile => `${profile.categoryName} / ${profile.strategyName}`).join(', ')}`}
+                  </p>
+                )}
+              </div>
+            )}
             <div className="flex flex-col gap-3 sm:flex-row sm:items-start sm:justify-between sm:gap-4">
               <ModuleSettingLabel
                 htmlFor="tcpPortsInput"
@@ -530,6 +641,7 @@ export function SettingsPage() {
               <div className="w-full sm:w-[11rem]">
                 <Input
                   id="tcpPortsInput"
+                  disabled={config.global_ports.auto}
                   value={tcpDraft}
                   onChange={e => setTcpDraft(e.target.value)}
                   onFocus={() => { tcpFocusedRef.current = true }}
@@ -539,20 +651,27 @@ export function SettingsPage() {
                     if (latestGlobalPorts.tcp === tcpDraft) {
                       return
                     }
-                    if (isValidPortRange(tcpDraft)) {
-                      setGlobalPorts({ ...latestGlobalPorts, tcp: tcpDraft })
-                      try {
-                        await saveNow()
-                        addConfigLog(`TCP порты изменены с ${latestGlobalPorts.tcp} на ${tcpDraft}`)
-                        await restartIfConnected()
-                      }
-                      catch (err) {
-                        console.error('Failed to apply TCP port change:', err)
-                        toast.error('Не удалось применить новые TCP порты')
-                      }
+                    let tcp: string
+                    try {
+                      tcp = await tauri.normalizePortList(tcpDraft)
+                    }
+                    catch (err) {
+                      toast.error(`Неверный формат портов: ${err}. Пример: 80,443 или 1000-2000`)
+                      return
+                    }
+                    setTcpDraft(tcp)
+                    if (latestGlobalPorts.tcp === tcp) {
+                      return
+                    }
+                    setGlobalPorts({ ...latestGlobalPorts, tcp })
+                    try {
+                      await saveNow()
+                      addConfigLog(`TCP порты изменены с ${latestGlobalPorts.tcp} на ${tcp}`)
+                      await restartIfConnected()
                     }
-                    else {
-                      toast.error('Неверный формат портов. Пример: 80,443 или 1000-2000')
+                    catch (err) {
+                      console.error('Failed to apply TCP port change:', err)
+                      toast.error('Не удалось применить новые TCP порты')
                     }
                   }}
                   placeholder="1-65535"
@@ -570,6 +689,7 @@ export function SettingsPage() {
               <div className="w-full sm:w-[11rem]">
                 <Input
                   id="udpPortsInput"
+                  disabled={config.global_ports.auto}
                   value={udpDraft}
                   onChange={e => setUdpDraft(e.target.value)}
                   onFocus={() => { udpFocusedRef.current = true }}
@@ -579,20 +699,27 @@ export function SettingsPage() {
                     if (latestGlobalPorts.udp === udpDraft) {
                       return
                     }
-                    if (isValidPortRange(udpDraft)) {
-                      setGlobalPorts({ ...latestGlobalPorts, udp: udpDraft })
-                      try {
-                        await saveNow()
-                        addConfigLog(`UDP порты изменены с ${latestGlobalPorts.udp} на ${udpDraft}`)
-                        await restartIfConnected()
-                      }
-                      catch (err) {
-                        console.error('Failed to apply UDP port change:', err)
-                        toast.error('Не удалось применить новые UDP порты')
-                      }
+                    let udp: string
+                    try {
+                      udp = await tauri.normalizePortList(udpDraft)
                     }
-                    else {
-                      toast.error('Неверный формат портов. Пример: 80,443 или 1000-2000')
+                    catch (err) {
+                      toast.error(`Неверный формат портов: ${err}. Пример: 80,443 или 1000-2000`)
+                      return
+                    }
+                    setUdpDraft(udp)
+                    if (latestGlobalPorts.udp === udp) {
+                      return
+                    }
+                    setGlobalPorts({ ...latestGlobalPorts, udp })
+                    try {
+                      await saveNow()
+                      addConfigLog(`UDP порты изменены с ${latestGlobalPorts.udp} на ${udp}`)
+                      await restartIfConnected()
+                    }
+                    catch (err) {
+                      console.error('Failed to apply UDP port change:', err)
+                      toast.error('Не удалось применить новые UDP порты')
                     }
                   }}
                   placeholder="1-65535"
@@ -602,6 +729,14 @@ export function SettingsPage() {
           </CardContent>
         </Card>
 
+        <ProfilesSection />
+
+        <PackSubscriptionsSection />
+
+        <BackupSection />
+
+        <ConfigHistorySection />
+
         <Card className={MODULE_PAGE_CARD_CLASS}>
           <ModuleSectionHeader
             icon={RotateCcw}
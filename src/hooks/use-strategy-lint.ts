@@ -0,0 +1,41 @@
+import type { StrategyDiagnostic } from '@/lib/types'
+import { useCallback, useRef, useState } from 'react'
+import * as tauri from '@/lib/tauri'
+
+const LINT_DEBOUNCE_MS = 300
+
+export function useStrategyLint() {
+  const [diagnostics, setDiagnostics] = useState<StrategyDiagnostic[]>([])
+  const timerRef = useRef<ReturnType<typeof setTimeout> | null>(null)
+  const requestRef = useRef(0)
+
+  const cancelPending = useCallback(() => {
+    if (timerRef.current) {
+      clearTimeout(timerRef.current)
+      timerRef.current = null
+    }
+    requestRef.current += 1
+  }, [])
+
+  const lint = useCallback((content: string) => {
+    cancelPending()
+    const request = requestRef.current
+    timerRef.current = setTimeout(() => {
+      timerRef.current = null
+      void tauri.lintStrategy(content)
+        .then((result) => {
+          if (request === requestRef.current) {
+            setDiagnostics(result)
+          }
+        })
+        .catch(() => {})
+    }, LINT_DEBOUNCE_MS)
+  }, [cancelPending])
+
+  const reset = useCallback(() => {
+    cancelPending()
+    setDiagnostics([])
+  }, [cancelPending])
+
+  return { diagnostics, lint, reset }
+}
@@ -85,6 +85,7 @@ export function buildRestoredCategory(currentCategory: Category, builtinCategory
 
   return {
     ...builtinCategory,
+    instanceGroup: currentCategory.instanceGroup,
     strategies: builtinCategory.strategies.map(strategy => ({
       ...strategy,
       active: activeByStrategyId.get(strategy.id) ?? false,
@@ -1,4 +1,4 @@
-import type { AppConfig, AppHealthSnapshot, DiscordPresenceActivityType, DnsLatencyResult, DnsProxyStatus, EnsureManagedFilesResult, ListMode, TgWsProxyStatus } from './types'
+import type { AppConfig, AppHealthSnapshot, BackupFileInfo, BackupRestorePreview, BatchImportResult, ConfigHistoryDiff, ConfigHistoryEntry, DebugStats, DerivedPorts, DiscordPresenceActivityType, DnsLatencyResult, DnsProxyStatus, EnsureManagedFilesResult, ListMode, PackRefreshResult, PlaceholderResolutionReport, PlaceholderStatus, ProfileIssue, ShareImportResult, SharePayload, StrategyDiagnostic, StrategyLintReport, StrategyMergeReport, TgWsProxyStatus, WinwsExitedEvent, WinwsInstanceArgs, WinwsInstanceStatus, WinwsOutputEvent, WinwsPreflightReport, WinwsReloadEvent } from './types'
 import { getVersion } from '@tauri-apps/api/app'
 import { invoke } from '@tauri-apps/api/core'
 import { listen } from '@tauri-apps/api/event'
@@ -9,6 +9,7 @@ export interface FileHealthChangedPayload {
   config_missing: boolean
   config_restored: boolean
   config_reloaded: boolean
+  config_validation_error: string | null
   restored_files: string[]
   unrecoverable_filters: string[]
 }
@@ -18,6 +19,11 @@ export const loadConfig = (): Promise<AppConfig> => invoke('load_config')
 export const getBuiltinConfig = (): Promise<AppConfig> => invoke('get_builtin_config')
 export const saveConfig = (config: AppConfig): Promise<void> => invoke('save_config', { config })
 export const resetConfig = (): Promise<AppConfig> => invoke('reset_config')
+export const getBuiltinMergeConflicts = (): Promise<StrategyMergeReport[]> => invoke('get_builtin_merge_conflicts')
+export const keepUserStrategyContent = (categoryId: string, strategyId: string): Promise<AppConfig> => invoke('keep_user_strategy_content', { categoryId, strategyId })
+export const addPackSubscription = (url: string, publicKey: string | null): Promise<AppConfig> => invoke('add_pack_subscription', { url, publicKey })
+export const removePackSubscription = (id: string): Promise<AppConfig> => invoke('remove_pack_subscription', { id })
+export const refreshPackSubscriptions = (force = false): Promise<PackRefreshResult[]> => invoke('refresh_pack_subscriptions', { force })
 export const getResourcesDirectory = (): Promise<string> => invoke('get_resources_directory')
 export const getAppHealthSnapshot = (forceRemoteUpdates = false): Promise<AppHealthSnapshot> => invoke('get_app_health_snapshot', { forceRemoteUpdates })
 export const ensureManagedFiles = (): Promise<EnsureManagedFilesResult> => invoke('ensure_managed_files')
@@ -25,15 +31,47 @@ export const restoreHashesFromDisk = (): Promise<void> => invoke('restore_hashes
 export const downloadBinaries = async (forceAll = false): Promise<void> => invoke('download_binaries', { forceAll })
 export const applyCoreFileUpdates = async (): Promise<void> => invoke('apply_core_file_updates')
 export const refreshListsIfStale = (): Promise<string[]> => invoke('refresh_lists_if_stale')
-export const startWinws = (args: string[], tcpPorts: string, udpPorts: string): Promise<number> => invoke('start_winws', { args, tcpPorts, udpPorts })
+export const getPlaceholderReport = (): Promise<PlaceholderResolutionReport> => invoke('get_placeholder_report')
+export const getPlaceholderStatuses = (): Promise<PlaceholderStatus[]> => invoke('get_placeholder_statuses')
+export const getDynamicPlaceholders = (): Promise<PlaceholderStatus[]> => invoke('get_dynamic_placeholders')
+export const getConfigSchema = (): Promise<Record<string, unknown>> => invoke('get_config_schema')
+export const getWinwsArgs = (): Promise<WinwsInstanceArgs[]> => invoke('get_winws_args')
+export const lintStrategy = (content: string): Promise<StrategyDiagnostic[]> => invoke('lint_strategy', { content })
+export const lintActiveStrategies = (): Promise<StrategyLintReport[]> => invoke('lint_active_strategies')
+export const analyzeActiveProfiles = (): Promise<ProfileIssue[]> => invoke('analyze_active_profiles')
+export const getDerivedPorts = (): Promise<DerivedPorts> => invoke('get_derived_ports')
+export const normalizePortList = (value: string): Promise<string> => invoke('normalize_port_list', { value })
+export const getDebugStats = (): Promise<DebugStats> => invoke('get_debug_stats')
+export const resetDebugStats = (): Promise<void> => invoke('reset_debug_stats')
+export const encodeShareCode = (categoryId: string, strategyId?: string): Promise<string> => invoke('encode_share_code', { categoryId, strategyId: strategyId ?? null })
+export const decodeShareCode = (code: string): Promise<SharePayload> => invoke('decode_share_code', { code })
+export const exportWinwsScript = (): Promise<string> => invoke('export_winws_script')
+export const importBatchPreset = (fileName: string, content: string): Promise<BatchImportResult> => invoke('import_batch_preset', { fileName, content })
+export const importShareCode = (code: string, categoryId: string | null): Promise<ShareImportResult> => invoke('import_share_code', { code, categoryId })
+export const startWinws = (): Promise<number> => invoke('start_winws')
+export const preflightWinws = (): Promise<WinwsPreflightReport> => invoke('preflight_winws')
 export const stopWinws = (): Promise<void> => invoke('stop_winws')
+export const reloadWinws = (): Promise<WinwsReloadEvent[]> => invoke('reload_winws')
+export const restartWinwsInstance = (instanceGroup: string): Promise<number> => invoke('restart_winws_instance', { instanceGroup })
 export const isWinwsRunning = (): Promise<boolean> => invoke('is_winws_running')
 export const killWindivertService = (): Promise<void> => invoke('kill_windivert_service')
 export const getRunningPid = (): Promise<number> => invoke('get_running_pid')
+export const getWinwsInstances = (): Promise<WinwsInstanceStatus[]> => invoke('get_winws_instances')
 export const checkAndRecoverOrphan = (): Promise<number | null> => invoke('check_and_recover_orphan')
 export const openAppDirectory = (): Promise<void> => invoke('open_app_directory')
 export const openFiltersDirectory = (): Promise<void> => invoke('open_filters_directory')
-export const getFiltersPath = (): Promise<string> => invoke('get_filters_path')
+export const createBackup = (): Promise<BackupFileInfo> => invoke('create_backup')
+export const listBackups = (): Promise<BackupFileInfo[]> => invoke('list_backups')
+export const previewBackupRestore = (path: string): Promise<BackupRestorePreview> => invoke('preview_backup_restore', { path })
+export const restoreBackup = (path: string): Promise<AppConfig> => invoke('restore_backup', { path })
+export const openBackupsDirectory = (): Promise<void> => invoke('open_backups_directory')
+export const listConfigHistory = (): Promise<ConfigHistoryEntry[]> => invoke('list_config_history')
+export const diffConfigHistory = (id: string): Promise<ConfigHistoryDiff> => invoke('diff_config_history', { id })
+export const restoreConfigHistory = (id: string): Promise<AppConfig> => invoke('restore_config_history', { id })
+export const switchProfile = (id: string): Promise<AppConfig> => invoke('switch_profile', { id })
+export const createProfile = (name: string): Promise<AppConfig> => invoke('create_profile', { name })
+export const renameProfile = (id: string, name: string): Promise<AppConfig> => invoke('rename_profile', { id, name })
+export const deleteProfile = (id: string): Promise<AppConfig> => invoke('delete_profile', { id })
 export const getReservedFilterFilenames = (): Promise<string[]> => invoke('get_reserved_filter_filenames')
 export const isAutostartEnabled = (): Promise<boolean> => invoke('is_autostart_enabled')
 export const setAutostartEnabled = (enabled: boolean): Promise<void> => invoke('set_autostart_enabled', { enabled })
@@ -62,10 +100,6 @@ export function deleteFilterFile(filename: string): Promise<void> {
   return invoke('delete_filter_file', { filename })
 }
 
-export function resolvePlaceholders(content: string, placeholders: { name: string, path: string }[]): Promise<string> {
-  return invoke('resolve_placeholders', { content, placeholders })
-}
-
 export const checkTcpTimestamps = (): Promise<boolean> => invoke('check_tcp_timestamps')
 export const enableTcpTimestamps = (): Promise<void> => invoke('enable_tcp_timestamps')
 export const setConnectedState = (connected: boolean): Promise<void> => invoke('set_connected_state', { connected })
@@ -106,6 +140,26 @@ export function onListModeChanged(callback: (mode: ListMode) => void): (() => vo
   return createAsyncListener<ListMode>('list-mode-changed', callback)
 }
 
+export function onProfileChanged(callback: (profileName: string) => void): (() => void) {
+  return createAsyncListener<string>('profile-changed', callback)
+}
+
+export function onProfileSwitchError(callback: (message: string) => void): (() => void) {
+  return createAsyncListener<string>('profile-switch-error', callback)
+}
+
+export function onWinwsOutput(callback: (event: WinwsOutputEvent) => void): (() => void) {
+  return createAsyncListener<WinwsOutputEvent>('winws-output', callback)
+}
+
+export function onWinwsExited(callback: (event: WinwsExitedEvent) => void): (() => void) {
+  return createAsyncListener<WinwsExitedEvent>('winws-exited', callback)
+}
+
+export function onWinwsReloaded(callback: (event: WinwsReloadEvent) => void): (() => void) {
+  return createAsyncListener<WinwsReloadEvent>('winws-reloaded', callback)
+}
+
 export function onFilesHealthChanged(callback: (payload: FileHealthChangedPayload) => void): (() => void) {
   return createAsyncListener<FileHealthChangedPayload>('files-health-changed', callback)
 }
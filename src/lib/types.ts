@@ -1,6 +1,7 @@
 export interface GlobalPorts {
   tcp: string
   udp: string
+  auto?: boolean
 }
 
 export interface DownloadProgress {
@@ -42,7 +43,15 @@ export interface TgWsProxyStatus {
   pid?: number | null
 }
 
-export interface Strategy {
+export interface StrategyMetadata {
+  description?: string
+  tags?: string[]
+  targets?: string[]
+  author?: string
+  lastVerified?: string
+}
+
+export interface Strategy extends StrategyMetadata {
   id: string
   name: string
   content: string
@@ -50,6 +59,7 @@ export interface Strategy {
   system?: boolean
   systemBaseName?: string
   systemBaseContent?: string
+  systemBaseMetadata?: StrategyMetadata
 }
 
 export interface Category {
@@ -58,14 +68,20 @@ export interface Category {
   strategies: Strategy[]
   system?: boolean
   systemBaseName?: string
+  sourceId?: string
+  instanceGroup?: string
 }
 
+export type PlaceholderKind = 'path' | 'string' | 'integer' | 'hex' | 'domainList'
+
 export interface Placeholder {
   name: string
   path: string
+  kind?: PlaceholderKind
   system?: boolean
   systemBaseName?: string
   systemBasePath?: string
+  sourceId?: string
 }
 
 export interface Filter {
@@ -85,13 +101,41 @@ export type ConnectionStatus = 'disconnected' | 'connecting' | 'connected' | 'di
 export type DiscordPresenceActivityType = 'playing' | 'listening' | 'watching' | 'competing'
 export type ListMode = 'exclude' | 'ipset'
 
+export interface ProfileStrategyRef {
+  categoryId: string
+  strategyId: string
+}
+
+export interface StrategyProfile {
+  id: string
+  name: string
+  activeStrategies: ProfileStrategyRef[]
+  activeFilterIds: string[]
+  listMode: ListMode
+  globalPorts: GlobalPorts
+}
+
+export interface PackSubscription {
+  id: string
+  url: string
+  name: string
+  publicKey?: string
+  version?: string
+  lastUpdatedAt?: number
+  lastError?: string
+}
+
 export interface AppConfig {
+  schemaVersion?: number
   global_ports: GlobalPorts
   categories: Category[]
   placeholders: Placeholder[]
   filters: Filter[]
   binaries_path: string
   listMode?: ListMode
+  profiles?: StrategyProfile[]
+  activeProfileId?: string
+  packSubscriptions?: PackSubscription[]
   dnsPresetId?: string
   dnsBootstrapResolvers?: string[]
   dnsAcceleratorEnabled?: boolean
@@ -106,6 +150,8 @@ export interface AppConfig {
   connectOnAutostart?: boolean
   coreFileUpdatePromptsEnabled?: boolean
   appAutoUpdatesEnabled?: boolean
+  winwsRestartLimit?: number
+  winwsDebug?: boolean
   systemRemovedCategoryIds?: string[]
   systemRemovedStrategyKeys?: string[]
   systemRemovedPlaceholderNames?: string[]
@@ -117,5 +163,268 @@ export interface EnsureManagedFilesResult {
   restored_files: string[]
   config_restored: boolean
   config_reloaded: boolean
+  config_validation_error: string | null
   unrecoverable_filters: string[]
 }
+
+export type StrategyDiagnosticSeverity = 'error' | 'warning'
+
+export type StrategyDiagnosticCode = 'notAnOption' | 'unknownOption' | 'globalOption' | 'profileSeparator' | 'missingValue' | 'unexpectedValue' | 'invalidValue' | 'duplicateOption' | 'missingDependency' | 'nonCanonicalPorts'
+
+export interface StrategyDiagnostic {
+  line: number
+  severity: StrategyDiagnosticSeverity
+  code: StrategyDiagnosticCode
+  option?: string | null
+  message: string
+}
+
+export interface StrategyLintReport {
+  categoryId: string
+  categoryName: string
+  strategyId: string
+  strategyName: string
+  diagnostics: StrategyDiagnostic[]
+}
+
+export type PlaceholderIssueKind = 'unknown' | 'missingTarget' | 'emptyTarget' | 'invalidValue'
+
+export interface AffectedStrategy {
+  categoryId: string
+  categoryName: string
+  strategyId: string
+  strategyName: string
+}
+
+export interface PlaceholderIssue {
+  name: string
+  kind: PlaceholderIssueKind
+  resolvedPath?: string | null
+  strategies: AffectedStrategy[]
+  message: string
+}
+
+export type ProfileIssueKind = 'shadowed' | 'duplicate' | 'uncoveredPorts'
+
+export interface ProfileSource {
+  position: number
+  instanceGroup: string
+  categoryId: string
+  categoryName: string
+  strategyId: string
+  strategyName: string
+}
+
+export interface ProfileIssue {
+  kind: ProfileIssueKind
+  profile: ProfileSource
+  shadowedBy: ProfileSource | null
+  message: string
+}
+
+export interface MergeConflict {
+  base: string[]
+  user: string[]
+  builtin: string[]
+}
+
+export interface StrategyMergeReport {
+  categoryId: string
+  categoryName: string
+  strategyId: string
+  strategyName: string
+  conflicts: MergeConflict[]
+}
+
+export interface SharedStrategy extends StrategyMetadata {
+  name: string
+  content: string
+}
+
+export interface SharedPlaceholder {
+  name: string
+  path: string
+}
+
+export interface SharePayload {
+  categoryName?: string
+  strategies: SharedStrategy[]
+  placeholders: SharedPlaceholder[]
+}
+
+export interface ShareImportResult {
+  config: AppConfig
+  categoryId: string
+  strategyIds: string[]
+  addedPlaceholders: string[]
+  unresolvedPlaceholders: string[]
+}
+
+export interface BatchFileReference {
+  profile: number
+  option: string
+  value: string
+}
+
+export interface BatchImportResult {
+  config: AppConfig
+  categoryId: string
+  strategyIds: string[]
+  wfTcp: string | null
+  wfUdp: string | null
+  skippedOptions: string[]
+  unmappedFiles: BatchFileReference[]
+}
+
+export interface PackRefreshResult {
+  id: string
+  name: string
+  updated: boolean
+  error?: string
+}
+
+export interface PlaceholderStatus {
+  name: string
+  resolved: string
+  issue?: PlaceholderIssueKind
+}
+
+export interface DerivedPorts {
+  tcp: string
+  udp: string
+  wideProfiles: ProfileSource[]
+}
+
+export interface ProfileDebugStats {
+  instanceGroup: string
+  profile: number
+  source: ProfileSource | null
+  matches: number
+  actions: Record<string, number>
+}
+
+export interface HostDebugStats {
+  instanceGroup: string
+  host: string
+  searches: number
+  l7: string[]
+  profiles: Record<number, number>
+  hostlistPositive: number
+  hostlistNegative: number
+}
+
+export interface DebugStats {
+  events: number
+  profiles: ProfileDebugStats[]
+  hosts: HostDebugStats[]
+}
+
+export interface PreflightFailure {
+  instanceGroup: string
+  profile: ProfileSource | null
+  output: string
+}
+
+export interface WinwsPreflightReport {
+  failures: PreflightFailure[]
+}
+
+export type WinwsReloadOutcome = 'unchanged' | 'started' | 'restarted' | 'stopped' | 'rolledBack' | 'failed'
+
+export interface WinwsExitedEvent {
+  instanceGroup: string
+  pid: number
+  exitCode: number | null
+  outputTail: string[]
+  restarting: boolean
+}
+
+export interface WinwsReloadEvent {
+  instanceGroup: string
+  outcome: WinwsReloadOutcome
+  pid: number | null
+  addedArgs: string[]
+  removedArgs: string[]
+  error: string | null
+  connected: boolean
+}
+
+export interface WinwsOutputEvent {
+  instanceGroup: string
+  line: string
+}
+
+export interface WinwsInstanceStatus {
+  instanceGroup: string
+  pid: number
+  running: boolean
+}
+
+export interface WinwsInstanceArgs {
+  instanceGroup: string
+  args: string[]
+}
+
+export interface PlaceholderResolutionReport {
+  issues: PlaceholderIssue[]
+}
+
+export type BackupEntryKind = 'config' | 'list' | 'filter' | 'fake'
+export type RestoreAction = 'create' | 'replace' | 'unchanged'
+
+export interface BackupEntry {
+  path: string
+  kind: BackupEntryKind
+  size: number
+  sha256: string
+}
+
+export interface BackupManifest {
+  format: string
+  formatVersion: number
+  appVersion: string
+  createdAt: number
+  entries: BackupEntry[]
+}
+
+export interface BackupFileInfo {
+  path: string
+  fileName: string
+  size: number
+  modifiedAt: number
+}
+
+export interface RestorePreviewEntry {
+  path: string
+  kind: BackupEntryKind
+  action: RestoreAction
+  targetPath: string
+}
+
+export interface BackupRestorePreview {
+  path: string
+  manifest: BackupManifest
+  entries: RestorePreviewEntry[]
+}
+
+export type ConfigChangeKind = 'added' | 'removed' | 'modified'
+
+export interface ConfigHistoryEntry {
+  id: string
+  createdAt: number
+  size: number
+}
+
+export interface ConfigItemChange {
+  id: string
+  name: string
+  change: ConfigChangeKind
+  fields: string[]
+}
+
+export interface ConfigHistoryDiff {
+  categories: ConfigItemChange[]
+  strategies: ConfigItemChange[]
+  placeholders: ConfigItemChange[]
+  filters: ConfigItemChange[]
+}
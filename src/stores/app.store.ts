@@ -132,6 +132,28 @@ export const useAppStore = create<AppStore>((set, get) => ({
       window.clearTimeout(listRefreshToastTimer)
     }
 
+    if (useConfigStore.getState().config?.packSubscriptions?.length) {
+      try {
+        // Packs are merged into the persisted config, so flush pending edits first.
+        await useConfigStore.getState().saveNow()
+        const results = await tauri.refreshPackSubscriptions()
+        for (const result of results) {
+          if (result.error) {
+            useConnectionStore.getState().addConfigLog(`не удалось обновить пакет "${result.name}": ${result.error}`)
+          }
+          else if (result.updated) {
+            useConnectionStore.getState().addConfigLog(`пакет стратегий "${result.name}" обновлён`)
+          }
+        }
+        if (results.length > 0) {
+          await useConfigStore.getState().reload()
+        }
+      }
+      catch (e) {
+        useConnectionStore.getState().addLog(`Не удалось обновить пакеты стратегий: ${e}`)
+      }
+    }
+
     const snapshot = await tauri.getAppHealthSnapshot(true)
     if (get().refreshVersion === version) {
       get().applyHealthSnapshot(snapshot)
@@ -199,6 +221,11 @@ export const useAppStore = create<AppStore>((set, get) => ({
         useConnectionStore.getState().addLog('Проверяю и восстанавливаю управляемые файлы')
         try {
           const ensured = await tauri.ensureManagedFiles()
+          if (ensured.config_validation_error) {
+            const message = `config.json содержит ошибку и не применён, используется последняя загруженная конфигурация: ${ensured.config_validation_error}`
+            useConnectionStore.getState().addLog(message)
+            toast.error(message)
+          }
           if (ensured.config_restored) {
             useConnectionStore.getState().addLog('config.json был автоматически восстановлен из дефолтного конфига')
           }
@@ -259,7 +286,7 @@ export const useAppStore = create<AppStore>((set, get) => ({
         })
 
         if (!get().filesWatcherCleanup) {
-          const offHealthChanged = tauri.onFilesHealthChanged(({ binaries_ok, lists_changed, config_missing, config_restored, config_reloaded, restored_files, unrecoverable_filters }) => {
+          const offHealthChanged = tauri.onFilesHealthChanged(({ binaries_ok, lists_changed, config_missing, config_restored, config_reloaded, config_validation_error, restored_files, unrecoverable_filters }) => {
             const previousState = get().binariesOk
             set({ configMissing: config_missing })
 
@@ -269,6 +296,12 @@ export const useAppStore = create<AppStore>((set, get) => ({
               })
             }
 
+            if (config_validation_error) {
+              const message = `config.json содержит ошибку и не применён, используется последняя загруженная конфигурация: ${config_validation_error}`
+              useConnectionStore.getState().addLog(message)
+              toast.error(message)
+            }
+
             if (config_restored) {
               useConnectionStore.getState().addLog('Watcher восстановил config.json из дефолтного конфига')
             }
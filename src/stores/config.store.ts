@@ -1,4 +1,4 @@
-import type { AppConfig, Category, DiscordPresenceActivityType, Filter, GlobalPorts, ListMode, Placeholder, Strategy } from '../lib/types'
+import type { AppConfig, Category, DiscordPresenceActivityType, Filter, GlobalPorts, ListMode, Placeholder, PlaceholderKind, Strategy } from '../lib/types'
 import { create } from 'zustand'
 import * as tauri from '../lib/tauri'
 import { reportAutosaveError, resetAutosaveErrorReporter } from './autosave-error-reporter'
@@ -41,12 +41,15 @@ interface ConfigStore {
   setDiscordPresenceEnabled: (enabled: boolean) => void
   setDiscordPresenceActivityType: (activityType: DiscordPresenceActivityType) => void
   setCoreFileUpdatePromptsEnabled: (enabled: boolean) => void
+  setWinwsRestartLimit: (limit: number) => void
+  setWinwsDebug: (enabled: boolean) => void
   setAppAutoUpdatesEnabled: (enabled: boolean) => void
   setMinimizeToTray: (enabled: boolean) => void
   setLaunchToTray: (enabled: boolean) => void
   setConnectOnAutostart: (enabled: boolean) => void
   addCategory: (name: string) => void
   updateCategory: (id: string, name: string) => void
+  setCategoryInstanceGroup: (id: string, instanceGroup: string | null) => void
   restoreBuiltinCategory: (categoryId: string, category: Category) => void
   deleteCategory: (id: string) => void
   reorderCategories: (oldIndex: number, newIndex: number) => void
@@ -57,8 +60,8 @@ interface ConfigStore {
   setActiveStrategy: (categoryId: string, strategyId: string) => void
   clearActiveStrategy: (categoryId: string, strategyId: string) => void
   clearAllActiveStrategies: (categoryId: string) => void
-  addPlaceholder: (name: string, path: string) => void
-  updatePlaceholder: (index: number, name: string, path: string) => void
+  addPlaceholder: (name: string, path: string, kind?: PlaceholderKind) => void
+  updatePlaceholder: (index: number, name: string, path: string, kind?: PlaceholderKind) => void
   deletePlaceholder: (index: number) => void
   setPlaceholders: (placeholders: Placeholder[]) => void
   replacePlaceholdersState: (placeholders: Placeholder[], removedPlaceholderNames?: string[]) => void
@@ -310,6 +313,18 @@ export const useConfigStore = create<ConfigStore>((set, get) => ({
       set({ config: { ...config, coreFileUpdatePromptsEnabled: enabled }, dirty: true })
   },
 
+  setWinwsRestartLimit: (limit) => {
+    const { config } = get()
+    if (config)
+      set({ config: { ...config, winwsRestartLimit: limit }, dirty: true })
+  },
+
+  setWinwsDebug: (enabled) => {
+    const { config } = get()
+    if (config)
+      set({ config: { ...config, winwsDebug: enabled }, dirty: true })
+  },
+
   setAppAutoUpdatesEnabled: (enabled) => {
     const { config } = get()
     if (config)
@@ -357,6 +372,16 @@ export const useConfigStore = create<ConfigStore>((set, get) => ({
     }
   },
 
+  setCategoryInstanceGroup: (id, instanceGroup) => {
+    const { config } = get()
+    if (config) {
+      const categories = config.categories.map(c =>
+        c.id === id ? { ...c, instanceGroup: instanceGroup || undefined } : c,
+      )
+      set({ config: { ...config, categories }, dirty: true })
+    }
+  },
+
   restoreBuiltinCategory: (categoryId, category) => {
     const { config } = get()
     if (config) {
@@ -536,10 +561,10 @@ export const useConfigStore = create<ConfigStore>((set, get) => ({
     }
   },
 
-  addPlaceholder: (name, path) => {
+  addPlaceholder: (name, path, kind = 'path') => {
     const { config } = get()
     if (config) {
-      const newPlaceholder: Placeholder = { name, path, system: false }
+      const newPlaceholder: Placeholder = { name, path, kind: kind === 'path' ? undefined : kind, system: false }
       set({
         config: { ...config, placeholders: [...config.placeholders, newPlaceholder] },
         dirty: true,
@@ -547,11 +572,11 @@ export const useConfigStore = create<ConfigStore>((set, get) => ({
     }
   },
 
-  updatePlaceholder: (index, name, path) => {
+  updatePlaceholder: (index, name, path, kind = 'path') => {
     const { config } = get()
     if (config) {
       const placeholders = [...config.placeholders]
-      placeholders[index] = { ...placeholders[index], name, path }
+      placeholders[index] = { ...placeholders[index], name, path, kind: kind === 'path' ? undefined : kind }
       set({ config: { ...config, placeholders }, dirty: true })
     }
   },
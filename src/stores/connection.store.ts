@@ -2,7 +2,6 @@ import type { AppConfig, ConnectionStatus } from '../lib/types'
 import { toast } from 'sonner'
 import { create } from 'zustand'
 import { applyDnsAccelerator, DNS_PRESETS, normalizeDnsPresetId } from '../lib/dns'
-import { buildFiltersCommand, buildFiltersCommandArray, buildStrategyCommand } from '../lib/strategy'
 import * as tauri from '../lib/tauri'
 import { isValidTgWsProxySecret, normalizeTgWsProxySecret } from '../lib/tg-ws-proxy'
 import { setAutosaveErrorLogger } from './autosave-error-reporter'
@@ -26,6 +25,14 @@ async function ensureMinimumTransition(startedAt: number) {
   }
 }
 
+// Categories without an instance group run in the default winws.exe, which
+// keeps its plain name in the log.
+const DEFAULT_INSTANCE_GROUP = 'main'
+
+function winwsLabel(instanceGroup: string) {
+  return instanceGroup === DEFAULT_INSTANCE_GROUP ? 'winws.exe' : `winws.exe [${instanceGroup}]`
+}
+
 function getSelectedDnsPreset(config: AppConfig) {
   const presetId = normalizeDnsPresetId(config.dnsPresetId)
   return DNS_PRESETS.find(preset => preset.id === presetId) ?? DNS_PRESETS[0]
@@ -217,36 +224,52 @@ export const useConnectionStore = create<ConnectionStore>((set, get) => ({
     set({ status: 'connecting', error: null })
     get().addLog('Начинаю подключение')
     get().addLog(`Режим списков: ${config.listMode}`)
-    get().addLog(`Порты: TCP ${config.global_ports.tcp}, UDP ${config.global_ports.udp}`)
+    if (!config.global_ports.auto) {
+      get().addLog(`Порты: TCP ${config.global_ports.tcp}, UDP ${config.global_ports.udp}`)
+    }
 
     try {
-      get().addLog('Получаю каталог фильтров')
-      const filtersDir = await tauri.getFiltersPath()
+      if (useConfigStore.getState().dirty) {
+        get().addLog('Сохраняю несохранённые изменения конфигурации')
+        await useConfigStore.getState().saveNow()
+      }
 
-      const filtersCommand = buildFiltersCommand(config.filters, filtersDir)
-      const filtersArgs = buildFiltersCommandArray(config.filters, filtersDir)
-      const strategyCommand = buildStrategyCommand(config)
-      get().addLog('Разрешаю плейсхолдеры стратегии')
-      const processedStrategy = await tauri.resolvePlaceholders(strategyCommand, config.placeholders)
+      if (config.global_ports.auto) {
+        const derivedPorts = await tauri.getDerivedPorts()
+        get().addLog(`Порты по активным стратегиям: TCP ${derivedPorts.tcp || 'не перехватываются'}, UDP ${derivedPorts.udp || 'не перехватываются'}`)
+      }
 
-      let fullCommand = `winws.exe --wf-tcp=${config.global_ports.tcp} --wf-udp=${config.global_ports.udp}`
-      if (filtersCommand) {
-        fullCommand += ` ${filtersCommand.replace(/\n/g, ' ')}`
+      const lintReports = await tauri.lintActiveStrategies()
+      for (const report of lintReports) {
+        for (const diagnostic of report.diagnostics) {
+          const level = diagnostic.severity === 'error' ? 'Ошибка' : 'Предупреждение'
+          get().addLog(`${level} в стратегии «${report.categoryName} / ${report.strategyName}», строка ${diagnostic.line}: ${diagnostic.message}`)
+        }
       }
-      fullCommand += ` ${processedStrategy.replace(/\n/g, ' ')}`
 
-      const strategyArgs = processedStrategy.split('\n').filter(Boolean)
-      const allArgs = [...filtersArgs, ...strategyArgs]
+      const profileIssues = await tauri.analyzeActiveProfiles()
+      for (const issue of profileIssues) {
+        get().addLog(`Предупреждение: ${issue.message}`)
+      }
 
-      get().addLog(`Подготовлено аргументов запуска: ${allArgs.length}`)
-      get().addLog(fullCommand)
-      get().addLog('Запускаю winws.exe')
+      const placeholderReport = await tauri.getPlaceholderReport()
+      if (placeholderReport.issues.length > 0) {
+        for (const issue of placeholderReport.issues) {
+          get().addLog(`Ошибка плейсхолдера ${issue.message}`)
+        }
+        throw new Error('стратегии ссылаются на неразрешённые плейсхолдеры')
+      }
 
-      const pid = await tauri.startWinws(
-        allArgs,
-        config.global_ports.tcp,
-        config.global_ports.udp,
-      )
+      get().addLog('Собираю аргументы запуска winws.exe')
+      const instances = await tauri.getWinwsArgs()
+
+      for (const { instanceGroup, args } of instances) {
+        get().addLog(`Подготовлено аргументов запуска ${winwsLabel(instanceGroup)}: ${args.length}`)
+        get().addLog(`${winwsLabel(instanceGroup)} ${args.join(' ')}`)
+      }
+      get().addLog(instances.length > 1 ? `Запускаю процессы winws.exe: ${instances.length}` : 'Запускаю winws.exe')
+
+      const pid = await tauri.startWinws()
       await ensureMinimumTransition(transitionStartedAt)
       set({ status: 'connected', pid })
       get().updateTrayState(true)
@@ -352,18 +375,15 @@ export const useConnectionStore = create<ConnectionStore>((set, get) => ({
       const toastId = toast.loading('Применяю изменения подключения...')
       const runCycle = async (): Promise<void> => {
         set({ pendingRestart: false })
-        get().addLog('Конфигурация подключения изменена, перезапускаю winws.exe')
-
-        // react-doctor-disable-next-line react-doctor/async-defer-await
-        await get().disconnect()
-        if (get().status !== 'disconnected') {
-          throw new Error('Не удалось остановить текущее подключение')
-        }
 
-        // react-doctor-disable-next-line react-doctor/async-defer-await
-        await get().connect()
-        if (get().status !== 'connected') {
-          throw new Error('Подключение не восстановилось после перезапуска')
+        // The backend restarts winws.exe only if its arguments changed and
+        // falls back to the previous ones when the new set fails to start,
+        // separately for every instance group; pid and status follow from
+        // the winws-reloaded events.
+        const results = await tauri.reloadWinws()
+        const failed = results.find(result => result.outcome === 'rolledBack' || result.outcome === 'failed')
+        if (failed) {
+          throw new Error(failed.error ?? `${winwsLabel(failed.instanceGroup)} не запустился с новыми аргументами`)
         }
 
         if (get().pendingRestart) {
@@ -449,9 +469,92 @@ export const useConnectionStore = create<ConnectionStore>((set, get) => ({
       get().addLog('Получена команда переключения из трея')
       get().toggle()
     })
+    // Profile switches come from both the tray and the settings page; the
+    // backend has already persisted them, so reload and reconnect from here.
+    const unlistenProfile = tauri.onProfileChanged((profileName) => {
+      get().addConfigLog(`выбран профиль "${profileName}"`)
+      void useConfigStore.getState().reload()
+        .then(() => get().restartIfConnected())
+        .catch((error) => {
+          get().addLog(`Ошибка применения профиля: ${error instanceof Error ? error.message : String(error)}`)
+        })
+    })
+    const unlistenProfileError = tauri.onProfileSwitchError((message) => {
+      toast.error(`Не удалось переключить профиль: ${message}`)
+    })
+    // With several instance groups the shown PID is the first still running
+    // winws.exe, so it is re-read whenever one of them exits or restarts.
+    const refreshPid = () => {
+      void tauri.getRunningPid()
+        .then(pid => set({ pid: pid || null }))
+        .catch(() => set({ pid: null }))
+    }
+    const unlistenWinwsOutput = tauri.onWinwsOutput((event) => {
+      get().addLog(`${winwsLabel(event.instanceGroup)}: ${event.line}`)
+    })
+    const unlistenWinwsExit = tauri.onWinwsExited((event) => {
+      const exitCode = event.exitCode === null ? 'без кода' : `код ${event.exitCode}`
+      const message = `${winwsLabel(event.instanceGroup)} (PID: ${event.pid}) неожиданно завершился, ${exitCode}`
+      get().addLog(message)
+      refreshPid()
+      toast.error(event.restarting ? `${message}. Перезапускаю...` : message)
+    })
+    const unlistenWinwsReload = tauri.onWinwsReloaded((event) => {
+      const label = winwsLabel(event.instanceGroup)
+      const changes = [
+        ...event.addedArgs.map(arg => `+${arg}`),
+        ...event.removedArgs.map(arg => `-${arg}`),
+      ].join(' ')
+      if (event.outcome === 'restarted') {
+        refreshPid()
+        get().addLog(changes
+          ? `Аргументы ${label} изменились, процесс перезапущен (PID: ${event.pid}): ${changes}`
+          : `${label} перезапущен (PID: ${event.pid})`)
+        return
+      }
+      if (event.outcome === 'started') {
+        refreshPid()
+        get().addLog(`${label} запущен (PID: ${event.pid})`)
+        return
+      }
+      if (event.outcome === 'stopped') {
+        refreshPid()
+        get().addLog(`${label} остановлен: в группе не осталось активных стратегий`)
+        return
+      }
+
+      const externalChange = !restartPromise
+      if (event.outcome === 'rolledBack') {
+        refreshPid()
+        const message = `Новые аргументы ${label} не применены (${event.error}), работают прежние`
+        get().addLog(message)
+        if (externalChange) {
+          toast.error(message)
+        }
+        return
+      }
+
+      const message = `Не удалось перезапустить ${label}: ${event.error}`
+      get().addLog(message)
+      if (event.connected) {
+        refreshPid()
+      }
+      else {
+        set({ status: 'error', pid: null, error: event.error })
+        get().updateTrayState(false)
+      }
+      if (externalChange) {
+        toast.error(message)
+      }
+    })
     trayListenerCleanup = () => {
       try {
         unlisten()
+        unlistenProfile()
+        unlistenProfileError()
+        unlistenWinwsOutput()
+        unlistenWinwsExit()
+        unlistenWinwsReload()
       }
       finally {
         trayListenerCleanup = null